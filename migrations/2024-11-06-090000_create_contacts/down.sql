@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE accounts
+DROP COLUMN IF EXISTS allow_messages_from_strangers;
+
+DROP TABLE IF EXISTS contact_requests;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE contact_requests (
+    id SERIAL PRIMARY KEY,
+    requester_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    addressee_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'accepted', 'declined')),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (requester_id <> addressee_id),
+    UNIQUE(requester_id, addressee_id)
+);
+
+CREATE INDEX index_contact_requests_on_requester_id ON contact_requests (requester_id);
+CREATE INDEX index_contact_requests_on_addressee_id ON contact_requests (addressee_id);
+
+ALTER TABLE accounts
+    ADD COLUMN allow_messages_from_strangers BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS moderation_actions;
+DROP TABLE IF EXISTS user_suspensions;
+DROP TABLE IF EXISTS message_reports;
+
+ALTER TABLE messages
+DROP COLUMN IF EXISTS is_hidden;
@@ -0,0 +1,47 @@
+-- Your SQL goes here
+ALTER TABLE messages
+    ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE message_reports (
+    id SERIAL PRIMARY KEY,
+    message_id INTEGER NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    reporter_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason VARCHAR(30) NOT NULL
+        CHECK (reason IN ('spam', 'harassment', 'hate_speech', 'sexual_content', 'violence', 'other')),
+    details TEXT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'actioned', 'dismissed')),
+    resolved_by INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(message_id, reporter_id)
+);
+
+CREATE INDEX index_message_reports_on_status ON message_reports (status);
+CREATE INDEX index_message_reports_on_message_id ON message_reports (message_id);
+
+CREATE TABLE user_suspensions (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    suspended_by INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT NULL,
+    suspended_until TIMESTAMP NULL,
+    lifted_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_user_suspensions_on_user_id ON user_suspensions (user_id);
+
+-- Append-only trail of every moderator decision
+CREATE TABLE moderation_actions (
+    id SERIAL PRIMARY KEY,
+    moderator_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(30) NOT NULL,
+    target_user_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    target_message_id INTEGER NULL REFERENCES messages(id) ON DELETE SET NULL,
+    report_id INTEGER NULL REFERENCES message_reports(id) ON DELETE SET NULL,
+    note TEXT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_moderation_actions_on_created_at ON moderation_actions (created_at);
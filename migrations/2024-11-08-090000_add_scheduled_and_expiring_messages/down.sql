@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_message_expires_at;
+DROP INDEX IF EXISTS idx_message_pending_delivery;
+
+ALTER TABLE messages
+DROP COLUMN IF EXISTS is_delivered,
+DROP COLUMN IF EXISTS expires_at,
+DROP COLUMN IF EXISTS deliver_at;
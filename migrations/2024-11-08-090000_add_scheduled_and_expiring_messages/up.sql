@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE messages
+    ADD COLUMN deliver_at TIMESTAMP NULL,
+    ADD COLUMN expires_at TIMESTAMP NULL,
+    ADD COLUMN is_delivered BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Lets the scheduler find due and expired messages without scanning the table
+CREATE INDEX idx_message_pending_delivery ON messages(deliver_at) WHERE is_delivered = FALSE;
+CREATE INDEX idx_message_expires_at ON messages(expires_at) WHERE expires_at IS NOT NULL;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS announcement_receipts;
+DROP TABLE IF EXISTS announcements;
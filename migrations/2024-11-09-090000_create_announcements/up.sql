@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE announcements (
+    id SERIAL PRIMARY KEY,
+    author_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_announcements_on_created_at ON announcements (created_at);
+
+-- One row per user that has received an announcement, either live or on fetch
+CREATE TABLE announcement_receipts (
+    announcement_id INTEGER NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    delivered_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (announcement_id, user_id)
+);
+
+CREATE INDEX index_announcement_receipts_on_user_id ON announcement_receipts (user_id);
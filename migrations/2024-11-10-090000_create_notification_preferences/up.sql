@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE notification_preferences (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    counterpart_user_id INTEGER NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Reserved for group conversations; no groups table exists yet
+    group_id INTEGER NULL,
+    mode VARCHAR(20) NOT NULL DEFAULT 'all'
+        CHECK (mode IN ('all', 'mentions_only', 'muted')),
+    muted_until TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((counterpart_user_id IS NULL) <> (group_id IS NULL))
+);
+
+CREATE UNIQUE INDEX index_notification_preferences_on_user_and_counterpart
+    ON notification_preferences (user_id, counterpart_user_id) WHERE counterpart_user_id IS NOT NULL;
+CREATE UNIQUE INDEX index_notification_preferences_on_user_and_group
+    ON notification_preferences (user_id, group_id) WHERE group_id IS NOT NULL;
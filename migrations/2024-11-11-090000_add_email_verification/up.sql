@@ -0,0 +1,19 @@
+-- Your SQL goes here
+ALTER TABLE users
+    ADD COLUMN email_verified_at TIMESTAMP NULL;
+
+-- Existing accounts predate verification and should keep working if it becomes mandatory
+UPDATE users SET email_verified_at = CURRENT_TIMESTAMP;
+
+-- Single-use tokens mailed to users (email verification, password reset, ...)
+CREATE TABLE user_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose VARCHAR(30) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_user_tokens_on_user_id_and_purpose ON user_tokens (user_id, purpose);
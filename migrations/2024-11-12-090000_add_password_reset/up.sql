@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX index_user_tokens_on_created_at ON user_tokens (created_at);
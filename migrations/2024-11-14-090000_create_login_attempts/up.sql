@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE login_attempts (
+    id SERIAL PRIMARY KEY,
+    -- The account's username, so its email and username share a counter; identifiers
+    -- naming no account are kept trimmed and lowercased so they are throttled too
+    username VARCHAR(255) NOT NULL,
+    ip_address VARCHAR(45) NULL,
+    succeeded BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_login_attempts_on_username_and_created_at ON login_attempts (username, created_at);
+CREATE INDEX index_login_attempts_on_ip_address_and_created_at ON login_attempts (ip_address, created_at);
+
+CREATE TABLE login_lockouts (
+    id SERIAL PRIMARY KEY,
+    username VARCHAR(255) NULL,
+    ip_address VARCHAR(45) NULL,
+    failed_attempts INTEGER NOT NULL,
+    locked_until TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (username IS NOT NULL OR ip_address IS NOT NULL)
+);
+
+CREATE INDEX index_login_lockouts_on_created_at ON login_lockouts (created_at);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Emails are matched case-insensitively at login and registration
+CREATE UNIQUE INDEX index_users_on_lower_email ON users (LOWER(email));
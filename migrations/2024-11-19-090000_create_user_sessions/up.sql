@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- One row per issued access token, so users can see and revoke where they are signed in
+CREATE TABLE user_sessions (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- The `jti` of the access token
+    token_id VARCHAR(64) NOT NULL UNIQUE,
+    user_agent VARCHAR(512) NULL,
+    ip_address VARCHAR(45) NULL,
+    expires_at TIMESTAMP NOT NULL,
+    last_active_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_user_sessions_on_user_id ON user_sessions (user_id);
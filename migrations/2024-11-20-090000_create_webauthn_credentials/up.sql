@@ -0,0 +1,28 @@
+-- Your SQL goes here
+-- Passkeys: WebAuthn public key credentials registered by users
+CREATE TABLE webauthn_credentials (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Base64url credential id chosen by the authenticator (at most 1023 bytes raw)
+    credential_id VARCHAR(1400) NOT NULL UNIQUE,
+    -- The COSE_Key from the attested credential data
+    public_key BYTEA NOT NULL,
+    algorithm INTEGER NOT NULL,
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    name VARCHAR(100) NOT NULL,
+    last_used_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_webauthn_credentials_on_user_id ON webauthn_credentials (user_id);
+
+-- Outstanding registration and authentication challenges; each one works once
+CREATE TABLE webauthn_challenges (
+    id SERIAL PRIMARY KEY,
+    -- Set for registrations; sign-ins find the user through the credential
+    user_id INTEGER NULL REFERENCES users(id) ON DELETE CASCADE,
+    ceremony VARCHAR(20) NOT NULL,
+    challenge_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
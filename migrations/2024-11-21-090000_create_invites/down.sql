@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS invites;
+
+ALTER TABLE users
+DROP COLUMN IF EXISTS pending_approval;
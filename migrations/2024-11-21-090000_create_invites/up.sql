@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- Sign-ups waiting for an admin while registration requires approval
+ALTER TABLE users
+    ADD COLUMN pending_approval BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Codes admins hand out for invite-only registration; only the SHA-256 of a code is stored
+CREATE TABLE invites (
+    id SERIAL PRIMARY KEY,
+    code_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_by INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    note VARCHAR(200) NULL,
+    max_uses INTEGER NOT NULL DEFAULT 1 CHECK (max_uses > 0),
+    use_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMP NULL,
+    revoked_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
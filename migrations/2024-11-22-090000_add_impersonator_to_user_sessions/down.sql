@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS index_user_sessions_on_impersonator_id;
+ALTER TABLE user_sessions
+DROP COLUMN IF EXISTS impersonator_id;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Set on sessions a superuser opened by impersonating the user, which keeps a record of every one
+ALTER TABLE user_sessions ADD COLUMN impersonator_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX index_user_sessions_on_impersonator_id ON user_sessions (impersonator_id) WHERE impersonator_id IS NOT NULL;
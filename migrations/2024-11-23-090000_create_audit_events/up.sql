@@ -0,0 +1,28 @@
+-- Your SQL goes here
+-- Append-only log of authentication and admin events. User ids carry no foreign keys
+-- so that events survive the accounts they mention.
+CREATE TABLE audit_events (
+    id SERIAL PRIMARY KEY,
+    action VARCHAR(32) NOT NULL,
+    outcome VARCHAR(16) NOT NULL,
+    actor_id INTEGER NULL,
+    target_user_id INTEGER NULL,
+    ip_address VARCHAR(45) NULL,
+    user_agent VARCHAR(512) NULL,
+    details VARCHAR(500) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_audit_events_on_created_at ON audit_events (created_at);
+CREATE INDEX index_audit_events_on_actor_id ON audit_events (actor_id);
+CREATE INDEX index_audit_events_on_target_user_id ON audit_events (target_user_id);
+
+CREATE FUNCTION reject_audit_event_changes() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_append_only
+    BEFORE UPDATE OR DELETE OR TRUNCATE ON audit_events
+    FOR EACH STATEMENT EXECUTE FUNCTION reject_audit_event_changes();
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use crate::domain::repositories::account_repository::AccountRepository;
 use crate::domain::entities::account::{Account, UpdateAccountDto};
+use crate::domain::services::validation::{validate_name, Validator};
 
 pub struct GetAccountUseCase<T: AccountRepository> {
     account_repository: Arc<T>,
@@ -40,6 +41,12 @@ impl<T: AccountRepository> UpdateAccountUseCase<T> {
     }
 
     pub async fn execute(&self, user_id: i32, account_dto: UpdateAccountDto) -> Result<Account, Box<dyn std::error::Error>> {
+        Validator::new()
+            .check_optional("first_name", account_dto.first_name.as_deref(), validate_name)
+            .check_optional("middle_name", account_dto.middle_name.as_deref(), validate_name)
+            .check_optional("last_name", account_dto.last_name.as_deref(), validate_name)
+            .finish()?;
+
         self.account_repository.update(user_id, account_dto).await
     }
 }
\ No newline at end of file
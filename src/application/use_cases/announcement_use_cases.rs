@@ -0,0 +1,81 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::announcement::{Announcement, CreateAnnouncementDto};
+use crate::domain::repositories::announcement_repository::AnnouncementRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+
+const RECENT_ANNOUNCEMENTS_LIMIT: i64 = 50;
+/// Users who come back after longer than this do not get older announcements replayed
+const PENDING_ANNOUNCEMENTS_WINDOW_DAYS: i64 = 30;
+
+pub struct PublishAnnouncementUseCase<A: AnnouncementRepository, R: RoleRepository> {
+    announcement_repository: Arc<A>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<A: AnnouncementRepository, R: RoleRepository> PublishAnnouncementUseCase<A, R> {
+    pub fn new(announcement_repository: Arc<A>, role_repository: Arc<R>) -> Self {
+        Self {
+            announcement_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, author_id: i32, announcement_dto: CreateAnnouncementDto) -> Result<Announcement, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(author_id, ADMIN_ROLES).await?;
+
+        let content = announcement_dto.content.trim().to_string();
+        if content.is_empty() {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "Announcement content cannot be empty"
+            )));
+        }
+
+        self.announcement_repository.create(author_id, content).await
+    }
+
+    /// Records the users that received the announcement over their live connection.
+    pub async fn mark_delivered(&self, announcement_id: i32, user_ids: Vec<i32>) -> Result<(), Box<dyn std::error::Error>> {
+        self.announcement_repository.mark_delivered(vec![announcement_id], user_ids).await
+    }
+}
+
+pub struct ListAnnouncementsUseCase<A: AnnouncementRepository> {
+    announcement_repository: Arc<A>,
+}
+
+impl<A: AnnouncementRepository> ListAnnouncementsUseCase<A> {
+    pub fn new(announcement_repository: Arc<A>) -> Self {
+        Self { announcement_repository }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<Announcement>, Box<dyn std::error::Error>> {
+        self.announcement_repository.find_recent(RECENT_ANNOUNCEMENTS_LIMIT).await
+    }
+}
+
+pub struct GetPendingAnnouncementsUseCase<A: AnnouncementRepository> {
+    announcement_repository: Arc<A>,
+}
+
+impl<A: AnnouncementRepository> GetPendingAnnouncementsUseCase<A> {
+    pub fn new(announcement_repository: Arc<A>) -> Self {
+        Self { announcement_repository }
+    }
+
+    /// Returns announcements the user missed while offline and marks them as received.
+    pub async fn execute(&self, user_id: i32) -> Result<Vec<Announcement>, Box<dyn std::error::Error>> {
+        let since = (Utc::now() - Duration::days(PENDING_ANNOUNCEMENTS_WINDOW_DAYS)).naive_utc();
+        let pending = self.announcement_repository.find_undelivered(user_id, since).await?;
+
+        if !pending.is_empty() {
+            let ids = pending.iter().map(|announcement| announcement.id).collect();
+            self.announcement_repository.mark_delivered(ids, vec![user_id]).await?;
+        }
+
+        Ok(pending)
+    }
+}
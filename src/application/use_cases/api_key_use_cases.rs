@@ -0,0 +1,146 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
+
+use crate::application::use_cases::auth_use_cases::ensure_may_sign_in;
+use crate::domain::entities::api_key::{ApiKey, CreateApiKeyDto, CreatedApiKey, NewApiKey, API_KEY_PREFIX, API_KEY_SCOPES, SCOPE_READ};
+use crate::domain::entities::auth::Claims;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::token_service::TokenService;
+
+const MAX_API_KEY_NAME_LENGTH: usize = 100;
+const MAX_API_KEYS_PER_USER: i64 = 20;
+const MAX_API_KEY_LIFETIME_DAYS: i64 = 365;
+/// Characters of the key kept in `prefix`, after `API_KEY_PREFIX`
+const DISPLAYED_KEY_CHARS: usize = 8;
+
+fn invalid_input(message: &str) -> Box<dyn std::error::Error> {
+    Box::new(IoError::new(ErrorKind::InvalidInput, message.to_string()))
+}
+
+pub struct ManageApiKeysUseCase<A: ApiKeyRepository> {
+    api_key_repository: Arc<A>,
+    token_service: Arc<TokenService>,
+}
+
+impl<A: ApiKeyRepository> ManageApiKeysUseCase<A> {
+    pub fn new(api_key_repository: Arc<A>, token_service: Arc<TokenService>) -> Self {
+        Self { api_key_repository, token_service }
+    }
+
+    /// Creates a key for the user. The key is returned here and never again.
+    pub async fn create(&self, user_id: i32, api_key_dto: CreateApiKeyDto) -> Result<CreatedApiKey, Box<dyn std::error::Error>> {
+        let name = api_key_dto.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_LENGTH {
+            return Err(invalid_input("Key name must be between 1 and 100 characters"));
+        }
+
+        let scopes = api_key_dto.scopes.unwrap_or_else(|| vec![SCOPE_READ.to_string()]);
+        if scopes.is_empty() || scopes.iter().any(|scope| !API_KEY_SCOPES.contains(&scope.as_str())) {
+            return Err(invalid_input("Scopes must be one or more of: read, write"));
+        }
+
+        let expires_at = match api_key_dto.expires_in_days {
+            Some(days) if !(1..=MAX_API_KEY_LIFETIME_DAYS).contains(&days) => {
+                return Err(invalid_input("Keys must expire within 1 to 365 days"));
+            }
+            Some(days) => Some((Utc::now() + Duration::days(days)).naive_utc()),
+            None => None,
+        };
+
+        let active_keys = self.api_key_repository
+            .count_active(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+        if active_keys >= MAX_API_KEYS_PER_USER {
+            return Err(invalid_input("Too many API keys; revoke one before creating another"));
+        }
+
+        let random = self.token_service.random().map_err(|e| e as Box<dyn std::error::Error>)?;
+        let key = format!("{}{}", API_KEY_PREFIX, random);
+
+        let api_key = self.api_key_repository
+            .create(NewApiKey {
+                user_id,
+                name,
+                prefix: format!("{}{}", API_KEY_PREFIX, &random[..DISPLAYED_KEY_CHARS]),
+                key_hash: self.token_service.hash(&key),
+                scopes,
+                expires_at,
+            })
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        info!("API key {} ({}) created by user {}", api_key.id, api_key.prefix, user_id);
+
+        Ok(CreatedApiKey { api_key, key })
+    }
+
+    pub async fn list(&self, user_id: i32) -> Result<Vec<ApiKey>, Box<dyn std::error::Error>> {
+        self.api_key_repository
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+
+    pub async fn revoke(&self, user_id: i32, key_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let revoked = self.api_key_repository
+            .revoke(user_id, key_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        if !revoked {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "API key not found")));
+        }
+
+        info!("API key {} revoked by user {}", key_id, user_id);
+        Ok(())
+    }
+}
+
+/// Turns an API key presented as a bearer token into the claims handlers expect.
+pub struct AuthenticateApiKeyUseCase<A: ApiKeyRepository, T: AuthRepository> {
+    api_key_repository: Arc<A>,
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+}
+
+impl<A: ApiKeyRepository, T: AuthRepository> AuthenticateApiKeyUseCase<A, T> {
+    pub fn new(
+        api_key_repository: Arc<A>,
+        auth_repository: T,
+        token_service: Arc<TokenService>,
+        access_tokens: Arc<AccessTokenService>,
+    ) -> Self {
+        Self { api_key_repository, auth_repository, token_service, access_tokens }
+    }
+
+    /// Returns None for unknown, revoked or expired keys, and fails with `PermissionDenied`
+    /// when the owner is suspended or still awaiting approval.
+    pub async fn execute(&self, key: &str) -> Result<Option<Claims>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(api_key) = self.api_key_repository.find_active_by_hash(self.token_service.hash(key)).await? else {
+            return Ok(None);
+        };
+        let Some(user) = self.auth_repository.find_by_id(api_key.user_id).await? else {
+            return Ok(None);
+        };
+        // A key is only as good as its owner's account
+        ensure_may_sign_in(&self.auth_repository, &user, false).await?;
+
+        // Failing to record the use should not fail the request
+        if let Err(e) = self.api_key_repository.record_use(api_key.id).await {
+            warn!("Failed to record use of API key {}: {}", api_key.id, e);
+        }
+
+        let roles = self.auth_repository.find_role_names(user.id).await?;
+        let mut claims = self.access_tokens.claims_for(&user, roles);
+        claims.jti = format!("api-key:{}", api_key.id);
+        claims.scopes = Some(api_key.scopes);
+
+        Ok(Some(claims))
+    }
+}
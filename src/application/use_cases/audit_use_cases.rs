@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use tracing::error;
+
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::audit::{AuditEvent, AuditEventQuery, NewAuditEvent};
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+
+const DEFAULT_AUDIT_EVENT_LIMIT: i64 = 100;
+const MAX_AUDIT_EVENT_LIMIT: i64 = 500;
+
+/// Appends an event to the audit log. A failed write is logged but does not fail the
+/// action being audited.
+pub(crate) async fn record_audit_event(audit_repository: &dyn AuditRepository, event: NewAuditEvent) {
+    let action = event.action;
+    if let Err(e) = audit_repository.record_event(event).await {
+        error!("Failed to record {} audit event: {}", action.as_str(), e);
+    }
+}
+
+pub struct ListAuditEventsUseCase<R: RoleRepository> {
+    audit_repository: Arc<dyn AuditRepository>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<R: RoleRepository> ListAuditEventsUseCase<R> {
+    pub fn new(audit_repository: Arc<dyn AuditRepository>, role_repository: Arc<R>) -> Self {
+        Self {
+            audit_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    /// The newest events matching the query; page back with `before_id`.
+    pub async fn execute(&self, admin_id: i32, query: AuditEventQuery) -> Result<Vec<AuditEvent>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let limit = query.limit.unwrap_or(DEFAULT_AUDIT_EVENT_LIMIT).clamp(1, MAX_AUDIT_EVENT_LIMIT);
+        self.audit_repository
+            .find_events(&query, limit)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+}
@@ -1,66 +1,530 @@
 use std::fmt;
-use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{debug, warn};
 
-use crate::domain::entities::auth::{AuthUser, Claims, RegisterUserDto, TokenResponse};
+use crate::application::use_cases::audit_use_cases::record_audit_event;
+use crate::domain::entities::audit::{AuditAction, AuditOutcome, NewAuditEvent};
+use crate::domain::entities::auth::{
+    AuthUser, ChangePasswordDto, Claims, ConfirmPasswordResetDto, RegisterUserDto, TokenPurpose, TokenResponse,
+};
+use crate::domain::entities::mfa::{LoginResponse, MfaChallenge};
+use crate::domain::entities::registration::{Admission, Registered, RegistrationMode};
+use crate::domain::entities::session::{ClientInfo, NewSession};
 use crate::domain::entities::user::User;
+use crate::application::use_cases::login_attempt_use_cases::LoginThrottleUseCase;
+use crate::domain::repositories::audit_repository::AuditRepository;
 use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::services::mailer::{Mailer, OutgoingEmail};
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::validation::{validate_email, validate_name, validate_username, PasswordPolicy, ValidationErrors, Validator};
 
-pub struct LoginUseCase<T: AuthRepository> {
+const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+const PASSWORD_RESET_TTL_MINUTES: i64 = 60;
+const PASSWORD_RESET_MAX_REQUESTS_PER_HOUR: i64 = 3;
+const MFA_CHALLENGE_TTL_MINUTES: i64 = 5;
+
+pub const EMAIL_NOT_VERIFIED: &str = "Email address is not verified";
+pub const ACCOUNT_PENDING_APPROVAL: &str = "Account is awaiting approval";
+pub const ACCOUNT_SUSPENDED: &str = "Account is suspended";
+
+fn validate_new_password(password_policy: &PasswordPolicy, new_password: &str) -> Result<(), ValidationErrors> {
+    Validator::new()
+        .check("new_password", password_policy.validate(new_password))
+        .finish()
+}
+
+/// Signs the access token handed out once a user is fully authenticated and records
+/// the session it opens.
+pub(crate) async fn issue_access_token<T: AuthRepository>(
+    auth_repository: &T,
+    access_tokens: &AccessTokenService,
+    user: &User,
+    client: &ClientInfo,
+) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+    let roles = auth_repository.find_role_names(user.id).await?;
+    let claims = access_tokens.claims_for(user, roles);
+
+    let expires_at = DateTime::from_timestamp(claims.exp, 0)
+        .ok_or("Invalid token expiry")?
+        .naive_utc();
+    auth_repository
+        .create_session(NewSession {
+            user_id: user.id,
+            token_id: claims.jti.clone(),
+            user_agent: client.user_agent.clone(),
+            ip_address: client.ip_address.clone(),
+            expires_at,
+            impersonator_id: None,
+        })
+        .await?;
+
+    access_tokens.sign(&claims)
+}
+
+/// Turns away suspended users and sign-ups still waiting for approval, and enforces email
+/// verification when required. Every way of signing in or presenting a credential runs this.
+pub(crate) async fn ensure_may_sign_in<T: AuthRepository>(
+    auth_repository: &T,
+    user: &User,
+    require_email_verification: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if auth_repository.is_suspended(user.id).await? {
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            ACCOUNT_SUSPENDED
+        )));
+    }
+
+    if auth_repository.is_pending_approval(user.id).await? {
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            ACCOUNT_PENDING_APPROVAL
+        )));
+    }
+
+    if require_email_verification && user.email_verified_at.is_none() {
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            EMAIL_NOT_VERIFIED
+        )));
+    }
+
+    Ok(())
+}
+
+/// The last step of every sign-in method once the user is known: checks the user may sign
+/// in at all and swaps the token for an MFA challenge when 2FA is on.
+pub(crate) async fn complete_login<T: AuthRepository>(
+    auth_repository: &T,
+    token_service: &TokenService,
+    access_tokens: &AccessTokenService,
+    user: &User,
+    client: &ClientInfo,
+    require_email_verification: bool,
+) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
+    ensure_may_sign_in(auth_repository, user, require_email_verification).await?;
+
+    let mfa_enabled = auth_repository
+        .find_totp(user.id)
+        .await?
+        .is_some_and(|totp| totp.is_enabled());
+
+    if !mfa_enabled {
+        return Ok(LoginResponse::Token(issue_access_token(auth_repository, access_tokens, user, client).await?));
+    }
+
+    let mfa_token = token_service.generate(TokenPurpose::MfaChallenge)?;
+    let expires_at = (Utc::now() + Duration::minutes(MFA_CHALLENGE_TTL_MINUTES)).naive_utc();
+    auth_repository
+        .create_token(user.id, TokenPurpose::MfaChallenge, token_service.hash(&mfa_token), expires_at)
+        .await?;
+
+    Ok(LoginResponse::MfaRequired(MfaChallenge {
+        mfa_required: true,
+        mfa_token,
+        expires_in: MFA_CHALLENGE_TTL_MINUTES * 60,
+    }))
+}
+
+pub struct LoginUseCase<T: AuthRepository, L: LoginAttemptRepository> {
     auth_repository: T,
+    login_throttle: LoginThrottleUseCase<L>,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+    audit_repository: Arc<dyn AuditRepository>,
+    require_email_verification: bool,
 }
 
-impl<T: AuthRepository> fmt::Debug for LoginUseCase<T> {
+impl<T: AuthRepository, L: LoginAttemptRepository> fmt::Debug for LoginUseCase<T, L> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("LoginUseCase")
             .field("auth_repository", &"AuthRepository")
+            .field("require_email_verification", &self.require_email_verification)
             .finish()
     }
 }
 
-impl<T: AuthRepository> LoginUseCase<T> {
-    pub fn new(auth_repository: T) -> Self {
+impl<T: AuthRepository, L: LoginAttemptRepository> LoginUseCase<T, L> {
+    pub fn new(
+        auth_repository: T,
+        login_attempt_repository: Arc<L>,
+        token_service: Arc<TokenService>,
+        access_tokens: Arc<AccessTokenService>,
+        audit_repository: Arc<dyn AuditRepository>,
+        require_email_verification: bool,
+    ) -> Self {
         Self {
             auth_repository,
+            login_throttle: LoginThrottleUseCase::new(login_attempt_repository),
+            token_service,
+            access_tokens,
+            audit_repository,
+            require_email_verification,
         }
     }
 
-    pub async fn execute(&self, auth: AuthUser) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let user = self.auth_repository.authenticate(auth).await?;
+    /// Returns the access token, or an MFA challenge when the user has two-factor
+    /// authentication enabled; the challenge is exchanged for the token with a valid code.
+    pub async fn execute(&self, auth: AuthUser, client: &ClientInfo) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let username = auth.username.clone();
+        let throttle_key = self.throttle_key(&username).await?;
+        let ip_address = client.ip_address.as_deref();
+        self.login_throttle.check(&throttle_key, ip_address).await?;
 
-        let secret_key = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set");
-        let now = Utc::now();
-        let exp = (now + Duration::hours(24)).timestamp();
-        let claims = Claims {
-            sub: user.id,
-            exp,
-            iat: now.timestamp(),
+        let user = match self.auth_repository.authenticate(auth).await {
+            Ok(user) => user,
+            Err(e) => {
+                // Only bad credentials count towards a lockout, not suspensions or database errors
+                let bad_credentials = e.downcast_ref::<std::io::Error>()
+                    .is_some_and(|io_error| io_error.kind() == std::io::ErrorKind::InvalidInput);
+                if bad_credentials {
+                    self.login_throttle.record_failure(&throttle_key, ip_address).await?;
+                }
+                // Successful logins are audited when their session is created
+                record_audit_event(&*self.audit_repository, NewAuditEvent {
+                    details: Some(format!("{} ({})", e, username)),
+                    ..NewAuditEvent::new(AuditAction::Login, AuditOutcome::Failure, client)
+                }).await;
+                return Err(e);
+            }
         };
 
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret_key.as_bytes()),
-        )?;
+        let response = complete_login(&self.auth_repository, &self.token_service, &self.access_tokens, &user, client, self.require_email_verification).await?;
+        // With 2FA on, the counter is only reset once the second factor is accepted
+        if matches!(response, LoginResponse::Token(_)) {
+            self.login_throttle.record_success(&throttle_key, ip_address).await?;
+        }
+        Ok(response)
+    }
 
-        Ok(TokenResponse {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            expires_in: exp - now.timestamp(),
+    /// Attempts are counted per account, so its username and email address share a
+    /// counter. Identifiers naming no account are counted trimmed and lowercased.
+    async fn throttle_key(&self, login: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(match self.auth_repository.find_by_login(login).await? {
+            Some(user) => user.username,
+            None => login.trim().to_lowercase(),
         })
     }
 }
 
+pub struct SendVerificationEmailUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    mailer: Arc<dyn Mailer>,
+    base_url: String,
+}
+
+impl<T: AuthRepository> SendVerificationEmailUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>, mailer: Arc<dyn Mailer>, base_url: String) -> Self {
+        Self { auth_repository, token_service, mailer, base_url }
+    }
+
+    pub async fn execute(&self, user: &User) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Only the most recent link stays valid
+        self.auth_repository.revoke_tokens(user.id, TokenPurpose::EmailVerification).await?;
+
+        let token = self.token_service.generate(TokenPurpose::EmailVerification)?;
+        let expires_at = (Utc::now() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS)).naive_utc();
+        self.auth_repository
+            .create_token(user.id, TokenPurpose::EmailVerification, self.token_service.hash(&token), expires_at)
+            .await?;
+
+        let link = format!("{}/api/v1/auth/verify-email?token={}", self.base_url.trim_end_matches('/'), token);
+        self.mailer.send(OutgoingEmail {
+            to: user.email.clone(),
+            subject: "Verify your email address".to_string(),
+            body: format!(
+                "Hi {},\n\nConfirm your email address by opening the link below:\n\n{}\n\nThe link expires in {} hours.",
+                user.username, link, EMAIL_VERIFICATION_TTL_HOURS
+            ),
+        }).await
+    }
+
+    /// Sends a fresh link to an unverified address. Unknown or already verified
+    /// addresses succeed silently so the endpoint cannot be used to probe for accounts.
+    pub async fn resend(&self, email: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match self.auth_repository.find_by_email(email).await? {
+            Some(user) if user.email_verified_at.is_none() => self.execute(&user).await,
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct VerifyEmailUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+}
+
+impl<T: AuthRepository> VerifyEmailUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>) -> Self {
+        Self { auth_repository, token_service }
+    }
+
+    pub async fn execute(&self, token: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let invalid = || -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid or expired verification token"
+            ))
+        };
+
+        if !self.token_service.verify(TokenPurpose::EmailVerification, token) {
+            return Err(invalid());
+        }
+
+        let user_id = self.auth_repository
+            .consume_token(TokenPurpose::EmailVerification, self.token_service.hash(token))
+            .await?
+            .ok_or_else(invalid)?;
+
+        self.auth_repository.mark_email_verified(user_id).await
+    }
+}
+
 pub struct RegisterUseCase<T: AuthRepository> {
     auth_repository: T,
+    send_verification_email_use_case: Arc<SendVerificationEmailUseCase<T>>,
+    password_policy: Arc<PasswordPolicy>,
+    token_service: Arc<TokenService>,
+    registration_mode: RegistrationMode,
 }
 
 impl<T: AuthRepository> RegisterUseCase<T> {
+    pub fn new(
+        auth_repository: T,
+        send_verification_email_use_case: Arc<SendVerificationEmailUseCase<T>>,
+        password_policy: Arc<PasswordPolicy>,
+        token_service: Arc<TokenService>,
+        registration_mode: RegistrationMode,
+    ) -> Self {
+        Self { auth_repository, send_verification_email_use_case, password_policy, token_service, registration_mode }
+    }
+
+    /// Decides how a sign-up gets in under the registration mode.
+    fn admission(&self, invite_code: Option<&str>) -> Result<Admission, Box<dyn std::error::Error + Send + Sync>> {
+        let invite_code_hash = invite_code
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(|code| self.token_service.hash(code));
+
+        match self.registration_mode {
+            // Codes are not needed, so they are not used up either
+            RegistrationMode::Open => Ok(Admission::default()),
+            RegistrationMode::InviteOnly => match invite_code_hash {
+                Some(invite_code_hash) => Ok(Admission { invite_code_hash: Some(invite_code_hash), pending_approval: false }),
+                None => Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "An invite code is required to register"
+                ))),
+            },
+            // An admin's invite vouches for the user
+            RegistrationMode::ApprovalRequired => Ok(Admission {
+                pending_approval: invite_code_hash.is_none(),
+                invite_code_hash,
+            }),
+            RegistrationMode::Closed => Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Registration is closed"
+            ))),
+        }
+    }
+
+    pub async fn execute(&self, register_dto: RegisterUserDto) -> Result<Registered, Box<dyn std::error::Error + Send + Sync>> {
+        let admission = self.admission(register_dto.invite_code.as_deref())?;
+        let pending_approval = admission.pending_approval;
+
+        Validator::new()
+            .check("username", validate_username(&register_dto.username))
+            .check("email", validate_email(&register_dto.email))
+            .check("password", self.password_policy.validate(&register_dto.password))
+            .check_optional("first_name", register_dto.first_name.as_deref(), validate_name)
+            .check_optional("middle_name", register_dto.middle_name.as_deref(), validate_name)
+            .check_optional("last_name", register_dto.last_name.as_deref(), validate_name)
+            .finish()?;
+
+        let user = self.auth_repository.register(register_dto, admission).await?;
+
+        // The account exists either way; the user can ask for a new link later
+        if let Err(e) = self.send_verification_email_use_case.execute(&user).await {
+            warn!("Failed to send verification email to user {}: {}", user.id, e);
+        }
+
+        Ok(Registered { user, pending_approval })
+    }
+}
+
+pub struct RequestPasswordResetUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    mailer: Arc<dyn Mailer>,
+    reset_url: String,
+}
+
+impl<T: AuthRepository> RequestPasswordResetUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>, mailer: Arc<dyn Mailer>, reset_url: String) -> Self {
+        Self { auth_repository, token_service, mailer, reset_url }
+    }
+
+    /// Mails a reset link if the address belongs to an account. Unknown addresses and
+    /// rate-limited requests succeed silently so the response never reveals either.
+    pub async fn execute(&self, email: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = match self.auth_repository.find_by_email(email).await? {
+            Some(user) => user,
+            None => return Ok(()),
+        };
+
+        let since = (Utc::now() - Duration::hours(1)).naive_utc();
+        let recent_requests = self.auth_repository
+            .count_tokens_since(user.id, TokenPurpose::PasswordReset, since)
+            .await?;
+        if recent_requests >= PASSWORD_RESET_MAX_REQUESTS_PER_HOUR {
+            debug!("Password reset rate limit reached for user {}", user.id);
+            return Ok(());
+        }
+
+        let token = self.token_service.generate(TokenPurpose::PasswordReset)?;
+        let expires_at = (Utc::now() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES)).naive_utc();
+        self.auth_repository
+            .create_token(user.id, TokenPurpose::PasswordReset, self.token_service.hash(&token), expires_at)
+            .await?;
+
+        let link = format!("{}?token={}", self.reset_url, token);
+        self.mailer.send(OutgoingEmail {
+            to: user.email,
+            subject: "Reset your password".to_string(),
+            body: format!(
+                "Hi {},\n\nSomeone asked to reset the password for your account. If it was you, open the link below:\n\n{}\n\nThe link expires in {} minutes. If you did not ask for a reset, you can ignore this email.",
+                user.username, link, PASSWORD_RESET_TTL_MINUTES
+            ),
+        }).await
+    }
+}
+
+pub struct ConfirmPasswordResetUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    password_policy: Arc<PasswordPolicy>,
+    audit_repository: Arc<dyn AuditRepository>,
+}
+
+impl<T: AuthRepository> ConfirmPasswordResetUseCase<T> {
+    pub fn new(
+        auth_repository: T,
+        token_service: Arc<TokenService>,
+        password_policy: Arc<PasswordPolicy>,
+        audit_repository: Arc<dyn AuditRepository>,
+    ) -> Self {
+        Self { auth_repository, token_service, password_policy, audit_repository }
+    }
+
+    pub async fn execute(&self, reset_dto: ConfirmPasswordResetDto, client: &ClientInfo) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.reset_password(reset_dto).await;
+
+        let user_id = result.as_ref().ok().copied();
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: user_id,
+            target_user_id: user_id,
+            ..NewAuditEvent::from_result(AuditAction::PasswordReset, &result, client)
+        }).await;
+
+        result.map(|_| ())
+    }
+
+    /// Returns the user whose password was reset.
+    async fn reset_password(&self, reset_dto: ConfirmPasswordResetDto) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+        validate_new_password(&self.password_policy, &reset_dto.new_password)?;
+
+        let invalid = || -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid or expired reset token"
+            ))
+        };
+
+        if !self.token_service.verify(TokenPurpose::PasswordReset, &reset_dto.token) {
+            return Err(invalid());
+        }
+
+        let user_id = self.auth_repository
+            .consume_token(TokenPurpose::PasswordReset, self.token_service.hash(&reset_dto.token))
+            .await?
+            .ok_or_else(invalid)?;
+
+        self.auth_repository.update_password(user_id, &reset_dto.new_password).await?;
+
+        // Any other outstanding links, every signed-in session and every API key die with the old password
+        self.auth_repository.revoke_tokens(user_id, TokenPurpose::PasswordReset).await?;
+        self.auth_repository.revoke_sessions(user_id).await?;
+        self.auth_repository.revoke_api_keys(user_id).await?;
+
+        Ok(user_id)
+    }
+}
+
+pub struct ChangePasswordUseCase<T: AuthRepository> {
+    auth_repository: T,
+    password_policy: Arc<PasswordPolicy>,
+    audit_repository: Arc<dyn AuditRepository>,
+}
+
+impl<T: AuthRepository> ChangePasswordUseCase<T> {
+    pub fn new(auth_repository: T, password_policy: Arc<PasswordPolicy>, audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self { auth_repository, password_policy, audit_repository }
+    }
+
+    pub async fn execute(&self, user_id: i32, change_dto: ChangePasswordDto, client: &ClientInfo) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.change_password(user_id, change_dto).await;
+
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: Some(user_id),
+            target_user_id: Some(user_id),
+            ..NewAuditEvent::from_result(AuditAction::PasswordChange, &result, client)
+        }).await;
+
+        result
+    }
+
+    async fn change_password(&self, user_id: i32, change_dto: ChangePasswordDto) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.auth_repository.verify_password(user_id, &change_dto.current_password).await? {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Current password is incorrect"
+            )));
+        }
+
+        validate_new_password(&self.password_policy, &change_dto.new_password)?;
+
+        self.auth_repository.update_password(user_id, &change_dto.new_password).await?;
+
+        // A reset link mailed earlier must not be able to undo the change
+        self.auth_repository.revoke_tokens(user_id, TokenPurpose::PasswordReset).await
+    }
+}
+
+pub struct ValidateSessionUseCase<T: AuthRepository> {
+    auth_repository: T,
+}
+
+impl<T: AuthRepository> ValidateSessionUseCase<T> {
     pub fn new(auth_repository: T) -> Self {
         Self { auth_repository }
     }
 
-    pub async fn execute(&self, register_dto: RegisterUserDto) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
-        self.auth_repository.register(register_dto).await
+    /// Returns false when the token's session was revoked, on its own or with all of the
+    /// user's sessions (e.g. by a password reset).
+    pub async fn execute(&self, claims: &Claims) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(session) = self.auth_repository.find_active_session(claims.sub, &claims.jti).await? else {
+            return Ok(false);
+        };
+
+        // Failing to record activity should not fail the request
+        if let Err(e) = self.auth_repository.touch_session(session.id).await {
+            warn!("Failed to record activity of session {}: {}", session.id, e);
+        }
+
+        Ok(true)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,215 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use crate::domain::entities::contact::{Contact, ContactRequest, ContactRequestStatus, ContactSettings};
+use crate::domain::repositories::account_repository::AccountRepository;
+use crate::domain::repositories::contact_repository::ContactRepository;
+use crate::domain::repositories::moderation_repository::ModerationRepository;
+
+/// How long after a decline the same requester has to wait before asking again.
+/// The user who declined can still send a request of their own at any time.
+const DECLINED_REQUEST_COOLDOWN_DAYS: i64 = 30;
+
+pub struct SendContactRequestUseCase<C: ContactRepository> {
+    contact_repository: Arc<C>,
+}
+
+impl<C: ContactRepository> SendContactRequestUseCase<C> {
+    pub fn new(contact_repository: Arc<C>) -> Self {
+        Self { contact_repository }
+    }
+
+    pub async fn execute(&self, requester_id: i32, addressee_id: i32) -> Result<ContactRequest, Box<dyn std::error::Error>> {
+        if requester_id == addressee_id {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "You cannot send a contact request to yourself"
+            )));
+        }
+
+        match self.contact_repository.find_request_between(requester_id, addressee_id).await? {
+            None => self.contact_repository.create_request(requester_id, addressee_id).await,
+            Some(existing) => match existing.status {
+                ContactRequestStatus::Accepted => Err(Box::new(IoError::new(
+                    ErrorKind::AlreadyExists,
+                    "You are already contacts"
+                ))),
+                // The other user already asked us, so treat this as mutual consent
+                ContactRequestStatus::Pending if existing.requester_id == addressee_id => {
+                    self.contact_repository.update_status(existing.id, ContactRequestStatus::Accepted).await
+                }
+                ContactRequestStatus::Pending => Err(Box::new(IoError::new(
+                    ErrorKind::AlreadyExists,
+                    "Contact request is already pending"
+                ))),
+                ContactRequestStatus::Declined
+                    if existing.requester_id == requester_id
+                        && existing.updated_at > (Utc::now() - Duration::days(DECLINED_REQUEST_COOLDOWN_DAYS)).naive_utc() =>
+                {
+                    Err(Box::new(IoError::new(
+                        ErrorKind::PermissionDenied,
+                        "You cannot send this user another contact request yet"
+                    )))
+                }
+                ContactRequestStatus::Declined => {
+                    self.contact_repository.delete_request(existing.id).await?;
+                    self.contact_repository.create_request(requester_id, addressee_id).await
+                }
+            },
+        }
+    }
+}
+
+pub struct RespondContactRequestUseCase<C: ContactRepository> {
+    contact_repository: Arc<C>,
+}
+
+impl<C: ContactRepository> RespondContactRequestUseCase<C> {
+    pub fn new(contact_repository: Arc<C>) -> Self {
+        Self { contact_repository }
+    }
+
+    pub async fn execute(&self, user_id: i32, request_id: i32, accept: bool) -> Result<ContactRequest, Box<dyn std::error::Error>> {
+        let request = self.contact_repository.find_request(request_id).await?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "Contact request not found"))?;
+
+        if request.addressee_id != user_id {
+            return Err(Box::new(IoError::new(
+                ErrorKind::PermissionDenied,
+                "Only the recipient can respond to this contact request"
+            )));
+        }
+
+        if request.status != ContactRequestStatus::Pending {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "Contact request has already been answered"
+            )));
+        }
+
+        let status = if accept {
+            ContactRequestStatus::Accepted
+        } else {
+            ContactRequestStatus::Declined
+        };
+
+        self.contact_repository.update_status(request_id, status).await
+    }
+}
+
+pub struct ListContactRequestsUseCase<C: ContactRepository> {
+    contact_repository: Arc<C>,
+}
+
+impl<C: ContactRepository> ListContactRequestsUseCase<C> {
+    pub fn new(contact_repository: Arc<C>) -> Self {
+        Self { contact_repository }
+    }
+
+    pub async fn execute(&self, user_id: i32) -> Result<Vec<ContactRequest>, Box<dyn std::error::Error>> {
+        self.contact_repository.find_pending_for_user(user_id).await
+    }
+}
+
+pub struct ListContactsUseCase<C: ContactRepository, A: AccountRepository> {
+    contact_repository: Arc<C>,
+    account_repository: Arc<A>,
+}
+
+impl<C: ContactRepository, A: AccountRepository> ListContactsUseCase<C, A> {
+    pub fn new(contact_repository: Arc<C>, account_repository: Arc<A>) -> Self {
+        Self {
+            contact_repository,
+            account_repository,
+        }
+    }
+
+    pub async fn execute(&self, user_id: i32) -> Result<Vec<Contact>, Box<dyn std::error::Error>> {
+        let contact_ids = self.contact_repository.find_contacts(user_id).await?;
+
+        let mut contacts = Vec::new();
+        for (contact_id, since) in contact_ids {
+            // find_by_user_id also loads the default avatar
+            let account = self.account_repository.find_by_user_id(contact_id).await.ok();
+            contacts.push(Contact {
+                user_id: contact_id,
+                account,
+                since,
+            });
+        }
+
+        Ok(contacts)
+    }
+}
+
+pub struct RemoveContactUseCase<C: ContactRepository> {
+    contact_repository: Arc<C>,
+}
+
+impl<C: ContactRepository> RemoveContactUseCase<C> {
+    pub fn new(contact_repository: Arc<C>) -> Self {
+        Self { contact_repository }
+    }
+
+    pub async fn execute(&self, user_id: i32, contact_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        // Only accepted requests are contacts; removing a pending or declined one would
+        // let either side clear a decline and ask again
+        let request = self.contact_repository.find_request_between(user_id, contact_id).await?
+            .filter(|request| request.status == ContactRequestStatus::Accepted)
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "Contact not found"))?;
+
+        self.contact_repository.delete_request(request.id).await
+    }
+}
+
+pub struct ContactSettingsUseCase<C: ContactRepository> {
+    contact_repository: Arc<C>,
+}
+
+impl<C: ContactRepository> ContactSettingsUseCase<C> {
+    pub fn new(contact_repository: Arc<C>) -> Self {
+        Self { contact_repository }
+    }
+
+    pub async fn get(&self, user_id: i32) -> Result<ContactSettings, Box<dyn std::error::Error>> {
+        self.contact_repository.get_settings(user_id).await
+    }
+
+    pub async fn update(&self, user_id: i32, allow_messages_from_strangers: bool) -> Result<ContactSettings, Box<dyn std::error::Error>> {
+        self.contact_repository.update_settings(user_id, allow_messages_from_strangers).await
+    }
+}
+
+pub const STRANGER_MESSAGES_DISABLED: &str = "Recipient only accepts messages from contacts";
+pub const SENDER_SUSPENDED: &str = "Your account is suspended";
+
+pub struct CheckMessagePermissionUseCase<C: ContactRepository, M: ModerationRepository> {
+    contact_repository: Arc<C>,
+    moderation_repository: Arc<M>,
+}
+
+impl<C: ContactRepository, M: ModerationRepository> CheckMessagePermissionUseCase<C, M> {
+    pub fn new(contact_repository: Arc<C>, moderation_repository: Arc<M>) -> Self {
+        Self {
+            contact_repository,
+            moderation_repository,
+        }
+    }
+
+    pub async fn execute(&self, sender_id: i32, receiver_id: i32) -> Result<bool, Box<dyn std::error::Error>> {
+        if self.moderation_repository.find_active_suspension(sender_id).await?.is_some() {
+            return Err(Box::new(IoError::new(ErrorKind::PermissionDenied, SENDER_SUSPENDED)));
+        }
+
+        if sender_id == receiver_id {
+            return Ok(true);
+        }
+
+        let settings = self.contact_repository.get_settings(receiver_id).await?;
+        if settings.allow_messages_from_strangers {
+            return Ok(true);
+        }
+
+        self.contact_repository.are_contacts(sender_id, receiver_id).await
+    }
+}
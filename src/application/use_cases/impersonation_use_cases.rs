@@ -0,0 +1,94 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{DateTime, Duration};
+use tracing::warn;
+
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, SUPERUSER_ROLES};
+use crate::domain::entities::auth::{Actor, Claims, TokenResponse};
+use crate::domain::entities::session::{ClientInfo, NewSession};
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::services::access_token::AccessTokenService;
+
+const IMPERSONATION_TTL_MINUTES: i64 = 15;
+
+fn permission_denied(message: &str) -> Box<dyn std::error::Error> {
+    Box::new(IoError::new(ErrorKind::PermissionDenied, message.to_string()))
+}
+
+pub struct ImpersonateUserUseCase<T: AuthRepository, R: RoleRepository> {
+    auth_repository: T,
+    access_tokens: Arc<AccessTokenService>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<T: AuthRepository, R: RoleRepository> ImpersonateUserUseCase<T, R> {
+    pub fn new(auth_repository: T, access_tokens: Arc<AccessTokenService>, role_repository: Arc<R>) -> Self {
+        Self {
+            auth_repository,
+            access_tokens,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    /// Mints a short-lived token for `user_id` on behalf of a superuser. The token names
+    /// the superuser in its `act` claim and opens a session that records them, so every
+    /// impersonation shows up in the user's sessions.
+    pub async fn execute(&self, claims: &Claims, user_id: i32, client: &ClientInfo) -> Result<TokenResponse, Box<dyn std::error::Error>> {
+        if claims.is_impersonated() {
+            return Err(permission_denied("Cannot impersonate another user while impersonating"));
+        }
+        if claims.scopes.is_some() {
+            return Err(permission_denied("API keys cannot impersonate users"));
+        }
+        self.authorize_role.execute(claims.sub, SUPERUSER_ROLES).await?;
+
+        if user_id == claims.sub {
+            return Err(Box::new(IoError::new(ErrorKind::InvalidInput, "You cannot impersonate yourself")));
+        }
+
+        let user = self.auth_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "User not found"))?;
+
+        let roles = self.auth_repository
+            .find_role_names(user.id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+        if roles.iter().any(|role| SUPERUSER_ROLES.contains(&role.as_str())) {
+            return Err(permission_denied("Superusers cannot be impersonated"));
+        }
+
+        let mut impersonation = self.access_tokens.claims_for(&user, roles);
+        impersonation.exp = impersonation.iat + Duration::minutes(IMPERSONATION_TTL_MINUTES).num_seconds();
+        impersonation.act = Some(Actor { sub: claims.sub, username: claims.username.clone() });
+
+        let expires_at = DateTime::from_timestamp(impersonation.exp, 0)
+            .ok_or("Invalid token expiry")?
+            .naive_utc();
+        self.auth_repository
+            .create_session(NewSession {
+                user_id: user.id,
+                token_id: impersonation.jti.clone(),
+                user_agent: client.user_agent.clone(),
+                ip_address: client.ip_address.clone(),
+                expires_at,
+                impersonator_id: Some(claims.sub),
+            })
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        warn!(
+            "User {} started impersonating user {} (session {} from {}, expires at {})",
+            claims.sub,
+            user.id,
+            impersonation.jti,
+            client.ip_address.as_deref().unwrap_or("unknown address"),
+            expires_at
+        );
+
+        self.access_tokens.sign(&impersonation).map_err(|e| e as Box<dyn std::error::Error>)
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use tracing::warn;
+
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::login_attempt::{FailureStats, LoginLockout, NewLoginLockout};
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+
+pub const LOGIN_LOCKED: &str = "Too many failed login attempts, try again later";
+
+const USERNAME_LOCKOUT_THRESHOLD: i64 = 5;
+/// Higher than the per-username threshold since many users can share an address
+const IP_LOCKOUT_THRESHOLD: i64 = 20;
+const BASE_LOCKOUT_SECS: i64 = 30;
+const MAX_LOCKOUT_SECS: i64 = 60 * 60;
+/// Failures older than this no longer count, even without a successful login in between
+const FAILURE_WINDOW_HOURS: i64 = 24;
+const LOCKOUT_LOG_LIMIT: i64 = 100;
+
+/// Lockout length once `threshold` failures are reached, doubling with every further failure.
+fn lockout_duration(stats: &FailureStats, threshold: i64) -> Option<Duration> {
+    if stats.failed_attempts < threshold {
+        return None;
+    }
+
+    let doublings = (stats.failed_attempts - threshold).min(32) as u32;
+    let seconds = BASE_LOCKOUT_SECS.saturating_mul(2i64.saturating_pow(doublings)).min(MAX_LOCKOUT_SECS);
+    Some(Duration::seconds(seconds))
+}
+
+fn locked_until(stats: &FailureStats, threshold: i64) -> Option<NaiveDateTime> {
+    Some(stats.last_failed_at? + lockout_duration(stats, threshold)?)
+}
+
+pub struct LoginThrottleUseCase<L: LoginAttemptRepository> {
+    login_attempt_repository: Arc<L>,
+}
+
+impl<L: LoginAttemptRepository> LoginThrottleUseCase<L> {
+    pub fn new(login_attempt_repository: Arc<L>) -> Self {
+        Self { login_attempt_repository }
+    }
+
+    /// Fails while the username or the client address is locked out.
+    pub async fn check(&self, username: &str, ip_address: Option<&str>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let now = Utc::now().naive_utc();
+        let since = now - Duration::hours(FAILURE_WINDOW_HOURS);
+
+        let username_stats = self.login_attempt_repository.failures_for_username(username, since).await?;
+        let mut locked = locked_until(&username_stats, USERNAME_LOCKOUT_THRESHOLD).is_some_and(|until| until > now);
+
+        if let Some(ip_address) = ip_address {
+            let ip_stats = self.login_attempt_repository.failures_for_ip(ip_address, since).await?;
+            locked |= locked_until(&ip_stats, IP_LOCKOUT_THRESHOLD).is_some_and(|until| until > now);
+        }
+
+        if locked {
+            return Err(Box::new(IoError::new(ErrorKind::PermissionDenied, LOGIN_LOCKED)));
+        }
+        Ok(())
+    }
+
+    pub async fn record_success(&self, username: &str, ip_address: Option<&str>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.login_attempt_repository.record_attempt(username, ip_address, true).await
+    }
+
+    /// Records the failure and, when it trips a lockout, a lockout event for admins to review.
+    pub async fn record_failure(&self, username: &str, ip_address: Option<&str>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.login_attempt_repository.record_attempt(username, ip_address, false).await?;
+
+        let since = (Utc::now() - Duration::hours(FAILURE_WINDOW_HOURS)).naive_utc();
+
+        let username_stats = self.login_attempt_repository.failures_for_username(username, since).await?;
+        if let Some(until) = locked_until(&username_stats, USERNAME_LOCKOUT_THRESHOLD) {
+            warn!("Locking out username {} until {} after {} failed logins", username, until, username_stats.failed_attempts);
+            self.login_attempt_repository.record_lockout(NewLoginLockout {
+                username: Some(username.to_string()),
+                ip_address: ip_address.map(str::to_string),
+                failed_attempts: username_stats.failed_attempts as i32,
+                locked_until: until,
+            }).await?;
+        }
+
+        if let Some(ip_address) = ip_address {
+            let ip_stats = self.login_attempt_repository.failures_for_ip(ip_address, since).await?;
+            if let Some(until) = locked_until(&ip_stats, IP_LOCKOUT_THRESHOLD) {
+                warn!("Locking out address {} until {} after {} failed logins", ip_address, until, ip_stats.failed_attempts);
+                self.login_attempt_repository.record_lockout(NewLoginLockout {
+                    username: None,
+                    ip_address: Some(ip_address.to_string()),
+                    failed_attempts: ip_stats.failed_attempts as i32,
+                    locked_until: until,
+                }).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ListLockoutsUseCase<L: LoginAttemptRepository, R: RoleRepository> {
+    login_attempt_repository: Arc<L>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<L: LoginAttemptRepository, R: RoleRepository> ListLockoutsUseCase<L, R> {
+    pub fn new(login_attempt_repository: Arc<L>, role_repository: Arc<R>) -> Self {
+        Self {
+            login_attempt_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, admin_id: i32) -> Result<Vec<LoginLockout>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+        self.login_attempt_repository
+            .find_lockouts(LOCKOUT_LOG_LIMIT)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+}
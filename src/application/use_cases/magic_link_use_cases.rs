@@ -0,0 +1,103 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::{debug, info};
+
+use crate::application::use_cases::auth_use_cases::complete_login;
+use crate::domain::entities::auth::TokenPurpose;
+use crate::domain::entities::mfa::LoginResponse;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::mailer::{Mailer, OutgoingEmail};
+use crate::domain::services::token_service::TokenService;
+
+const MAGIC_LINK_TTL_MINUTES: i64 = 15;
+const MAGIC_LINK_MAX_REQUESTS_PER_HOUR: i64 = 5;
+
+pub struct RequestMagicLinkUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    mailer: Arc<dyn Mailer>,
+    magic_link_url: String,
+}
+
+impl<T: AuthRepository> RequestMagicLinkUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>, mailer: Arc<dyn Mailer>, magic_link_url: String) -> Self {
+        Self { auth_repository, token_service, mailer, magic_link_url }
+    }
+
+    /// Mails a sign-in link if the address belongs to an account. Like password resets,
+    /// unknown addresses and rate-limited requests succeed silently.
+    pub async fn execute(&self, email: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = match self.auth_repository.find_by_email(email).await? {
+            Some(user) => user,
+            None => return Ok(()),
+        };
+
+        let since = (Utc::now() - Duration::hours(1)).naive_utc();
+        let recent_requests = self.auth_repository
+            .count_tokens_since(user.id, TokenPurpose::MagicLink, since)
+            .await?;
+        if recent_requests >= MAGIC_LINK_MAX_REQUESTS_PER_HOUR {
+            debug!("Magic link rate limit reached for user {}", user.id);
+            return Ok(());
+        }
+
+        let token = self.token_service.generate(TokenPurpose::MagicLink)?;
+        let expires_at = (Utc::now() + Duration::minutes(MAGIC_LINK_TTL_MINUTES)).naive_utc();
+        self.auth_repository
+            .create_token(user.id, TokenPurpose::MagicLink, self.token_service.hash(&token), expires_at)
+            .await?;
+
+        let link = format!("{}?token={}", self.magic_link_url, token);
+        self.mailer.send(OutgoingEmail {
+            to: user.email,
+            subject: "Your sign-in link".to_string(),
+            body: format!(
+                "Hi {},\n\nOpen the link below to sign in:\n\n{}\n\nThe link works once and expires in {} minutes. If you did not ask to sign in, you can ignore this email.",
+                user.username, link, MAGIC_LINK_TTL_MINUTES
+            ),
+        }).await
+    }
+}
+
+pub struct VerifyMagicLinkUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+}
+
+impl<T: AuthRepository> VerifyMagicLinkUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>, access_tokens: Arc<AccessTokenService>) -> Self {
+        Self { auth_repository, token_service, access_tokens }
+    }
+
+    /// Exchanges the token from the link for an access token. Receiving the link proves
+    /// the email address, so it is marked verified; users with 2FA still get an MFA
+    /// challenge instead of the token.
+    pub async fn execute(&self, token: &str, client: &ClientInfo) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid = || -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(IoError::new(ErrorKind::InvalidInput, "Invalid or expired sign-in link"))
+        };
+
+        if !self.token_service.verify(TokenPurpose::MagicLink, token) {
+            return Err(invalid());
+        }
+
+        let user_id = self.auth_repository
+            .consume_token(TokenPurpose::MagicLink, self.token_service.hash(token))
+            .await?
+            .ok_or_else(invalid)?;
+
+        let mut user = self.auth_repository.find_by_id(user_id).await?.ok_or_else(invalid)?;
+
+        if user.email_verified_at.is_none() {
+            self.auth_repository.mark_email_verified(user.id).await?;
+            user.email_verified_at = Some(Utc::now().naive_utc());
+        }
+
+        info!("User {} signed in with a magic link", user.id);
+        complete_login(&self.auth_repository, &self.token_service, &self.access_tokens, &user, client, false).await
+    }
+}
@@ -1,23 +1,59 @@
-use crate::domain::entities::message::DatabaseMessage;
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use tracing::{debug, warn};
+use crate::application::use_cases::contact_use_cases::{CheckMessagePermissionUseCase, STRANGER_MESSAGES_DISABLED};
+use crate::domain::entities::message::{DatabaseMessage, SendMessageDto};
+use crate::domain::repositories::contact_repository::ContactRepository;
 use crate::domain::repositories::message_repository::MessageRepository;
+use crate::domain::repositories::moderation_repository::ModerationRepository;
 
-pub struct SendMessageUseCase<T: MessageRepository> {
+pub struct SendMessageUseCase<T: MessageRepository, C: ContactRepository, M: ModerationRepository> {
     message_repository: T,
+    message_permission: CheckMessagePermissionUseCase<C, M>,
 }
 
-impl<T: MessageRepository> SendMessageUseCase<T> {
-    pub fn new(message_repository: T) -> Self {
-        Self { message_repository }
+impl<T: MessageRepository, C: ContactRepository, M: ModerationRepository> SendMessageUseCase<T, C, M> {
+    pub fn new(message_repository: T, contact_repository: Arc<C>, moderation_repository: Arc<M>) -> Self {
+        Self {
+            message_repository,
+            message_permission: CheckMessagePermissionUseCase::new(contact_repository, moderation_repository),
+        }
     }
 
-    pub async fn execute(&self, sender_id: i32, receiver_id: i32, content: String) -> Result<DatabaseMessage, String> {
+    pub async fn execute(&self, sender_id: i32, message_dto: SendMessageDto) -> Result<DatabaseMessage, String> {
+        let receiver_id = message_dto.receiver_id;
+        let allowed = self.message_permission
+            .execute(sender_id, receiver_id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if !allowed {
+            return Err(STRANGER_MESSAGES_DISABLED.to_string());
+        }
+
+        let now = Utc::now().naive_utc();
+
+        // A delivery time in the past just means "send now"
+        let deliver_at = message_dto.deliver_at.filter(|deliver_at| *deliver_at > now);
+
+        let expires_at = match message_dto.ttl_seconds {
+            Some(ttl) if ttl <= 0 => return Err("ttl_seconds must be greater than zero".to_string()),
+            Some(ttl) => Some(deliver_at.unwrap_or(now) + Duration::seconds(ttl)),
+            None => None,
+        };
+
         let message = DatabaseMessage {
             id: 0, // Will be set by the database
             sender_id,
             receiver_id,
-            content,
+            content: message_dto.content,
             is_read: false,
-            created_at: chrono::Utc::now().naive_utc(),
+            created_at: now,
+            is_hidden: false,
+            deliver_at,
+            expires_at,
+            is_delivered: deliver_at.is_none(),
         };
         self.message_repository.save_message(message).await
     }
@@ -33,6 +69,82 @@ impl<T: MessageRepository> GetMessagesUseCase<T> {
     }
 
     pub async fn execute(&self, user1_id: i32, user2_id: i32) -> Result<Vec<DatabaseMessage>, String> {
-        self.message_repository.get_messages(user1_id, user2_id).await
+        let now = Utc::now().naive_utc();
+        let messages = self.message_repository.get_messages(user1_id, user2_id).await?;
+        Ok(messages.into_iter().filter(|message| !message.is_expired(now)).collect())
     }
-}
\ No newline at end of file
+}
+
+pub struct GetScheduledMessagesUseCase<T: MessageRepository> {
+    message_repository: T,
+}
+
+impl<T: MessageRepository> GetScheduledMessagesUseCase<T> {
+    pub fn new(message_repository: T) -> Self {
+        Self { message_repository }
+    }
+
+    pub async fn execute(&self, sender_id: i32) -> Result<Vec<DatabaseMessage>, String> {
+        self.message_repository.get_scheduled_messages(sender_id).await
+    }
+}
+
+pub struct DeliverDueMessagesUseCase<T: MessageRepository, C: ContactRepository, M: ModerationRepository> {
+    message_repository: T,
+    message_permission: CheckMessagePermissionUseCase<C, M>,
+}
+
+impl<T: MessageRepository, C: ContactRepository, M: ModerationRepository> DeliverDueMessagesUseCase<T, C, M> {
+    pub fn new(message_repository: T, contact_repository: Arc<C>, moderation_repository: Arc<M>) -> Self {
+        Self {
+            message_repository,
+            message_permission: CheckMessagePermissionUseCase::new(contact_repository, moderation_repository),
+        }
+    }
+
+    /// Marks every scheduled message due at `now` as delivered and returns them for pushing.
+    ///
+    /// The sender may have been suspended or lost permission to message the recipient since
+    /// scheduling, so both are checked again and refused messages are dropped.
+    pub async fn execute(&self, now: NaiveDateTime) -> Result<Vec<DatabaseMessage>, String> {
+        let mut delivered = Vec::new();
+
+        for message in self.message_repository.find_due_messages(now).await? {
+            let allowed = match self.message_permission.execute(message.sender_id, message.receiver_id).await {
+                Ok(allowed) => allowed,
+                Err(e) if e.downcast_ref::<IoError>().map(|io_error| io_error.kind()) == Some(ErrorKind::PermissionDenied) => false,
+                Err(e) => {
+                    // Left scheduled, so the next tick tries again
+                    warn!("Could not check scheduled message {}: {}", message.id, e);
+                    continue;
+                }
+            };
+
+            if !allowed {
+                debug!("Dropping scheduled message {}: sender may no longer message the recipient", message.id);
+                self.message_repository.delete_message(message.id).await?;
+                continue;
+            }
+
+            if self.message_repository.mark_as_delivered(message.id).await? {
+                delivered.push(DatabaseMessage { is_delivered: true, ..message });
+            }
+        }
+
+        Ok(delivered)
+    }
+}
+
+pub struct PurgeExpiredMessagesUseCase<T: MessageRepository> {
+    message_repository: T,
+}
+
+impl<T: MessageRepository> PurgeExpiredMessagesUseCase<T> {
+    pub fn new(message_repository: T) -> Self {
+        Self { message_repository }
+    }
+
+    pub async fn execute(&self, now: NaiveDateTime) -> Result<usize, String> {
+        self.message_repository.delete_expired(now).await
+    }
+}
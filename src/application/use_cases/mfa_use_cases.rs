@@ -0,0 +1,192 @@
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::application::use_cases::audit_use_cases::record_audit_event;
+use crate::application::use_cases::auth_use_cases::issue_access_token;
+use crate::application::use_cases::login_attempt_use_cases::LoginThrottleUseCase;
+use crate::domain::entities::audit::{AuditAction, NewAuditEvent};
+use crate::domain::entities::auth::{TokenPurpose, TokenResponse};
+use crate::domain::entities::mfa::{DisableTotpDto, MfaLoginDto, RecoveryCodes, TotpEnrollment};
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::totp;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+fn invalid_code() -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        "Invalid two-factor code"
+    ))
+}
+
+/// Checks a TOTP code (rejecting replays) or, failing that, burns a matching recovery code.
+async fn verify_second_factor<T: AuthRepository>(
+    auth_repository: &T,
+    token_service: &TokenService,
+    user_id: i32,
+    code: &str,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let totp = match auth_repository.find_totp(user_id).await? {
+        Some(totp) if totp.is_enabled() => totp,
+        _ => return Ok(false),
+    };
+
+    if let Some(step) = totp::verify(&totp.secret, code, Utc::now().timestamp()) {
+        return auth_repository.record_totp_step(user_id, step).await;
+    }
+
+    let recovery_code = code.trim().to_ascii_lowercase();
+    auth_repository.consume_recovery_code(user_id, token_service.hash(&recovery_code)).await
+}
+
+pub struct CompleteMfaLoginUseCase<T: AuthRepository, L: LoginAttemptRepository> {
+    auth_repository: T,
+    login_throttle: LoginThrottleUseCase<L>,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+}
+
+impl<T: AuthRepository, L: LoginAttemptRepository> CompleteMfaLoginUseCase<T, L> {
+    pub fn new(auth_repository: T, login_attempt_repository: Arc<L>, token_service: Arc<TokenService>, access_tokens: Arc<AccessTokenService>) -> Self {
+        Self {
+            auth_repository,
+            login_throttle: LoginThrottleUseCase::new(login_attempt_repository),
+            token_service,
+            access_tokens,
+        }
+    }
+
+    pub async fn execute(&self, mfa_dto: MfaLoginDto, client: &ClientInfo) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid_challenge = || -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid or expired MFA token"
+            ))
+        };
+
+        if !self.token_service.verify(TokenPurpose::MfaChallenge, &mfa_dto.mfa_token) {
+            return Err(invalid_challenge());
+        }
+
+        // The challenge is spent even when the code is wrong, so guessing codes
+        // costs a full password login per attempt
+        let user_id = self.auth_repository
+            .consume_token(TokenPurpose::MfaChallenge, self.token_service.hash(&mfa_dto.mfa_token))
+            .await?
+            .ok_or_else(invalid_challenge)?;
+
+        let user = self.auth_repository.find_by_id(user_id).await?.ok_or_else(invalid_challenge)?;
+        let ip_address = client.ip_address.as_deref();
+        // Wrong codes count like wrong passwords, so the lockout also caps code guessing
+        self.login_throttle.check(&user.username, ip_address).await?;
+
+        if !verify_second_factor(&self.auth_repository, &self.token_service, user_id, &mfa_dto.code).await? {
+            self.login_throttle.record_failure(&user.username, ip_address).await?;
+            return Err(invalid_code());
+        }
+
+        // The sign-in is complete, so earlier failed passwords stop counting
+        self.login_throttle.record_success(&user.username, ip_address).await?;
+        issue_access_token(&self.auth_repository, &self.access_tokens, &user, client).await
+    }
+}
+
+pub struct TotpEnrollmentUseCase<T: AuthRepository> {
+    auth_repository: T,
+    token_service: Arc<TokenService>,
+    issuer: String,
+    audit_repository: Arc<dyn AuditRepository>,
+}
+
+impl<T: AuthRepository> TotpEnrollmentUseCase<T> {
+    pub fn new(auth_repository: T, token_service: Arc<TokenService>, issuer: String, audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self { auth_repository, token_service, issuer, audit_repository }
+    }
+
+    async fn audit<R>(&self, action: AuditAction, user_id: i32, result: &Result<R, Box<dyn std::error::Error + Send + Sync>>, client: &ClientInfo) {
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: Some(user_id),
+            target_user_id: Some(user_id),
+            ..NewAuditEvent::from_result(action, result, client)
+        }).await;
+    }
+
+    /// Starts (or restarts) enrollment. 2FA stays off until `confirm` sees a valid code.
+    pub async fn enroll(&self, user_id: i32) -> Result<TotpEnrollment, Box<dyn std::error::Error + Send + Sync>> {
+        if self.auth_repository.find_totp(user_id).await?.is_some_and(|totp| totp.is_enabled()) {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "Two-factor authentication is already enabled"
+            )));
+        }
+
+        let user = self.auth_repository.find_by_id(user_id).await?
+            .ok_or_else(|| Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "User not found")) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let secret = totp::generate_secret()?;
+        self.auth_repository.save_totp_secret(user_id, secret.clone()).await?;
+
+        Ok(TotpEnrollment {
+            otpauth_uri: totp::otpauth_uri(&self.issuer, &user.username, &secret),
+            secret,
+        })
+    }
+
+    pub async fn confirm(&self, user_id: i32, code: &str, client: &ClientInfo) -> Result<RecoveryCodes, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.enable(user_id, code).await;
+        self.audit(AuditAction::TotpEnabled, user_id, &result, client).await;
+        result
+    }
+
+    async fn enable(&self, user_id: i32, code: &str) -> Result<RecoveryCodes, Box<dyn std::error::Error + Send + Sync>> {
+        let pending = match self.auth_repository.find_totp(user_id).await? {
+            Some(totp) if !totp.is_enabled() => totp,
+            Some(_) => return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "Two-factor authentication is already enabled"
+            ))),
+            None => return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No two-factor enrollment in progress"
+            ))),
+        };
+
+        let step = totp::verify(&pending.secret, code, Utc::now().timestamp()).ok_or_else(invalid_code)?;
+        self.auth_repository.record_totp_step(user_id, step).await?;
+
+        let recovery_codes = (0..RECOVERY_CODE_COUNT)
+            .map(|_| totp::generate_recovery_code())
+            .collect::<Result<Vec<_>, _>>()?;
+        let hashes = recovery_codes.iter().map(|code| self.token_service.hash(code)).collect();
+
+        self.auth_repository.confirm_totp(user_id, hashes).await?;
+
+        Ok(RecoveryCodes { recovery_codes })
+    }
+
+    pub async fn disable(&self, user_id: i32, disable_dto: DisableTotpDto, client: &ClientInfo) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.remove(user_id, disable_dto).await;
+        self.audit(AuditAction::TotpDisabled, user_id, &result, client).await;
+        result
+    }
+
+    async fn remove(&self, user_id: i32, disable_dto: DisableTotpDto) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.auth_repository.verify_password(user_id, &disable_dto.password).await? {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Password is incorrect"
+            )));
+        }
+
+        if !verify_second_factor(&self.auth_repository, &self.token_service, user_id, &disable_dto.code).await? {
+            return Err(invalid_code());
+        }
+
+        self.auth_repository.delete_totp(user_id).await
+    }
+}
@@ -2,4 +2,21 @@ pub mod user_use_cases;
 pub mod auth_use_cases;
 pub mod account_use_cases;
 pub mod message_use_cases;
-pub mod avatar_use_cases;
\ No newline at end of file
+pub mod avatar_use_cases;
+pub mod contact_use_cases;
+pub mod moderation_use_cases;
+pub mod role_use_cases;
+pub mod announcement_use_cases;
+pub mod notification_use_cases;
+pub mod mfa_use_cases;
+
+pub mod login_attempt_use_cases;
+pub mod oidc_use_cases;
+pub mod oauth_use_cases;
+pub mod api_key_use_cases;
+pub mod session_use_cases;
+pub mod passkey_use_cases;
+pub mod magic_link_use_cases;
+pub mod registration_use_cases;
+pub mod impersonation_use_cases;
+pub mod audit_use_cases;
\ No newline at end of file
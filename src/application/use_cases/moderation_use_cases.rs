@@ -0,0 +1,206 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::moderation::{
+    CreateReportDto, MessageReport, ModerationAction, ModerationActionType, NewModerationAction,
+    ReportStatus, ReportedMessage, SuspendUserDto, UserSuspension,
+};
+use crate::domain::repositories::moderation_repository::ModerationRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+
+const MODERATION_LOG_LIMIT: i64 = 200;
+
+pub struct ReportMessageUseCase<M: ModerationRepository> {
+    moderation_repository: Arc<M>,
+}
+
+impl<M: ModerationRepository> ReportMessageUseCase<M> {
+    pub fn new(moderation_repository: Arc<M>) -> Self {
+        Self { moderation_repository }
+    }
+
+    pub async fn execute(&self, reporter_id: i32, report: CreateReportDto) -> Result<MessageReport, Box<dyn std::error::Error>> {
+        let message = self.moderation_repository.find_message(report.message_id).await?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "Message not found"))?;
+
+        // Only participants of the conversation can see, and therefore report, a message
+        if message.sender_id != reporter_id && message.receiver_id != reporter_id {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "Message not found")));
+        }
+
+        if message.sender_id == reporter_id {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "You cannot report your own message"
+            )));
+        }
+
+        self.moderation_repository.create_report(reporter_id, report).await
+    }
+}
+
+pub struct ListReportsUseCase<M: ModerationRepository, R: RoleRepository> {
+    moderation_repository: Arc<M>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> ListReportsUseCase<M, R> {
+    pub fn new(moderation_repository: Arc<M>, role_repository: Arc<R>) -> Self {
+        Self {
+            moderation_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, moderator_id: i32, status: Option<ReportStatus>) -> Result<Vec<ReportedMessage>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+        self.moderation_repository.find_reports(status).await
+    }
+}
+
+pub struct DismissReportUseCase<M: ModerationRepository, R: RoleRepository> {
+    moderation_repository: Arc<M>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> DismissReportUseCase<M, R> {
+    pub fn new(moderation_repository: Arc<M>, role_repository: Arc<R>) -> Self {
+        Self {
+            moderation_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, moderator_id: i32, report_id: i32, note: Option<String>) -> Result<MessageReport, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+
+        let report = self.moderation_repository.find_report(report_id).await?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "Report not found"))?;
+
+        if report.status != ReportStatus::Open {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "Report has already been resolved"
+            )));
+        }
+
+        self.moderation_repository.resolve_report(report_id, ReportStatus::Dismissed, NewModerationAction {
+            moderator_id,
+            action: ModerationActionType::DismissReport,
+            target_user_id: None,
+            target_message_id: Some(report.message_id),
+            report_id: Some(report.id),
+            note,
+        }).await
+    }
+}
+
+pub struct HideMessageUseCase<M: ModerationRepository, R: RoleRepository> {
+    moderation_repository: Arc<M>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> HideMessageUseCase<M, R> {
+    pub fn new(moderation_repository: Arc<M>, role_repository: Arc<R>) -> Self {
+        Self {
+            moderation_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    /// Hides (or restores) a message and closes any open reports against it.
+    pub async fn execute(&self, moderator_id: i32, message_id: i32, hidden: bool, note: Option<String>) -> Result<ModerationAction, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+
+        let message = self.moderation_repository.find_message(message_id).await?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "Message not found"))?;
+
+        let action = if hidden {
+            ModerationActionType::HideMessage
+        } else {
+            ModerationActionType::UnhideMessage
+        };
+
+        self.moderation_repository.set_message_hidden(message_id, hidden, NewModerationAction {
+            moderator_id,
+            action,
+            target_user_id: Some(message.sender_id),
+            target_message_id: Some(message_id),
+            report_id: None,
+            note,
+        }).await
+    }
+}
+
+pub struct SuspendUserUseCase<M: ModerationRepository, R: RoleRepository> {
+    moderation_repository: Arc<M>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> SuspendUserUseCase<M, R> {
+    pub fn new(moderation_repository: Arc<M>, role_repository: Arc<R>) -> Self {
+        Self {
+            moderation_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, moderator_id: i32, user_id: i32, suspension: SuspendUserDto) -> Result<UserSuspension, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+
+        if moderator_id == user_id {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "You cannot suspend yourself"
+            )));
+        }
+
+        let action = NewModerationAction {
+            moderator_id,
+            action: ModerationActionType::SuspendUser,
+            target_user_id: Some(user_id),
+            target_message_id: None,
+            report_id: None,
+            note: suspension.reason.clone(),
+        };
+
+        self.moderation_repository
+            .suspend_user(user_id, suspension.reason, suspension.suspended_until, action)
+            .await
+    }
+
+    pub async fn lift(&self, moderator_id: i32, user_id: i32, note: Option<String>) -> Result<ModerationAction, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+
+        let action = NewModerationAction {
+            moderator_id,
+            action: ModerationActionType::LiftSuspension,
+            target_user_id: Some(user_id),
+            target_message_id: None,
+            report_id: None,
+            note,
+        };
+
+        self.moderation_repository.lift_suspensions(user_id, action).await?
+            .ok_or_else(|| Box::new(IoError::new(ErrorKind::NotFound, "User is not suspended")) as Box<dyn std::error::Error>)
+    }
+}
+
+pub struct ListModerationActionsUseCase<M: ModerationRepository, R: RoleRepository> {
+    moderation_repository: Arc<M>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> ListModerationActionsUseCase<M, R> {
+    pub fn new(moderation_repository: Arc<M>, role_repository: Arc<R>) -> Self {
+        Self {
+            moderation_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn execute(&self, moderator_id: i32) -> Result<Vec<ModerationAction>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(moderator_id, ADMIN_ROLES).await?;
+        self.moderation_repository.find_actions(MODERATION_LOG_LIMIT).await
+    }
+}
@@ -0,0 +1,45 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use crate::domain::entities::notification::{NotificationMode, NotificationPreference, UpdateNotificationPreferenceDto};
+use crate::domain::repositories::notification_repository::NotificationRepository;
+
+pub struct NotificationPreferencesUseCase<N: NotificationRepository> {
+    notification_repository: Arc<N>,
+}
+
+impl<N: NotificationRepository> NotificationPreferencesUseCase<N> {
+    pub fn new(notification_repository: Arc<N>) -> Self {
+        Self { notification_repository }
+    }
+
+    pub async fn list(&self, user_id: i32) -> Result<Vec<NotificationPreference>, Box<dyn std::error::Error>> {
+        self.notification_repository.find_all_for_user(user_id).await
+    }
+
+    pub async fn get(&self, user_id: i32, counterpart_user_id: i32) -> Result<Option<NotificationPreference>, Box<dyn std::error::Error>> {
+        self.notification_repository.find_for_counterpart(user_id, counterpart_user_id).await
+    }
+
+    pub async fn update(&self, user_id: i32, counterpart_user_id: i32, preference_dto: UpdateNotificationPreferenceDto) -> Result<NotificationPreference, Box<dyn std::error::Error>> {
+        if user_id == counterpart_user_id {
+            return Err(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "You cannot set notification preferences for yourself"
+            )));
+        }
+
+        // An expiry only makes sense for a mute
+        let muted_until = match preference_dto.mode {
+            NotificationMode::Muted => preference_dto.muted_until,
+            _ => None,
+        };
+
+        self.notification_repository
+            .upsert_for_counterpart(user_id, counterpart_user_id, preference_dto.mode, muted_until)
+            .await
+    }
+
+    pub async fn reset(&self, user_id: i32, counterpart_user_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        self.notification_repository.delete_for_counterpart(user_id, counterpart_user_id).await
+    }
+}
@@ -0,0 +1,409 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use reqwest::Url;
+use tracing::info;
+
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::auth::TokenPurpose;
+use crate::domain::entities::oauth::{
+    AuthorizationGrant, AuthorizationRequest, CreateOAuthClientDto, CreatedOAuthClient, IdTokenClaims, Jwks,
+    NewAuthorizationCode, NewOAuthClient, OAuthAccessTokenClaims, OAuthClient, OAuthError, OAuthTokenResponse,
+    OpenIdConfiguration, TokenRequest, UserProfile, GRANT_AUTHORIZATION_CODE, GRANT_CLIENT_CREDENTIALS,
+    SCOPE_EMAIL, SCOPE_OPENID, SCOPE_PROFILE, STANDARD_SCOPES, SUPPORTED_GRANT_TYPES,
+};
+use crate::domain::repositories::oauth_repository::OAuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::services::oidc::pkce_challenge;
+use crate::domain::services::signing_key::SigningKeys;
+use crate::domain::services::token_service::TokenService;
+
+const AUTHORIZATION_CODE_TTL_MINUTES: i64 = 10;
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 60;
+const ID_TOKEN_TTL_MINUTES: i64 = 60;
+const MAX_CLIENT_NAME_LENGTH: usize = 100;
+
+fn split_scope(scope: &str) -> Vec<&str> {
+    scope.split_whitespace().collect()
+}
+
+fn invalid_input(message: &str) -> Box<dyn std::error::Error> {
+    Box::new(IoError::new(ErrorKind::InvalidInput, message.to_string()))
+}
+
+pub struct ManageOAuthClientsUseCase<O: OAuthRepository, R: RoleRepository> {
+    oauth_repository: Arc<O>,
+    token_service: Arc<TokenService>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<O: OAuthRepository, R: RoleRepository> ManageOAuthClientsUseCase<O, R> {
+    pub fn new(oauth_repository: Arc<O>, token_service: Arc<TokenService>, role_repository: Arc<R>) -> Self {
+        Self {
+            oauth_repository,
+            token_service,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    /// Registers a client. The secret is returned here and never again.
+    pub async fn create(&self, admin_id: i32, client_dto: CreateOAuthClientDto) -> Result<CreatedOAuthClient, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let name = client_dto.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_CLIENT_NAME_LENGTH {
+            return Err(invalid_input("Client name must be between 1 and 100 characters"));
+        }
+
+        let grant_types = client_dto.grant_types.unwrap_or_else(|| vec![GRANT_AUTHORIZATION_CODE.to_string()]);
+        if grant_types.is_empty() || grant_types.iter().any(|grant| !SUPPORTED_GRANT_TYPES.contains(&grant.as_str())) {
+            return Err(invalid_input("Unsupported grant type"));
+        }
+
+        let confidential = client_dto.confidential.unwrap_or(true);
+        if !confidential && grant_types.iter().any(|grant| grant == GRANT_CLIENT_CREDENTIALS) {
+            return Err(invalid_input("Public clients cannot use the client_credentials grant"));
+        }
+
+        let uses_redirects = grant_types.iter().any(|grant| grant == GRANT_AUTHORIZATION_CODE);
+        if uses_redirects && client_dto.redirect_uris.is_empty() {
+            return Err(invalid_input("At least one redirect URI is required"));
+        }
+        for redirect_uri in &client_dto.redirect_uris {
+            match Url::parse(redirect_uri) {
+                Ok(url) if url.fragment().is_none() => {}
+                _ => return Err(invalid_input("Redirect URIs must be absolute URLs without a fragment")),
+            }
+        }
+
+        let scopes = client_dto.scopes
+            .unwrap_or_else(|| STANDARD_SCOPES.iter().map(|scope| scope.to_string()).collect());
+        if scopes.iter().any(|scope| scope.is_empty() || scope.contains(char::is_whitespace)) {
+            return Err(invalid_input("Scopes must be non-empty and contain no whitespace"));
+        }
+
+        let client_secret = if confidential {
+            Some(self.token_service.random().map_err(|e| e as Box<dyn std::error::Error>)?)
+        } else {
+            None
+        };
+
+        let client = self.oauth_repository
+            .create_client(NewOAuthClient {
+                client_id: uuid::Uuid::new_v4().simple().to_string(),
+                client_secret_hash: client_secret.as_deref().map(|secret| self.token_service.hash(secret)),
+                name,
+                redirect_uris: client_dto.redirect_uris,
+                grant_types,
+                scopes,
+                created_by: admin_id,
+            })
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        info!("OAuth client {} ({}) registered by user {}", client.client_id, client.name, admin_id);
+
+        Ok(CreatedOAuthClient { client, client_secret })
+    }
+
+    pub async fn list(&self, admin_id: i32) -> Result<Vec<OAuthClient>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+        self.oauth_repository
+            .find_clients()
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+
+    pub async fn delete(&self, admin_id: i32, client_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let deleted = self.oauth_repository
+            .delete_client(client_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        if !deleted {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "Client not found")));
+        }
+
+        info!("OAuth client {} deleted by user {}", client_id, admin_id);
+        Ok(())
+    }
+}
+
+/// The authorization endpoint. The browser first lands on `consent_url`, where the user
+/// signs in and approves; the consent page then calls `approve` with the user's token.
+pub struct AuthorizeUseCase<O: OAuthRepository> {
+    oauth_repository: Arc<O>,
+    token_service: Arc<TokenService>,
+    consent_url: String,
+}
+
+impl<O: OAuthRepository> AuthorizeUseCase<O> {
+    pub fn new(oauth_repository: Arc<O>, token_service: Arc<TokenService>, consent_url: String) -> Self {
+        Self { oauth_repository, token_service, consent_url }
+    }
+
+    /// Checks the request and returns the consent page URL carrying the same query string.
+    pub async fn start(&self, request: &AuthorizationRequest, query_string: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.validate(request).await?;
+
+        let separator = if self.consent_url.contains('?') { '&' } else { '?' };
+        Ok(format!("{}{}{}", self.consent_url, separator, query_string))
+    }
+
+    pub async fn approve(&self, user_id: i32, request: AuthorizationRequest) -> Result<AuthorizationGrant, Box<dyn std::error::Error + Send + Sync>> {
+        let (scope, code_challenge) = self.validate(&request).await?;
+
+        let code = self.token_service.generate(TokenPurpose::OAuthCode)?;
+        self.oauth_repository.save_authorization_code(NewAuthorizationCode {
+            code_hash: self.token_service.hash(&code),
+            client_id: request.client_id.clone(),
+            user_id,
+            redirect_uri: request.redirect_uri.clone(),
+            scope,
+            code_challenge,
+            nonce: request.nonce,
+            expires_at: (Utc::now() + Duration::minutes(AUTHORIZATION_CODE_TTL_MINUTES)).naive_utc(),
+        }).await?;
+
+        info!("User {} authorized OAuth client {}", user_id, request.client_id);
+
+        let mut redirect_to = Url::parse(&request.redirect_uri)?;
+        redirect_to.query_pairs_mut().append_pair("code", &code);
+        if let Some(state) = &request.state {
+            redirect_to.query_pairs_mut().append_pair("state", state);
+        }
+
+        Ok(AuthorizationGrant { redirect_to: redirect_to.to_string() })
+    }
+
+    /// Returns the granted scope and the PKCE challenge. The redirect URI must match a
+    /// registered one exactly, and PKCE (S256) is required of every client.
+    async fn validate(&self, request: &AuthorizationRequest) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
+        let client = self.oauth_repository
+            .find_client(&request.client_id)
+            .await?
+            .ok_or_else(|| OAuthError::invalid_client("Unknown client"))?;
+
+        if !client.redirect_uris.iter().any(|registered| registered == &request.redirect_uri) {
+            return Err(Box::new(OAuthError::invalid_request("redirect_uri is not registered for this client")));
+        }
+        if request.response_type != "code" {
+            return Err(Box::new(OAuthError::unsupported_response_type("Only the code response type is supported")));
+        }
+        if !client.allows_grant(GRANT_AUTHORIZATION_CODE) {
+            return Err(Box::new(OAuthError::unauthorized_client("Client may not use the authorization code grant")));
+        }
+
+        let scope = request.scope.as_deref().unwrap_or(SCOPE_OPENID);
+        let scopes = split_scope(scope);
+        if scopes.is_empty() || !client.allows_scopes(&scopes) {
+            return Err(Box::new(OAuthError::invalid_scope("Requested scope is not allowed for this client")));
+        }
+
+        let code_challenge = match (request.code_challenge.as_deref(), request.code_challenge_method.as_deref()) {
+            (Some(challenge), Some("S256")) if !challenge.is_empty() => challenge.to_string(),
+            _ => return Err(Box::new(OAuthError::invalid_request("A PKCE code_challenge with method S256 is required"))),
+        };
+
+        Ok((scopes.join(" "), code_challenge))
+    }
+}
+
+/// The token endpoint: exchanges authorization codes and serves client credentials.
+pub struct IssueOAuthTokenUseCase<O: OAuthRepository> {
+    oauth_repository: Arc<O>,
+    token_service: Arc<TokenService>,
+    signing_keys: Arc<SigningKeys>,
+    issuer: String,
+}
+
+impl<O: OAuthRepository> IssueOAuthTokenUseCase<O> {
+    pub fn new(oauth_repository: Arc<O>, token_service: Arc<TokenService>, signing_keys: Arc<SigningKeys>, issuer: String) -> Self {
+        Self { oauth_repository, token_service, signing_keys, issuer }
+    }
+
+    /// `basic_credentials` are the client id and secret from an HTTP Basic header, if any.
+    pub async fn execute(&self, request: TokenRequest, basic_credentials: Option<(String, String)>) -> Result<OAuthTokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let (client_id, client_secret) = match basic_credentials {
+            Some((client_id, client_secret)) => (Some(client_id), Some(client_secret)),
+            None => (request.client_id.clone(), request.client_secret.clone()),
+        };
+        let client_id = client_id.ok_or_else(|| OAuthError::invalid_client("Client authentication is required"))?;
+        let client = self.authenticate_client(&client_id, client_secret.as_deref()).await?;
+
+        match request.grant_type.as_str() {
+            GRANT_AUTHORIZATION_CODE => self.exchange_code(&client, request).await,
+            GRANT_CLIENT_CREDENTIALS => self.client_credentials(&client, request).await,
+            _ => Err(Box::new(OAuthError::unsupported_grant_type("Unsupported grant type"))),
+        }
+    }
+
+    async fn authenticate_client(&self, client_id: &str, client_secret: Option<&str>) -> Result<OAuthClient, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid_client = || OAuthError::invalid_client("Client authentication failed");
+
+        let client = self.oauth_repository
+            .find_client(client_id)
+            .await?
+            .ok_or_else(invalid_client)?;
+
+        match (&client.client_secret_hash, client_secret) {
+            (Some(secret_hash), Some(secret)) if *secret_hash == self.token_service.hash(secret) => Ok(client),
+            (None, None) => Ok(client),
+            _ => Err(Box::new(invalid_client())),
+        }
+    }
+
+    async fn exchange_code(&self, client: &OAuthClient, request: TokenRequest) -> Result<OAuthTokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid_grant = || OAuthError::invalid_grant("Invalid or expired authorization code");
+
+        if !client.allows_grant(GRANT_AUTHORIZATION_CODE) {
+            return Err(Box::new(OAuthError::unauthorized_client("Client may not use the authorization code grant")));
+        }
+
+        let code = request.code.ok_or_else(|| OAuthError::invalid_request("code is required"))?;
+        let code_verifier = request.code_verifier.ok_or_else(|| OAuthError::invalid_request("code_verifier is required"))?;
+
+        if !self.token_service.verify(TokenPurpose::OAuthCode, &code) {
+            return Err(Box::new(invalid_grant()));
+        }
+
+        // Spent before the checks below, so a stolen code is useless after one failed try
+        let authorization = self.oauth_repository
+            .consume_authorization_code(self.token_service.hash(&code))
+            .await?
+            .ok_or_else(invalid_grant)?;
+
+        if authorization.client_id != client.client_id
+            || request.redirect_uri.as_deref() != Some(authorization.redirect_uri.as_str())
+            || pkce_challenge(&code_verifier) != authorization.code_challenge
+        {
+            return Err(Box::new(invalid_grant()));
+        }
+
+        let profile = self.oauth_repository
+            .find_user_profile(authorization.user_id)
+            .await?
+            .ok_or_else(invalid_grant)?;
+
+        let scopes = split_scope(&authorization.scope);
+        let id_token = if scopes.contains(&SCOPE_OPENID) {
+            Some(self.signing_keys.sign(&self.id_token_claims(client, &profile, &scopes, authorization.nonce))?)
+        } else {
+            None
+        };
+
+        self.token_response(client, profile.user_id.to_string(), authorization.scope.clone(), id_token)
+    }
+
+    async fn client_credentials(&self, client: &OAuthClient, request: TokenRequest) -> Result<OAuthTokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        if !client.is_confidential() || !client.allows_grant(GRANT_CLIENT_CREDENTIALS) {
+            return Err(Box::new(OAuthError::unauthorized_client("Client may not use the client credentials grant")));
+        }
+
+        // No user is involved, so the user-facing OpenID scopes make no sense here
+        let scopes = match request.scope.as_deref() {
+            Some(scope) => split_scope(scope),
+            None => client.scopes.iter().map(String::as_str).filter(|scope| !STANDARD_SCOPES.contains(scope)).collect(),
+        };
+        if !client.allows_scopes(&scopes) || scopes.iter().any(|scope| STANDARD_SCOPES.contains(scope)) {
+            return Err(Box::new(OAuthError::invalid_scope("Requested scope is not allowed for this client")));
+        }
+
+        self.token_response(client, client.client_id.clone(), scopes.join(" "), None)
+    }
+
+    fn token_response(&self, client: &OAuthClient, subject: String, scope: String, id_token: Option<String>) -> Result<OAuthTokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let now = Utc::now().timestamp();
+        let expires_in = Duration::minutes(ACCESS_TOKEN_TTL_MINUTES).num_seconds();
+
+        let access_token = self.signing_keys.sign(&OAuthAccessTokenClaims {
+            iss: self.issuer.clone(),
+            sub: subject,
+            aud: client.client_id.clone(),
+            exp: now + expires_in,
+            iat: now,
+            scope: scope.clone(),
+            client_id: client.client_id.clone(),
+        })?;
+
+        Ok(OAuthTokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+            scope,
+            id_token,
+        })
+    }
+
+    /// Standard claims, released according to the `profile` and `email` scopes.
+    fn id_token_claims(&self, client: &OAuthClient, profile: &UserProfile, scopes: &[&str], nonce: Option<String>) -> IdTokenClaims {
+        let now = Utc::now().timestamp();
+        let with_profile = scopes.contains(&SCOPE_PROFILE);
+        let with_email = scopes.contains(&SCOPE_EMAIL);
+
+        let name_parts: Vec<&str> = [&profile.first_name, &profile.middle_name, &profile.last_name]
+            .into_iter()
+            .filter_map(|part| part.as_deref())
+            .filter(|part| !part.is_empty())
+            .collect();
+        let name = (!name_parts.is_empty()).then(|| name_parts.join(" "));
+
+        IdTokenClaims {
+            iss: self.issuer.clone(),
+            sub: profile.user_id.to_string(),
+            aud: client.client_id.clone(),
+            exp: now + Duration::minutes(ID_TOKEN_TTL_MINUTES).num_seconds(),
+            iat: now,
+            nonce,
+            preferred_username: with_profile.then(|| profile.username.clone()),
+            name: name.filter(|_| with_profile),
+            given_name: profile.first_name.clone().filter(|_| with_profile),
+            middle_name: profile.middle_name.clone().filter(|_| with_profile),
+            family_name: profile.last_name.clone().filter(|_| with_profile),
+            updated_at: profile.updated_at.filter(|_| with_profile).map(|updated_at| updated_at.and_utc().timestamp()),
+            email: with_email.then(|| profile.email.clone()),
+            email_verified: with_email.then_some(profile.email_verified),
+        }
+    }
+}
+
+/// Discovery metadata and the public keys relying parties verify our tokens with.
+pub struct OpenIdDiscoveryUseCase {
+    signing_keys: Arc<SigningKeys>,
+    issuer: String,
+}
+
+impl OpenIdDiscoveryUseCase {
+    pub fn new(signing_keys: Arc<SigningKeys>, issuer: String) -> Self {
+        Self { signing_keys, issuer }
+    }
+
+    pub fn configuration(&self) -> OpenIdConfiguration {
+        let issuer = self.issuer.trim_end_matches('/');
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+
+        OpenIdConfiguration {
+            issuer: self.issuer.clone(),
+            authorization_endpoint: format!("{}/api/v1/oauth/authorize", issuer),
+            token_endpoint: format!("{}/api/v1/oauth/token", issuer),
+            jwks_uri: format!("{}/.well-known/jwks.json", issuer),
+            response_types_supported: strings(&["code"]),
+            grant_types_supported: strings(SUPPORTED_GRANT_TYPES),
+            subject_types_supported: strings(&["public"]),
+            id_token_signing_alg_values_supported: vec![self.signing_keys.active_algorithm().to_string()],
+            scopes_supported: strings(STANDARD_SCOPES),
+            token_endpoint_auth_methods_supported: strings(&["client_secret_basic", "client_secret_post", "none"]),
+            code_challenge_methods_supported: strings(&["S256"]),
+            claims_supported: strings(&[
+                "iss", "sub", "aud", "exp", "iat", "nonce", "preferred_username", "name", "given_name",
+                "middle_name", "family_name", "updated_at", "email", "email_verified",
+            ]),
+        }
+    }
+
+    pub fn jwks(&self) -> Jwks {
+        self.signing_keys.jwks()
+    }
+}
@@ -0,0 +1,203 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+use crate::application::use_cases::auth_use_cases::complete_login;
+use crate::domain::entities::auth::{RegisterUserDto, TokenPurpose};
+use crate::domain::entities::identity::{ExternalIdentity, OidcLoginState};
+use crate::domain::entities::mfa::LoginResponse;
+use crate::domain::entities::registration::RegistrationMode;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::entities::user::User;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::identity_repository::IdentityRepository;
+use crate::domain::services::oidc::{pkce_challenge, OidcProvider, OidcProviders};
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::token_service::TokenService;
+
+const LOGIN_STATE_TTL_MINUTES: i64 = 10;
+const MAX_USERNAME_LENGTH: usize = 30;
+
+fn find_provider<'a>(providers: &'a OidcProviders, name: &str) -> Result<&'a Arc<dyn OidcProvider>, Box<dyn std::error::Error + Send + Sync>> {
+    providers.get(name).ok_or_else(|| {
+        Box::new(IoError::new(ErrorKind::NotFound, format!("Unknown sign-in provider: {}", name))) as Box<dyn std::error::Error + Send + Sync>
+    })
+}
+
+fn redirect_uri(base_url: &str, provider: &str) -> String {
+    format!("{}/api/v1/auth/oidc/{}/callback", base_url.trim_end_matches('/'), provider)
+}
+
+pub struct StartOidcLoginUseCase<I: IdentityRepository> {
+    identity_repository: I,
+    providers: Arc<OidcProviders>,
+    token_service: Arc<TokenService>,
+    base_url: String,
+}
+
+impl<I: IdentityRepository> StartOidcLoginUseCase<I> {
+    pub fn new(identity_repository: I, providers: Arc<OidcProviders>, token_service: Arc<TokenService>, base_url: String) -> Self {
+        Self { identity_repository, providers, token_service, base_url }
+    }
+
+    /// Returns the provider URL to send the browser to.
+    pub async fn execute(&self, provider_name: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let provider = find_provider(&self.providers, provider_name)?;
+
+        let state = self.token_service.generate(TokenPurpose::OidcState)?;
+        let code_verifier = self.token_service.random()?;
+
+        self.identity_repository.save_login_state(OidcLoginState {
+            provider: provider_name.to_string(),
+            state_hash: self.token_service.hash(&state),
+            code_verifier: code_verifier.clone(),
+            expires_at: (Utc::now() + Duration::minutes(LOGIN_STATE_TTL_MINUTES)).naive_utc(),
+        }).await?;
+
+        Ok(provider.authorization_url(&state, &pkce_challenge(&code_verifier), &redirect_uri(&self.base_url, provider_name)))
+    }
+}
+
+/// Deployment settings that decide what a completed external sign-in may do.
+#[derive(Debug, Clone)]
+pub struct OidcLoginSettings {
+    /// The API's public URL, from which the provider callback URL is built
+    pub base_url: String,
+    pub require_email_verification: bool,
+    pub registration_mode: RegistrationMode,
+}
+
+pub struct CompleteOidcLoginUseCase<T: AuthRepository, I: IdentityRepository> {
+    auth_repository: T,
+    identity_repository: I,
+    providers: Arc<OidcProviders>,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+    settings: OidcLoginSettings,
+}
+
+impl<T: AuthRepository, I: IdentityRepository> CompleteOidcLoginUseCase<T, I> {
+    pub fn new(
+        auth_repository: T,
+        identity_repository: I,
+        providers: Arc<OidcProviders>,
+        token_service: Arc<TokenService>,
+        access_tokens: Arc<AccessTokenService>,
+        settings: OidcLoginSettings,
+    ) -> Self {
+        Self {
+            auth_repository,
+            identity_repository,
+            providers,
+            token_service,
+            access_tokens,
+            settings,
+        }
+    }
+
+    pub async fn execute(&self, provider_name: &str, code: &str, state: &str, client: &ClientInfo) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let provider = find_provider(&self.providers, provider_name)?;
+
+        let invalid_state = || -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(IoError::new(ErrorKind::InvalidInput, "Invalid or expired sign-in state"))
+        };
+
+        if !self.token_service.verify(TokenPurpose::OidcState, state) {
+            return Err(invalid_state());
+        }
+
+        let code_verifier = self.identity_repository
+            .consume_login_state(provider_name, self.token_service.hash(state))
+            .await?
+            .ok_or_else(invalid_state)?;
+
+        let identity = provider
+            .exchange_code(code, &code_verifier, &redirect_uri(&self.settings.base_url, provider_name))
+            .await?;
+
+        let user = self.resolve_user(&identity).await?;
+
+        complete_login(&self.auth_repository, &self.token_service, &self.access_tokens, &user, client, self.settings.require_email_verification).await
+    }
+
+    /// Finds the user linked to the identity, links an existing user with the same verified
+    /// email, or creates a new user if the registration mode lets anyone sign up.
+    async fn resolve_user(&self, identity: &ExternalIdentity) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(user) = self.identity_repository.find_user_by_identity(&identity.provider, &identity.subject).await? {
+            return Ok(user);
+        }
+
+        let email = identity.email.clone().ok_or_else(|| {
+            Box::new(IoError::new(ErrorKind::InvalidInput, "The provider did not share an email address")) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        if let Some(existing) = self.auth_repository.find_by_email(&email).await? {
+            // Only the provider vouching for the address proves it is the same person
+            if !identity.email_verified {
+                return Err(Box::new(IoError::new(
+                    ErrorKind::AlreadyExists,
+                    "An account with this email address already exists"
+                )));
+            }
+
+            info!("Linking {} identity {} to user {}", identity.provider, identity.subject, existing.id);
+            self.identity_repository.link_identity(existing.id, identity).await?;
+            return Ok(existing);
+        }
+
+        // Providers have no way to pass an invite code along
+        let pending_approval = match self.settings.registration_mode {
+            RegistrationMode::Open => false,
+            RegistrationMode::ApprovalRequired => true,
+            RegistrationMode::InviteOnly | RegistrationMode::Closed => {
+                return Err(Box::new(IoError::new(
+                    ErrorKind::PermissionDenied,
+                    "Registration is not open; ask an administrator for an account"
+                )));
+            }
+        };
+
+        let register_dto = RegisterUserDto {
+            username: self.available_username(identity, &email).await?,
+            email,
+            // Nobody knows this password; the user can set one through a password reset
+            password: self.token_service.random()?,
+            first_name: identity.given_name.clone(),
+            middle_name: None,
+            last_name: identity.family_name.clone(),
+            invite_code: None,
+        };
+
+        info!("Creating user {} from {} identity {}", register_dto.username, identity.provider, identity.subject);
+        self.identity_repository.register_with_identity(register_dto, identity, pending_approval).await
+    }
+
+    async fn available_username(&self, identity: &ExternalIdentity, email: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let wanted = identity.preferred_username.as_deref()
+            .unwrap_or_else(|| email.split('@').next().unwrap_or_default());
+
+        let mut base: String = wanted
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            .take(MAX_USERNAME_LENGTH - 5)
+            .collect();
+        if base.is_empty() {
+            base = "user".to_string();
+        }
+
+        if !self.identity_repository.username_exists(&base).await? {
+            return Ok(base);
+        }
+
+        for _ in 0..5 {
+            let suffix = &uuid::Uuid::new_v4().simple().to_string()[..4];
+            let candidate = format!("{}_{}", base, suffix);
+            if !self.identity_repository.username_exists(&candidate).await? {
+                return Ok(candidate);
+            }
+        }
+
+        Err(Box::new(IoError::new(ErrorKind::AlreadyExists, "Could not find a free username")))
+    }
+}
@@ -0,0 +1,254 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+use crate::application::use_cases::auth_use_cases::{ensure_may_sign_in, issue_access_token};
+use crate::domain::entities::auth::TokenResponse;
+use crate::domain::entities::passkey::{
+    AuthenticatorSelection, Ceremony, CreationOptions, CredentialDescriptor, CredentialParameters, NewPasskey,
+    NewWebAuthnChallenge, Passkey, PasskeyLoginDto, RegisterPasskeyDto, RelyingPartyEntity, RequestOptions, UserEntity,
+};
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::passkey_repository::PasskeyRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::webauthn::{
+    self, decode_base64url, encode_base64url, RelyingParty, SUPPORTED_ALGORITHMS,
+};
+
+const CHALLENGE_TTL_MINUTES: i64 = 5;
+const MAX_PASSKEY_NAME_LENGTH: usize = 100;
+const DEFAULT_PASSKEY_NAME: &str = "Passkey";
+const PUBLIC_KEY_CREDENTIAL: &str = "public-key";
+/// Passkeys stand in for the password, so the authenticator must verify the user itself
+const USER_VERIFICATION: &str = "required";
+
+fn invalid_input(message: &str) -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(IoError::new(ErrorKind::InvalidInput, message.to_string()))
+}
+
+/// The WebAuthn user handle: the user id, which identifies nothing outside this service.
+fn user_handle(user_id: i32) -> String {
+    encode_base64url(user_id.to_string().as_bytes())
+}
+
+/// Credential ids are compared in their canonical unpadded base64url form.
+fn canonical_credential_id(credential_id: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(encode_base64url(&decode_base64url(credential_id)?))
+}
+
+/// Stores the hash of a fresh challenge and returns the challenge to send to the browser.
+async fn issue_challenge<P: PasskeyRepository>(
+    passkey_repository: &P,
+    token_service: &TokenService,
+    user_id: Option<i32>,
+    ceremony: Ceremony,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let challenge = token_service.random()?;
+
+    passkey_repository
+        .save_challenge(NewWebAuthnChallenge {
+            user_id,
+            ceremony,
+            challenge_hash: token_service.hash(&challenge),
+            expires_at: (Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES)).naive_utc(),
+        })
+        .await?;
+
+    Ok(challenge)
+}
+
+pub struct ManagePasskeysUseCase<T: AuthRepository, P: PasskeyRepository> {
+    auth_repository: T,
+    passkey_repository: P,
+    token_service: Arc<TokenService>,
+    relying_party: Arc<RelyingParty>,
+}
+
+impl<T: AuthRepository, P: PasskeyRepository> ManagePasskeysUseCase<T, P> {
+    pub fn new(
+        auth_repository: T,
+        passkey_repository: P,
+        token_service: Arc<TokenService>,
+        relying_party: Arc<RelyingParty>,
+    ) -> Self {
+        Self { auth_repository, passkey_repository, token_service, relying_party }
+    }
+
+    /// Starts a registration: the options for `navigator.credentials.create()`.
+    pub async fn registration_options(&self, user_id: i32) -> Result<CreationOptions, Box<dyn std::error::Error + Send + Sync>> {
+        let user = self.auth_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| Box::new(IoError::new(ErrorKind::NotFound, "User not found")) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let challenge = issue_challenge(&self.passkey_repository, &self.token_service, Some(user_id), Ceremony::Registration).await?;
+
+        // Keeps the browser from registering a second passkey on the same authenticator
+        let exclude_credentials = self.passkey_repository
+            .find_passkeys(user_id)
+            .await?
+            .into_iter()
+            .map(|passkey| CredentialDescriptor { credential_type: PUBLIC_KEY_CREDENTIAL, id: passkey.credential_id })
+            .collect();
+
+        Ok(CreationOptions {
+            challenge,
+            rp: RelyingPartyEntity {
+                id: self.relying_party.id.clone(),
+                name: self.relying_party.name.clone(),
+            },
+            user: UserEntity {
+                id: user_handle(user.id),
+                name: user.username.clone(),
+                display_name: user.username,
+            },
+            pub_key_cred_params: SUPPORTED_ALGORITHMS
+                .iter()
+                .map(|alg| CredentialParameters { credential_type: PUBLIC_KEY_CREDENTIAL, alg: *alg })
+                .collect(),
+            timeout: (CHALLENGE_TTL_MINUTES * 60 * 1000) as u64,
+            attestation: "none",
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "required",
+                require_resident_key: true,
+                user_verification: USER_VERIFICATION,
+            },
+            exclude_credentials,
+        })
+    }
+
+    /// Finishes a registration with the credential the browser created.
+    pub async fn register(&self, user_id: i32, register_dto: RegisterPasskeyDto) -> Result<Passkey, Box<dyn std::error::Error + Send + Sync>> {
+        let name = register_dto.name
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|| DEFAULT_PASSKEY_NAME.to_string());
+        if name.is_empty() || name.chars().count() > MAX_PASSKEY_NAME_LENGTH {
+            return Err(invalid_input("Passkey name must be between 1 and 100 characters"));
+        }
+
+        let response = register_dto.credential.response;
+        let client_data_json = decode_base64url(&response.client_data_json)?;
+        let attestation_object = decode_base64url(&response.attestation_object)?;
+
+        let challenge = webauthn::client_data_challenge(&client_data_json)?;
+        let challenge_user = self.passkey_repository
+            .consume_challenge(Ceremony::Registration, self.token_service.hash(&challenge))
+            .await?;
+        if challenge_user != Some(Some(user_id)) {
+            return Err(invalid_input("Invalid or expired registration challenge"));
+        }
+
+        let credential = webauthn::verify_registration(&self.relying_party, &challenge, &client_data_json, &attestation_object)?;
+
+        let passkey = self.passkey_repository
+            .create_passkey(NewPasskey {
+                user_id,
+                credential_id: encode_base64url(&credential.credential_id),
+                public_key: credential.public_key,
+                algorithm: credential.algorithm as i32,
+                sign_count: credential.sign_count as i64,
+                name,
+            })
+            .await?;
+
+        info!("Passkey {} registered for user {}", passkey.id, user_id);
+        Ok(passkey)
+    }
+
+    pub async fn list(&self, user_id: i32) -> Result<Vec<Passkey>, Box<dyn std::error::Error + Send + Sync>> {
+        self.passkey_repository.find_passkeys(user_id).await
+    }
+
+    pub async fn delete(&self, user_id: i32, passkey_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.passkey_repository.delete_passkey(user_id, passkey_id).await? {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "Passkey not found")));
+        }
+
+        info!("Passkey {} removed by user {}", passkey_id, user_id);
+        Ok(())
+    }
+}
+
+/// Passwordless sign-in. The passkey already proves possession and user verification, so
+/// no second factor is asked for.
+pub struct PasskeyLoginUseCase<T: AuthRepository, P: PasskeyRepository> {
+    auth_repository: T,
+    passkey_repository: P,
+    token_service: Arc<TokenService>,
+    access_tokens: Arc<AccessTokenService>,
+    relying_party: Arc<RelyingParty>,
+    require_email_verification: bool,
+}
+
+impl<T: AuthRepository, P: PasskeyRepository> PasskeyLoginUseCase<T, P> {
+    pub fn new(
+        auth_repository: T,
+        passkey_repository: P,
+        token_service: Arc<TokenService>,
+        access_tokens: Arc<AccessTokenService>,
+        relying_party: Arc<RelyingParty>,
+        require_email_verification: bool,
+    ) -> Self {
+        Self { auth_repository, passkey_repository, token_service, access_tokens, relying_party, require_email_verification }
+    }
+
+    /// Starts a sign-in: the options for `navigator.credentials.get()`.
+    pub async fn options(&self) -> Result<RequestOptions, Box<dyn std::error::Error + Send + Sync>> {
+        let challenge = issue_challenge(&self.passkey_repository, &self.token_service, None, Ceremony::Authentication).await?;
+
+        Ok(RequestOptions {
+            challenge,
+            rp_id: self.relying_party.id.clone(),
+            timeout: (CHALLENGE_TTL_MINUTES * 60 * 1000) as u64,
+            user_verification: USER_VERIFICATION,
+            allow_credentials: Vec::new(),
+        })
+    }
+
+    pub async fn execute(&self, login_dto: PasskeyLoginDto, client: &ClientInfo) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid_passkey = || invalid_input("Invalid passkey");
+
+        let response = login_dto.response;
+        let client_data_json = decode_base64url(&response.client_data_json)?;
+        let authenticator_data = decode_base64url(&response.authenticator_data)?;
+        let signature = decode_base64url(&response.signature)?;
+
+        let challenge = webauthn::client_data_challenge(&client_data_json)?;
+        self.passkey_repository
+            .consume_challenge(Ceremony::Authentication, self.token_service.hash(&challenge))
+            .await?
+            .ok_or_else(|| invalid_input("Invalid or expired sign-in challenge"))?;
+
+        let passkey = self.passkey_repository
+            .find_passkey_by_credential_id(&canonical_credential_id(&login_dto.id)?)
+            .await?
+            .ok_or_else(invalid_passkey)?;
+
+        if let Some(handle) = &response.user_handle {
+            if canonical_credential_id(handle)? != user_handle(passkey.user_id) {
+                return Err(invalid_passkey());
+            }
+        }
+
+        let sign_count = webauthn::verify_assertion(
+            &self.relying_party,
+            &challenge,
+            &passkey.public_key,
+            u32::try_from(passkey.sign_count).unwrap_or(u32::MAX),
+            &client_data_json,
+            &authenticator_data,
+            &signature,
+        )?;
+        self.passkey_repository.record_passkey_use(passkey.id, sign_count as i64).await?;
+
+        let user = self.auth_repository.find_by_id(passkey.user_id).await?.ok_or_else(invalid_passkey)?;
+
+        ensure_may_sign_in(&self.auth_repository, &user, self.require_email_verification).await?;
+
+        info!("User {} signed in with passkey {}", user.id, passkey.id);
+        issue_access_token(&self.auth_repository, &self.access_tokens, &user, client).await
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
+
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::registration::{CreateInviteDto, CreatedInvite, Invite, NewInvite, PendingUser};
+use crate::domain::repositories::registration_repository::RegistrationRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::services::mailer::{Mailer, OutgoingEmail};
+use crate::domain::services::token_service::TokenService;
+
+const MAX_INVITE_USES: i32 = 1000;
+const MAX_INVITE_LIFETIME_DAYS: i64 = 365;
+const MAX_INVITE_NOTE_LENGTH: usize = 200;
+
+fn invalid_input(message: &str) -> Box<dyn std::error::Error> {
+    Box::new(IoError::new(ErrorKind::InvalidInput, message.to_string()))
+}
+
+pub struct ManageInvitesUseCase<G: RegistrationRepository, R: RoleRepository> {
+    registration_repository: G,
+    token_service: Arc<TokenService>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<G: RegistrationRepository, R: RoleRepository> ManageInvitesUseCase<G, R> {
+    pub fn new(registration_repository: G, token_service: Arc<TokenService>, role_repository: Arc<R>) -> Self {
+        Self {
+            registration_repository,
+            token_service,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    /// Creates an invite. The code is returned here and never again.
+    pub async fn create(&self, admin_id: i32, invite_dto: CreateInviteDto) -> Result<CreatedInvite, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let note = invite_dto.note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if note.as_ref().is_some_and(|note| note.chars().count() > MAX_INVITE_NOTE_LENGTH) {
+            return Err(invalid_input("Invite notes must be at most 200 characters"));
+        }
+
+        let max_uses = invite_dto.max_uses.unwrap_or(1);
+        if !(1..=MAX_INVITE_USES).contains(&max_uses) {
+            return Err(invalid_input("An invite must allow between 1 and 1000 uses"));
+        }
+
+        let expires_at = match invite_dto.expires_in_days {
+            Some(days) if (1..=MAX_INVITE_LIFETIME_DAYS).contains(&days) => Some((Utc::now() + Duration::days(days)).naive_utc()),
+            Some(_) => return Err(invalid_input("Invites must expire within 1 to 365 days")),
+            None => None,
+        };
+
+        let code = self.token_service.random().map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        let invite = self.registration_repository
+            .create_invite(NewInvite {
+                code_hash: self.token_service.hash(&code),
+                created_by: admin_id,
+                note,
+                max_uses,
+                expires_at,
+            })
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        info!("Invite {} for {} sign-ups created by user {}", invite.id, max_uses, admin_id);
+
+        Ok(CreatedInvite { invite, code })
+    }
+
+    pub async fn list(&self, admin_id: i32) -> Result<Vec<Invite>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+        self.registration_repository
+            .find_invites()
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+
+    pub async fn revoke(&self, admin_id: i32, invite_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let revoked = self.registration_repository
+            .revoke_invite(invite_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+        if !revoked {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "Invite not found")));
+        }
+
+        info!("Invite {} revoked by user {}", invite_id, admin_id);
+        Ok(())
+    }
+}
+
+/// Admin decisions on sign-ups made while registration requires approval.
+pub struct ReviewRegistrationsUseCase<G: RegistrationRepository, R: RoleRepository> {
+    registration_repository: G,
+    mailer: Arc<dyn Mailer>,
+    authorize_role: AuthorizeRoleUseCase<R>,
+}
+
+impl<G: RegistrationRepository, R: RoleRepository> ReviewRegistrationsUseCase<G, R> {
+    pub fn new(registration_repository: G, mailer: Arc<dyn Mailer>, role_repository: Arc<R>) -> Self {
+        Self {
+            registration_repository,
+            mailer,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+        }
+    }
+
+    pub async fn list_pending(&self, admin_id: i32) -> Result<Vec<PendingUser>, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+        self.registration_repository
+            .find_pending_users()
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)
+    }
+
+    /// Lets the user sign in and tells them by email.
+    pub async fn approve(&self, admin_id: i32, user_id: i32) -> Result<PendingUser, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let user = self.registration_repository
+            .approve_user(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "No registration awaiting approval for this user"))?;
+
+        info!("Registration of user {} approved by user {}", user_id, admin_id);
+
+        // The approval stands either way; the user can simply try to sign in
+        let email = OutgoingEmail {
+            to: user.email.clone(),
+            subject: "Your account has been approved".to_string(),
+            body: format!("Hi {},\n\nAn administrator approved your account. You can sign in now.", user.username),
+        };
+        if let Err(e) = self.mailer.send(email).await {
+            warn!("Failed to send approval email to user {}: {}", user_id, e);
+        }
+
+        Ok(user)
+    }
+
+    /// Deletes a sign-up that is still waiting for approval.
+    pub async fn reject(&self, admin_id: i32, user_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        self.authorize_role.execute(admin_id, ADMIN_ROLES).await?;
+
+        let rejected = self.registration_repository
+            .reject_user(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+        if !rejected {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "No registration awaiting approval for this user")));
+        }
+
+        info!("Registration of user {} rejected by user {}", user_id, admin_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use crate::domain::repositories::role_repository::RoleRepository;
+
+/// Roles allowed to use moderation and other administrative endpoints.
+pub const ADMIN_ROLES: &[&str] = &["admin", "superuser"];
+/// Roles allowed to impersonate other users.
+pub const SUPERUSER_ROLES: &[&str] = &["superuser"];
+
+pub struct AuthorizeRoleUseCase<R: RoleRepository> {
+    role_repository: Arc<R>,
+}
+
+impl<R: RoleRepository> AuthorizeRoleUseCase<R> {
+    pub fn new(role_repository: Arc<R>) -> Self {
+        Self { role_repository }
+    }
+
+    pub async fn execute(&self, user_id: i32, role_names: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+        if self.role_repository.has_any_role(user_id, role_names).await? {
+            Ok(())
+        } else {
+            Err(Box::new(IoError::new(
+                ErrorKind::PermissionDenied,
+                "You do not have permission to perform this action"
+            )))
+        }
+    }
+
+    /// Lets users act on their own account, and holders of `role_names` on anyone's.
+    pub async fn execute_unless_self(&self, user_id: i32, target_user_id: i32, role_names: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+        if user_id == target_user_id {
+            return Ok(());
+        }
+        self.execute(user_id, role_names).await
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{Error as IoError, ErrorKind};
+use tracing::info;
+
+use crate::domain::entities::session::Session;
+use crate::domain::repositories::auth_repository::AuthRepository;
+
+pub struct ManageSessionsUseCase<T: AuthRepository> {
+    auth_repository: T,
+}
+
+impl<T: AuthRepository> ManageSessionsUseCase<T> {
+    pub fn new(auth_repository: T) -> Self {
+        Self { auth_repository }
+    }
+
+    /// The user's active sessions; `current_token_id` marks the one making the request.
+    pub async fn list(&self, user_id: i32, current_token_id: &str) -> Result<Vec<Session>, Box<dyn std::error::Error>> {
+        let mut sessions = self.auth_repository
+            .find_sessions(user_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        for session in &mut sessions {
+            session.current = session.token_id == current_token_id;
+        }
+
+        Ok(sessions)
+    }
+
+    /// Signs the session out; its token is rejected from the next request on.
+    pub async fn revoke(&self, user_id: i32, session_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let revoked = self.auth_repository
+            .revoke_session(user_id, session_id)
+            .await
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        if !revoked {
+            return Err(Box::new(IoError::new(ErrorKind::NotFound, "Session not found")));
+        }
+
+        info!("Session {} revoked by user {}", session_id, user_id);
+        Ok(())
+    }
+}
@@ -1,9 +1,22 @@
 use std::fmt;
+use std::sync::Arc;
+use tracing::warn;
 use crate::domain::{
     entities::user::{User, CreateUserDto},
     repositories::user_repository::UserRepository,
 };
+use crate::application::use_cases::audit_use_cases::record_audit_event;
+use crate::application::use_cases::auth_use_cases::SendVerificationEmailUseCase;
+use crate::application::use_cases::role_use_cases::{AuthorizeRoleUseCase, ADMIN_ROLES};
+use crate::domain::entities::audit::{AuditAction, NewAuditEvent};
+use crate::domain::entities::auth::RegisterUserDto;
+use crate::domain::entities::registration::Admission;
+use crate::domain::entities::session::ClientInfo;
 use crate::domain::entities::user::UpdateUserDto;
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::services::validation::{validate_email, validate_username, PasswordPolicy, Validator};
 
 pub struct GetUserByIdUseCase<T: UserRepository> {
     user_repository: T,
@@ -27,17 +40,57 @@ impl<T: UserRepository> GetUserByIdUseCase<T> {
     }
 }
 
-pub struct CreateUserUseCase<T: UserRepository> {
-    user_repository: T,
+pub struct CreateUserUseCase<A: AuthRepository, R: RoleRepository> {
+    auth_repository: A,
+    authorize_role: AuthorizeRoleUseCase<R>,
+    password_policy: Arc<PasswordPolicy>,
+    audit_repository: Arc<dyn AuditRepository>,
 }
 
-impl<T: UserRepository> CreateUserUseCase<T> {
-    pub fn new(user_repository: T) -> Self {
-        Self { user_repository }
+impl<A: AuthRepository, R: RoleRepository> CreateUserUseCase<A, R> {
+    pub fn new(auth_repository: A, role_repository: Arc<R>, password_policy: Arc<PasswordPolicy>, audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self {
+            auth_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+            password_policy,
+            audit_repository,
+        }
+    }
+
+    /// Lets an admin add an account directly, whatever the registration mode. The
+    /// password is hashed like a sign-up's.
+    pub async fn execute(&self, actor_id: i32, user_dto: CreateUserDto, client: &ClientInfo) -> Result<User, Box<dyn std::error::Error>> {
+        let result = self.create(actor_id, user_dto).await;
+
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: Some(actor_id),
+            target_user_id: result.as_ref().ok().map(|user| user.id),
+            ..NewAuditEvent::from_result(AuditAction::UserCreated, &result, client)
+        }).await;
+
+        result
     }
 
-    pub async fn execute(&self, user_dto: CreateUserDto) -> Result<User, Box<dyn std::error::Error>> {
-        self.user_repository.create(user_dto).await
+    async fn create(&self, actor_id: i32, user_dto: CreateUserDto) -> Result<User, Box<dyn std::error::Error>> {
+        self.authorize_role.execute(actor_id, ADMIN_ROLES).await?;
+
+        Validator::new()
+            .check("username", validate_username(&user_dto.username))
+            .check("email", validate_email(&user_dto.email))
+            .check("password", self.password_policy.validate(&user_dto.password))
+            .finish()?;
+
+        let register_dto = RegisterUserDto {
+            username: user_dto.username,
+            email: user_dto.email,
+            password: user_dto.password,
+            first_name: None,
+            middle_name: None,
+            last_name: None,
+            invite_code: None,
+        };
+        self.auth_repository.register(register_dto, Admission::default()).await
+            .map_err(|e| e as Box<dyn std::error::Error>)
     }
 }
 
@@ -56,31 +109,94 @@ impl<T: UserRepository> ListUsersUseCase<T> {
     }
 }
 
-pub struct UpdateUserUseCase<T: UserRepository> {
+pub struct UpdateUserUseCase<T: UserRepository, A: AuthRepository, R: RoleRepository> {
     user_repository: T,
+    authorize_role: AuthorizeRoleUseCase<R>,
+    send_verification_email_use_case: Arc<SendVerificationEmailUseCase<A>>,
+    audit_repository: Arc<dyn AuditRepository>,
 }
 
-impl<T: UserRepository> UpdateUserUseCase<T> {
-    pub fn new(user_repository: T) -> Self {
-        Self { user_repository }
+impl<T: UserRepository, A: AuthRepository, R: RoleRepository> UpdateUserUseCase<T, A, R> {
+    pub fn new(
+        user_repository: T,
+        role_repository: Arc<R>,
+        send_verification_email_use_case: Arc<SendVerificationEmailUseCase<A>>,
+        audit_repository: Arc<dyn AuditRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+            send_verification_email_use_case,
+            audit_repository,
+        }
     }
 
-    pub async fn execute(&self, id: i32, user_dto: UpdateUserDto) -> Result<User, Box<dyn std::error::Error>> {
-        self.user_repository.update(id, user_dto).await
+    /// Users may update their own profile; admins may update anyone's.
+    pub async fn execute(&self, actor_id: i32, id: i32, user_dto: UpdateUserDto, client: &ClientInfo) -> Result<User, Box<dyn std::error::Error>> {
+        let result = self.update(actor_id, id, user_dto).await;
+
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: Some(actor_id),
+            target_user_id: Some(id),
+            ..NewAuditEvent::from_result(AuditAction::UserUpdated, &result, client)
+        }).await;
+
+        result
+    }
+
+    async fn update(&self, actor_id: i32, id: i32, user_dto: UpdateUserDto) -> Result<User, Box<dyn std::error::Error>> {
+        self.authorize_role.execute_unless_self(actor_id, id, ADMIN_ROLES).await?;
+
+        Validator::new()
+            .check_optional("username", user_dto.username.as_deref(), validate_username)
+            .check_optional("email", user_dto.email.as_deref(), validate_email)
+            .finish()?;
+
+        let previous = self.user_repository.find_by_id(id).await?;
+        let updated = self.user_repository.update(id, user_dto).await?;
+
+        // The new address is unverified until its owner opens the link
+        if !updated.email.eq_ignore_ascii_case(&previous.email) {
+            if let Err(e) = self.send_verification_email_use_case.execute(&updated).await {
+                warn!("Failed to send verification email to user {}: {}", updated.id, e);
+            }
+        }
+
+        Ok(updated)
     }
 }
 
 
-pub struct DeleteUserUseCase<T: UserRepository> {
+pub struct DeleteUserUseCase<T: UserRepository, R: RoleRepository> {
     user_repository: T,
+    authorize_role: AuthorizeRoleUseCase<R>,
+    audit_repository: Arc<dyn AuditRepository>,
 }
 
-impl<T: UserRepository> DeleteUserUseCase<T> {
-    pub fn new(user_repository: T) -> Self {
-        Self { user_repository }
+impl<T: UserRepository, R: RoleRepository> DeleteUserUseCase<T, R> {
+    pub fn new(user_repository: T, role_repository: Arc<R>, audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self {
+            user_repository,
+            authorize_role: AuthorizeRoleUseCase::new(role_repository),
+            audit_repository,
+        }
+    }
+
+    /// Users may delete their own account; admins may delete anyone's.
+    pub async fn execute(&self, actor_id: i32, user_id: i32, client: &ClientInfo) -> Result<(), Box<dyn std::error::Error>> {
+        let result = self.delete(actor_id, user_id).await;
+
+        record_audit_event(&*self.audit_repository, NewAuditEvent {
+            actor_id: Some(actor_id),
+            target_user_id: Some(user_id),
+            ..NewAuditEvent::from_result(AuditAction::UserDeleted, &result, client)
+        }).await;
+
+        result
     }
 
-    pub async fn execute(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+    async fn delete(&self, actor_id: i32, user_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        self.authorize_role.execute_unless_self(actor_id, user_id, ADMIN_ROLES).await?;
         self.user_repository.delete(user_id).await
     }
 }
\ No newline at end of file
@@ -12,6 +12,7 @@ table! {
         username -> Varchar,
         password -> Varchar,
         email -> Varchar,
+        email_verified_at -> Nullable<Timestamp>,
     }
 }
 
@@ -45,6 +46,7 @@ struct NewUser {
     username: String,
     email: String,
     password: String,
+    email_verified_at: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Insertable)]
@@ -101,6 +103,8 @@ fn main() {
             username,
             email,
             password: hashed_password,
+            // Superusers are created by an operator, so there is no link to click
+            email_verified_at: Some(chrono::Utc::now().naive_utc()),
         };
 
         // Insert the user and get their ID
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Announcement {
+    pub id: i32,
+    pub author_id: Option<i32>,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAnnouncementDto {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnnouncementPublished {
+    pub announcement: Announcement,
+    pub delivered_to: usize,
+}
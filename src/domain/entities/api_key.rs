@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+/// Every key starts with this, so the bearer validator can tell keys from JWTs.
+pub const API_KEY_PREFIX: &str = "rca_";
+
+/// Safe methods only
+pub const SCOPE_READ: &str = "read";
+/// Everything, including `read`
+pub const SCOPE_WRITE: &str = "write";
+pub const API_KEY_SCOPES: &[&str] = &[SCOPE_READ, SCOPE_WRITE];
+
+#[derive(Debug, Serialize)]
+pub struct ApiKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewApiKey {
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyDto {
+    pub name: String,
+    /// Defaults to `read`
+    pub scopes: Option<Vec<String>>,
+    /// Never expires when left out
+    pub expires_in_days: Option<i64>,
+}
+
+/// Returned once on creation; only the hash of the key is stored.
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::session::ClientInfo;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Login,
+    Impersonation,
+    PasswordChange,
+    PasswordReset,
+    TotpEnabled,
+    TotpDisabled,
+    UserCreated,
+    UserUpdated,
+    UserDeleted,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Login => "login",
+            AuditAction::Impersonation => "impersonation",
+            AuditAction::PasswordChange => "password_change",
+            AuditAction::PasswordReset => "password_reset",
+            AuditAction::TotpEnabled => "totp_enabled",
+            AuditAction::TotpDisabled => "totp_disabled",
+            AuditAction::UserCreated => "user_created",
+            AuditAction::UserUpdated => "user_updated",
+            AuditAction::UserDeleted => "user_deleted",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+impl AuditOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOutcome::Success => "success",
+            AuditOutcome::Failure => "failure",
+        }
+    }
+}
+
+/// One row of the audit log. User ids are kept without foreign keys so events
+/// outlive the accounts they mention.
+#[derive(Debug, Serialize)]
+pub struct AuditEvent {
+    pub id: i32,
+    pub action: String,
+    pub outcome: String,
+    /// Who did it; `None` when nobody was signed in, e.g. a failed login
+    pub actor_id: Option<i32>,
+    /// The account it was done to
+    pub target_user_id: Option<i32>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAuditEvent {
+    pub action: AuditAction,
+    pub outcome: AuditOutcome,
+    pub actor_id: Option<i32>,
+    pub target_user_id: Option<i32>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: Option<String>,
+}
+
+impl NewAuditEvent {
+    /// An event without actor, target or details; set those with struct update syntax.
+    pub fn new(action: AuditAction, outcome: AuditOutcome, client: &ClientInfo) -> Self {
+        Self {
+            action,
+            outcome,
+            actor_id: None,
+            target_user_id: None,
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+            details: None,
+        }
+    }
+
+    /// An event for the outcome of an action; failures keep the error as details.
+    pub fn from_result<T, E: fmt::Display>(action: AuditAction, result: &Result<T, E>, client: &ClientInfo) -> Self {
+        match result {
+            Ok(_) => Self::new(action, AuditOutcome::Success, client),
+            Err(e) => Self {
+                details: Some(e.to_string()),
+                ..Self::new(action, AuditOutcome::Failure, client)
+            },
+        }
+    }
+}
+
+/// Filters of the admin query; every one is optional and they combine with AND.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditEventQuery {
+    pub action: Option<AuditAction>,
+    pub outcome: Option<AuditOutcome>,
+    pub actor_id: Option<i32>,
+    pub target_user_id: Option<i32>,
+    pub ip_address: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+    /// Only events older than this one, for paging back through the log
+    pub before_id: Option<i32>,
+    pub limit: Option<i64>,
+}
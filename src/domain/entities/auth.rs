@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AuthUser {
+    /// Either the username or the email address
+    #[serde(alias = "email", alias = "login")]
     pub username: String,
     pub password: String,
 }
@@ -11,6 +13,31 @@ pub struct Claims {
     pub sub: i32,  // user_id
     pub exp: i64,  // expiration time
     pub iat: i64,  // issued at
+    pub nbf: i64,  // not valid before
+    pub iss: String,
+    pub aud: String,
+    pub jti: String,  // unique token id
+    pub username: String,
+    pub roles: Vec<String>,
+    /// Set only for API keys, which may be limited to `read`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    /// The superuser acting as `sub`, set only on impersonation tokens (RFC 8693 `act`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+}
+
+impl Claims {
+    pub fn is_impersonated(&self) -> bool {
+        self.act.is_some()
+    }
+}
+
+/// Who is really behind an impersonation token.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Actor {
+    pub sub: i32,
+    pub username: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -28,4 +55,66 @@ pub struct RegisterUserDto {
     pub first_name: Option<String>,
     pub middle_name: Option<String>,
     pub last_name: Option<String>,
-}
\ No newline at end of file
+    /// Required when registration is invite-only
+    pub invite_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenPurpose {
+    EmailVerification,
+    PasswordReset,
+    MfaChallenge,
+    OidcState,
+    OAuthCode,
+    MagicLink,
+}
+
+impl TokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "email_verification",
+            TokenPurpose::PasswordReset => "password_reset",
+            TokenPurpose::MfaChallenge => "mfa_challenge",
+            TokenPurpose::OidcState => "oidc_state",
+            TokenPurpose::OAuthCode => "oauth_code",
+            TokenPurpose::MagicLink => "magic_link",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyEmailDto {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResendVerificationDto {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RequestPasswordResetDto {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RequestMagicLinkDto {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyMagicLinkDto {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmPasswordResetDto {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangePasswordDto {
+    pub current_password: String,
+    pub new_password: String,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::domain::entities::account::Account;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactRequestStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+impl ContactRequestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactRequestStatus::Pending => "pending",
+            ContactRequestStatus::Accepted => "accepted",
+            ContactRequestStatus::Declined => "declined",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ContactRequestStatus::Pending),
+            "accepted" => Some(ContactRequestStatus::Accepted),
+            "declined" => Some(ContactRequestStatus::Declined),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContactRequest {
+    pub id: i32,
+    pub requester_id: i32,
+    pub addressee_id: i32,
+    pub status: ContactRequestStatus,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Contact {
+    pub user_id: i32,
+    pub account: Option<Account>,
+    pub since: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SendContactRequestDto {
+    pub addressee_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContactSettings {
+    pub user_id: i32,
+    pub allow_messages_from_strangers: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateContactSettingsDto {
+    pub allow_messages_from_strangers: bool,
+}
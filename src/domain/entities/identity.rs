@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+/// The user as described by an external OpenID Connect / OAuth2 provider.
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub preferred_username: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct OidcLoginState {
+    pub provider: String,
+    pub state_hash: String,
+    pub code_verifier: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set by the provider when the user denied access or the request was invalid
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
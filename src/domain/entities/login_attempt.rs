@@ -0,0 +1,27 @@
+use serde::Serialize;
+use chrono::NaiveDateTime;
+
+/// Failed logins since the last successful one inside the throttling window.
+#[derive(Debug, Clone, Default)]
+pub struct FailureStats {
+    pub failed_attempts: i64,
+    pub last_failed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginLockout {
+    pub id: i32,
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+    pub failed_attempts: i32,
+    pub locked_until: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewLoginLockout {
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+    pub failed_attempts: i32,
+    pub locked_until: NaiveDateTime,
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use crate::domain::entities::notification::NotificationHint;
 use crate::schema::messages;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable)]
@@ -12,6 +13,27 @@ pub struct DatabaseMessage {
     pub content: String,
     pub is_read: bool,
     pub created_at: NaiveDateTime,
+    pub is_hidden: bool,
+    pub deliver_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub is_delivered: bool,
+}
+
+impl DatabaseMessage {
+    /// Expired messages stay hidden even before the scheduler purges them.
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SendMessageDto {
+    pub receiver_id: i32,
+    pub content: String,
+    /// Holds the message back until this time when set
+    pub deliver_at: Option<NaiveDateTime>,
+    /// Deletes the message this many seconds after it is delivered
+    pub ttl_seconds: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -19,6 +41,11 @@ pub enum WebSocketMessage {
     Chat {
         to_user_id: i32,
         content: String,
+        /// Filled in by the server when pushing to the recipient
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_user_id: Option<i32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        notification: Option<NotificationHint>,
     },
     Status {
         user_id: i32,
@@ -41,7 +68,12 @@ pub enum WebSocketMessage {
     },
     Error {
         message: String,
-    }
+    },
+    Announcement {
+        id: i32,
+        content: String,
+        created_at: NaiveDateTime,
+    },
 }
 
 impl actix::Message for WebSocketMessage {
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::domain::entities::auth::TokenResponse;
+
+#[derive(Debug, Clone)]
+pub struct UserTotp {
+    pub secret: String,
+    pub confirmed_at: Option<NaiveDateTime>,
+}
+
+impl UserTotp {
+    pub fn is_enabled(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TotpCodeDto {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisableTotpDto {
+    pub password: String,
+    pub code: String,
+}
+
+/// Shown once when 2FA is confirmed; only hashes are kept.
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MfaLoginDto {
+    pub mfa_token: String,
+    /// Either a code from the authenticator app or an unused recovery code
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaChallenge {
+    pub mfa_required: bool,
+    pub mfa_token: String,
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Token(TokenResponse),
+    MfaRequired(MfaChallenge),
+}
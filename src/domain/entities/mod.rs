@@ -2,4 +2,17 @@ pub mod user;
 pub mod auth;
 pub mod account;
 pub mod message;
-pub mod avatar;
\ No newline at end of file
+pub mod avatar;
+pub mod contact;
+pub mod moderation;
+pub mod announcement;
+pub mod notification;
+pub mod mfa;
+pub mod login_attempt;
+pub mod identity;
+pub mod oauth;
+pub mod api_key;
+pub mod session;
+pub mod passkey;
+pub mod registration;
+pub mod audit;
\ No newline at end of file
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::domain::entities::message::DatabaseMessage;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    SexualContent,
+    Violence,
+    Other,
+}
+
+impl ReportReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "spam",
+            ReportReason::Harassment => "harassment",
+            ReportReason::HateSpeech => "hate_speech",
+            ReportReason::SexualContent => "sexual_content",
+            ReportReason::Violence => "violence",
+            ReportReason::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spam" => Some(ReportReason::Spam),
+            "harassment" => Some(ReportReason::Harassment),
+            "hate_speech" => Some(ReportReason::HateSpeech),
+            "sexual_content" => Some(ReportReason::SexualContent),
+            "violence" => Some(ReportReason::Violence),
+            "other" => Some(ReportReason::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Open,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Actioned => "actioned",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(ReportStatus::Open),
+            "actioned" => Some(ReportStatus::Actioned),
+            "dismissed" => Some(ReportStatus::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageReport {
+    pub id: i32,
+    pub message_id: i32,
+    pub reporter_id: i32,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A report together with the message it points at, as shown in the review queue.
+#[derive(Debug, Serialize)]
+pub struct ReportedMessage {
+    pub report: MessageReport,
+    pub message: DatabaseMessage,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateReportDto {
+    pub message_id: i32,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportQuery {
+    pub status: Option<ReportStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationActionType {
+    HideMessage,
+    UnhideMessage,
+    DismissReport,
+    SuspendUser,
+    LiftSuspension,
+}
+
+impl ModerationActionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationActionType::HideMessage => "hide_message",
+            ModerationActionType::UnhideMessage => "unhide_message",
+            ModerationActionType::DismissReport => "dismiss_report",
+            ModerationActionType::SuspendUser => "suspend_user",
+            ModerationActionType::LiftSuspension => "lift_suspension",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "hide_message" => Some(ModerationActionType::HideMessage),
+            "unhide_message" => Some(ModerationActionType::UnhideMessage),
+            "dismiss_report" => Some(ModerationActionType::DismissReport),
+            "suspend_user" => Some(ModerationActionType::SuspendUser),
+            "lift_suspension" => Some(ModerationActionType::LiftSuspension),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModerationAction {
+    pub id: i32,
+    pub moderator_id: Option<i32>,
+    pub action: ModerationActionType,
+    pub target_user_id: Option<i32>,
+    pub target_message_id: Option<i32>,
+    pub report_id: Option<i32>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewModerationAction {
+    pub moderator_id: i32,
+    pub action: ModerationActionType,
+    pub target_user_id: Option<i32>,
+    pub target_message_id: Option<i32>,
+    pub report_id: Option<i32>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModerationNoteDto {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserSuspension {
+    pub id: i32,
+    pub user_id: i32,
+    pub suspended_by: Option<i32>,
+    pub reason: Option<String>,
+    pub suspended_until: Option<NaiveDateTime>,
+    pub lifted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuspendUserDto {
+    pub reason: Option<String>,
+    /// `None` suspends the user until an admin lifts it
+    pub suspended_until: Option<NaiveDateTime>,
+}
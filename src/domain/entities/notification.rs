@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationMode {
+    All,
+    MentionsOnly,
+    Muted,
+}
+
+impl NotificationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationMode::All => "all",
+            NotificationMode::MentionsOnly => "mentions_only",
+            NotificationMode::Muted => "muted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "all" => Some(NotificationMode::All),
+            "mentions_only" => Some(NotificationMode::MentionsOnly),
+            "muted" => Some(NotificationMode::Muted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationPreference {
+    pub id: i32,
+    pub user_id: i32,
+    pub counterpart_user_id: Option<i32>,
+    pub group_id: Option<i32>,
+    pub mode: NotificationMode,
+    pub muted_until: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl NotificationPreference {
+    /// A mute with an expiry in the past no longer applies.
+    pub fn effective_mode(&self, now: NaiveDateTime) -> NotificationMode {
+        match (self.mode, self.muted_until) {
+            (NotificationMode::Muted, Some(until)) if until <= now => NotificationMode::All,
+            (mode, _) => mode,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateNotificationPreferenceDto {
+    pub mode: NotificationMode,
+    /// Only used with `muted`; `None` mutes until changed again
+    pub muted_until: Option<NaiveDateTime>,
+}
+
+/// Attached to pushed chat messages so clients know whether to alert.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationHint {
+    pub mode: NotificationMode,
+    pub muted_until: Option<NaiveDateTime>,
+}
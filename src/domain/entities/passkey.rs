@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+/// The two WebAuthn ceremonies a challenge can be issued for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ceremony {
+    Registration,
+    Authentication,
+}
+
+impl Ceremony {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ceremony::Registration => "registration",
+            Ceremony::Authentication => "authentication",
+        }
+    }
+}
+
+/// A WebAuthn credential registered by a user.
+#[derive(Debug, Serialize)]
+pub struct Passkey {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    /// Base64url, as browsers report it
+    pub credential_id: String,
+    #[serde(skip_serializing)]
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    #[serde(skip_serializing)]
+    pub sign_count: i64,
+    pub name: String,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewPasskey {
+    pub user_id: i32,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    pub sign_count: i64,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct NewWebAuthnChallenge {
+    pub user_id: Option<i32>,
+    pub ceremony: Ceremony,
+    pub challenge_hash: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelyingPartyEntity {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntity {
+    /// The user handle: opaque, base64url
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialParameters {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub alg: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub require_resident_key: bool,
+    pub user_verification: &'static str,
+}
+
+/// `PublicKeyCredentialCreationOptions` for `navigator.credentials.create()`, with binary
+/// fields base64url-encoded.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationOptions {
+    pub challenge: String,
+    pub rp: RelyingPartyEntity,
+    pub user: UserEntity,
+    pub pub_key_cred_params: Vec<CredentialParameters>,
+    pub timeout: u64,
+    pub attestation: &'static str,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+/// `PublicKeyCredentialRequestOptions` for `navigator.credentials.get()`. The allow list is
+/// empty: passkeys are discoverable, so the browser offers the ones it has for this site.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub user_verification: &'static str,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "attestationObject")]
+    pub attestation_object: String,
+}
+
+/// The `PublicKeyCredential` from `navigator.credentials.create()`, serialized with `toJSON()`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistrationCredential {
+    pub id: String,
+    pub response: AttestationResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterPasskeyDto {
+    /// Defaults to "Passkey"
+    pub name: Option<String>,
+    pub credential: RegistrationCredential,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "authenticatorData")]
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(rename = "userHandle")]
+    pub user_handle: Option<String>,
+}
+
+/// The `PublicKeyCredential` from `navigator.credentials.get()`, serialized with `toJSON()`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PasskeyLoginDto {
+    pub id: String,
+    pub response: AssertionResponse,
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::user::User;
+
+/// Who may create an account through registration and external sign-in providers.
+/// Admins can always create users directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationMode {
+    /// Anyone can sign up
+    Open,
+    /// Sign-ups need an invite code from an admin
+    InviteOnly,
+    /// Anyone can sign up, but cannot sign in until an admin approves the account.
+    /// An invite code skips the approval.
+    ApprovalRequired,
+    /// Nobody can sign up
+    Closed,
+}
+
+impl RegistrationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistrationMode::Open => "open",
+            RegistrationMode::InviteOnly => "invite_only",
+            RegistrationMode::ApprovalRequired => "approval_required",
+            RegistrationMode::Closed => "closed",
+        }
+    }
+}
+
+impl FromStr for RegistrationMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "open" => Ok(RegistrationMode::Open),
+            "invite_only" => Ok(RegistrationMode::InviteOnly),
+            "approval_required" => Ok(RegistrationMode::ApprovalRequired),
+            "closed" => Ok(RegistrationMode::Closed),
+            other => Err(format!("Unknown registration mode: {}", other)),
+        }
+    }
+}
+
+/// How a sign-up is let in: the invite it uses up, if any, and whether it has to wait
+/// for an admin's approval.
+#[derive(Debug, Default)]
+pub struct Admission {
+    pub invite_code_hash: Option<String>,
+    pub pending_approval: bool,
+}
+
+/// The result of a sign-up.
+#[derive(Debug)]
+pub struct Registered {
+    pub user: User,
+    pub pending_approval: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Invite {
+    pub id: i32,
+    pub created_by: Option<i32>,
+    pub note: Option<String>,
+    pub max_uses: i32,
+    pub use_count: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewInvite {
+    pub code_hash: String,
+    pub created_by: i32,
+    pub note: Option<String>,
+    pub max_uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateInviteDto {
+    pub note: Option<String>,
+    /// Defaults to a single use
+    pub max_uses: Option<i32>,
+    /// Never expires when left out
+    pub expires_in_days: Option<i64>,
+}
+
+/// Returned once on creation; only the hash of the code is stored.
+#[derive(Debug, Serialize)]
+pub struct CreatedInvite {
+    #[serde(flatten)]
+    pub invite: Invite,
+    pub code: String,
+}
+
+/// A sign-up waiting for an admin's approval.
+#[derive(Debug, Serialize)]
+pub struct PendingUser {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+    pub email_verified_at: Option<NaiveDateTime>,
+}
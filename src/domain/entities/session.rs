@@ -0,0 +1,36 @@
+use serde::Serialize;
+use chrono::NaiveDateTime;
+
+/// Where a sign-in came from, as reported by the HTTP request.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// A signed-in device: one row per access token handed out.
+#[derive(Debug, Serialize)]
+pub struct Session {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub token_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub last_active_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    /// Opened by a superuser impersonating the user
+    pub impersonated: bool,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+#[derive(Debug)]
+pub struct NewSession {
+    pub user_id: i32,
+    pub token_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub impersonator_id: Option<i32>,
+}
@@ -1,23 +1,79 @@
+use std::fmt;
 use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// A user as stored, including the password hash. Deliberately not `Serialize`:
+/// responses use `UserResponse` or `AdminUserResponse` instead.
+#[derive(Clone)]
 pub struct User {
     pub id: i32,
     pub username: String,
     pub email: String,
     pub password: String,
+    pub email_verified_at: Option<NaiveDateTime>,
 }
 
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("username", &self.username)
+            .field("email", &self.email)
+            .field("password", &"[REDACTED]")
+            .field("email_verified_at", &self.email_verified_at)
+            .finish()
+    }
+}
+
+/// What any signed-in user may see of another user.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct CreateUserDto {
+pub struct UserResponse {
+    pub id: i32,
+    pub username: String,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        UserResponse {
+            id: user.id,
+            username: user.username,
+        }
+    }
+}
+
+/// Everything but the password hash, for admins and for users looking at their own account.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserResponse {
+    pub id: i32,
     pub username: String,
     pub email: String,
-    pub password: String,
+    pub email_verified: bool,
+    pub email_verified_at: Option<NaiveDateTime>,
+}
+
+impl From<User> for AdminUserResponse {
+    fn from(user: User) -> Self {
+        AdminUserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            email_verified_at: user.email_verified_at,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct UpdateUserDto {
+pub struct CreateUserDto {
     pub username: String,
     pub email: String,
     pub password: String,
+}
+
+/// Partial profile update; fields left out are not changed. Passwords are changed
+/// through the dedicated change-password endpoint instead.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateUserDto {
+    pub username: Option<String>,
+    pub email: Option<String>,
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod entities;
-pub mod repositories;
\ No newline at end of file
+pub mod repositories;
+pub mod services;
\ No newline at end of file
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::domain::entities::announcement::Announcement;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AnnouncementRepository {
+    async fn create(&self, author_id: i32, content: String) -> Result<Announcement, Box<dyn std::error::Error>>;
+    async fn find_recent(&self, limit: i64) -> Result<Vec<Announcement>, Box<dyn std::error::Error>>;
+    async fn find_undelivered(&self, user_id: i32, since: NaiveDateTime) -> Result<Vec<Announcement>, Box<dyn std::error::Error>>;
+    /// Records receipts for the users that exist; unknown user ids are skipped.
+    async fn mark_delivered(&self, announcement_ids: Vec<i32>, user_ids: Vec<i32>) -> Result<(), Box<dyn std::error::Error>>;
+}
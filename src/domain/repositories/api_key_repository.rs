@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use crate::domain::entities::api_key::{ApiKey, NewApiKey};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ApiKeyRepository {
+    async fn create(&self, api_key: NewApiKey) -> Result<ApiKey, Box<dyn std::error::Error + Send + Sync>>;
+    /// Keys that have not been revoked, newest first.
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ApiKey>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn count_active(&self, user_id: i32) -> Result<i64, Box<dyn std::error::Error + Send + Sync>>;
+    /// A key that is neither revoked nor expired.
+    async fn find_active_by_hash(&self, key_hash: String) -> Result<Option<ApiKey>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn record_use(&self, key_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn revoke(&self, user_id: i32, key_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::entities::audit::{AuditEvent, AuditEventQuery, NewAuditEvent};
+
+/// Shared as `Arc<dyn AuditRepository>` by every use case that records events.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    /// Appends an event; the table accepts no updates or deletes.
+    async fn record_event(&self, event: NewAuditEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Events matching the query, newest first; `limit` has already been clamped.
+    async fn find_events(&self, query: &AuditEventQuery, limit: i64) -> Result<Vec<AuditEvent>, Box<dyn std::error::Error + Send + Sync>>;
+}
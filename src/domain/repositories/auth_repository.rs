@@ -1,11 +1,57 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use crate::domain::entities::{
-    auth::{AuthUser, RegisterUserDto},
+    auth::{AuthUser, RegisterUserDto, TokenPurpose},
+    mfa::UserTotp,
+    registration::Admission,
+    session::{NewSession, Session},
     user::User,
 };
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait AuthRepository {
     async fn authenticate(&self, auth: AuthUser) -> Result<User, Box<dyn std::error::Error + Send + Sync>>;
-    async fn register(&self, register_dto: RegisterUserDto) -> Result<User, Box<dyn std::error::Error + Send + Sync>>;
+    /// Creates the user and its account, using up the admission's invite in the same
+    /// transaction. Fails with `InvalidInput` if the invite is unknown, expired or used up.
+    async fn register(&self, register_dto: RegisterUserDto, admission: Admission) -> Result<User, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_by_id(&self, user_id: i32) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Role names embedded in access tokens.
+    async fn find_role_names(&self, user_id: i32) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>>;
+    /// The user a sign-in identifier (username or email address) names, as `authenticate` resolves it.
+    async fn find_by_login(&self, login: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn is_suspended(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    async fn is_pending_approval(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    async fn mark_email_verified(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn create_token(&self, user_id: i32, purpose: TokenPurpose, token_hash: String, expires_at: NaiveDateTime) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Marks a valid, unexpired token as used and returns the user it belongs to.
+    async fn consume_token(&self, purpose: TokenPurpose, token_hash: String) -> Result<Option<i32>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn revoke_tokens(&self, user_id: i32, purpose: TokenPurpose) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn count_tokens_since(&self, user_id: i32, purpose: TokenPurpose, since: NaiveDateTime) -> Result<i64, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn verify_password(&self, user_id: i32, password: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    async fn update_password(&self, user_id: i32, new_password: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Invalidates every access token issued to the user up to now.
+    async fn revoke_sessions(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn revoke_api_keys(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn create_session(&self, session: NewSession) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Sessions that are neither revoked nor expired, most recently active first.
+    async fn find_sessions(&self, user_id: i32) -> Result<Vec<Session>, Box<dyn std::error::Error + Send + Sync>>;
+    /// The unrevoked, unexpired session of an access token.
+    async fn find_active_session(&self, user_id: i32, token_id: &str) -> Result<Option<Session>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn touch_session(&self, session_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn revoke_session(&self, user_id: i32, session_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn find_totp(&self, user_id: i32) -> Result<Option<UserTotp>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Stores a new, unconfirmed secret, replacing any pending enrollment.
+    async fn save_totp_secret(&self, user_id: i32, secret: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Confirms the enrollment and replaces the user's recovery codes.
+    async fn confirm_totp(&self, user_id: i32, recovery_code_hashes: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Records the step of an accepted code; returns false if that step (or a later one) was already used.
+    async fn record_totp_step(&self, user_id: i32, step: i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    async fn delete_totp(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn consume_recovery_code(&self, user_id: i32, code_hash: String) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
 }
\ No newline at end of file
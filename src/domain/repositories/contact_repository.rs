@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::domain::entities::contact::{ContactRequest, ContactRequestStatus, ContactSettings};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ContactRepository {
+    async fn create_request(&self, requester_id: i32, addressee_id: i32) -> Result<ContactRequest, Box<dyn std::error::Error>>;
+    async fn find_request(&self, request_id: i32) -> Result<Option<ContactRequest>, Box<dyn std::error::Error>>;
+    async fn find_request_between(&self, user1_id: i32, user2_id: i32) -> Result<Option<ContactRequest>, Box<dyn std::error::Error>>;
+    async fn find_pending_for_user(&self, user_id: i32) -> Result<Vec<ContactRequest>, Box<dyn std::error::Error>>;
+    async fn update_status(&self, request_id: i32, status: ContactRequestStatus) -> Result<ContactRequest, Box<dyn std::error::Error>>;
+    async fn delete_request(&self, request_id: i32) -> Result<(), Box<dyn std::error::Error>>;
+    async fn find_contacts(&self, user_id: i32) -> Result<Vec<(i32, NaiveDateTime)>, Box<dyn std::error::Error>>;
+    async fn are_contacts(&self, user1_id: i32, user2_id: i32) -> Result<bool, Box<dyn std::error::Error>>;
+    async fn get_settings(&self, user_id: i32) -> Result<ContactSettings, Box<dyn std::error::Error>>;
+    async fn update_settings(&self, user_id: i32, allow_messages_from_strangers: bool) -> Result<ContactSettings, Box<dyn std::error::Error>>;
+}
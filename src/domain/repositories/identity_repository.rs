@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use crate::domain::entities::{
+    auth::RegisterUserDto,
+    identity::{ExternalIdentity, OidcLoginState},
+    user::User,
+};
+
+#[async_trait]
+pub trait IdentityRepository {
+    async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn link_identity(&self, user_id: i32, identity: &ExternalIdentity) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Creates the user, its account and the identity link in one transaction.
+    async fn register_with_identity(&self, register_dto: RegisterUserDto, identity: &ExternalIdentity, pending_approval: bool) -> Result<User, Box<dyn std::error::Error + Send + Sync>>;
+    async fn username_exists(&self, username: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn save_login_state(&self, state: OidcLoginState) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Deletes an unexpired state and returns its PKCE verifier, so each state works once.
+    async fn consume_login_state(&self, provider: &str, state_hash: String) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>>;
+}
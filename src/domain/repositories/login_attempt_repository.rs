@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::domain::entities::login_attempt::{FailureStats, LoginLockout, NewLoginLockout};
+
+#[async_trait]
+pub trait LoginAttemptRepository {
+    async fn record_attempt(&self, username: &str, ip_address: Option<&str>, succeeded: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn failures_for_username(&self, username: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>>;
+    async fn failures_for_ip(&self, ip_address: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn record_lockout(&self, lockout: NewLoginLockout) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_lockouts(&self, limit: i64) -> Result<Vec<LoginLockout>, Box<dyn std::error::Error + Send + Sync>>;
+}
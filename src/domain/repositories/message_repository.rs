@@ -1,9 +1,18 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use crate::domain::entities::message::DatabaseMessage;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait MessageRepository {
     async fn save_message(&self, message: DatabaseMessage) -> Result<DatabaseMessage, String>;
     async fn get_messages(&self, user1_id: i32, user2_id: i32) -> Result<Vec<DatabaseMessage>, String>;
     async fn mark_as_read(&self, message_id: i32) -> Result<(), String>;
-}
\ No newline at end of file
+    async fn get_scheduled_messages(&self, sender_id: i32) -> Result<Vec<DatabaseMessage>, String>;
+    /// Scheduled messages whose delivery time has come, oldest first.
+    async fn find_due_messages(&self, now: NaiveDateTime) -> Result<Vec<DatabaseMessage>, String>;
+    /// Returns false when the message was already delivered, so overlapping ticks push it only once.
+    async fn mark_as_delivered(&self, message_id: i32) -> Result<bool, String>;
+    async fn delete_message(&self, message_id: i32) -> Result<(), String>;
+    async fn delete_expired(&self, now: NaiveDateTime) -> Result<usize, String>;
+}
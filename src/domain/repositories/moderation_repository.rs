@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::domain::entities::message::DatabaseMessage;
+use crate::domain::entities::moderation::{
+    CreateReportDto, MessageReport, ModerationAction, NewModerationAction, ReportStatus,
+    ReportedMessage, UserSuspension,
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ModerationRepository {
+    async fn find_message(&self, message_id: i32) -> Result<Option<DatabaseMessage>, Box<dyn std::error::Error>>;
+    /// Hides or restores the message and logs `action` in one transaction. Hiding also
+    /// resolves the open reports against the message as actioned.
+    async fn set_message_hidden(&self, message_id: i32, hidden: bool, action: NewModerationAction) -> Result<ModerationAction, Box<dyn std::error::Error>>;
+
+    async fn create_report(&self, reporter_id: i32, report: CreateReportDto) -> Result<MessageReport, Box<dyn std::error::Error>>;
+    async fn find_report(&self, report_id: i32) -> Result<Option<MessageReport>, Box<dyn std::error::Error>>;
+    async fn find_reports(&self, status: Option<ReportStatus>) -> Result<Vec<ReportedMessage>, Box<dyn std::error::Error>>;
+    /// Resolves the report as `action.moderator_id` and logs `action` in one transaction.
+    async fn resolve_report(&self, report_id: i32, status: ReportStatus, action: NewModerationAction) -> Result<MessageReport, Box<dyn std::error::Error>>;
+
+    /// Suspends the user, revokes their sessions and API keys and logs `action` in one transaction.
+    async fn suspend_user(&self, user_id: i32, reason: Option<String>, suspended_until: Option<NaiveDateTime>, action: NewModerationAction) -> Result<UserSuspension, Box<dyn std::error::Error>>;
+    /// Lifts the user's suspensions and logs `action` in one transaction; `None`, with
+    /// nothing logged, when there was no suspension to lift.
+    async fn lift_suspensions(&self, user_id: i32, action: NewModerationAction) -> Result<Option<ModerationAction>, Box<dyn std::error::Error>>;
+    async fn find_active_suspension(&self, user_id: i32) -> Result<Option<UserSuspension>, Box<dyn std::error::Error>>;
+
+    async fn find_actions(&self, limit: i64) -> Result<Vec<ModerationAction>, Box<dyn std::error::Error>>;
+}
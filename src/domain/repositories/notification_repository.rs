@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::domain::entities::notification::{NotificationMode, NotificationPreference};
+
+/// Shared as `Arc<dyn NotificationRepository>` by the realtime message manager.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn find_all_for_user(&self, user_id: i32) -> Result<Vec<NotificationPreference>, Box<dyn std::error::Error>>;
+    async fn find_for_counterpart(&self, user_id: i32, counterpart_user_id: i32) -> Result<Option<NotificationPreference>, Box<dyn std::error::Error>>;
+    async fn upsert_for_counterpart(&self, user_id: i32, counterpart_user_id: i32, mode: NotificationMode, muted_until: Option<NaiveDateTime>) -> Result<NotificationPreference, Box<dyn std::error::Error>>;
+    async fn delete_for_counterpart(&self, user_id: i32, counterpart_user_id: i32) -> Result<(), Box<dyn std::error::Error>>;
+}
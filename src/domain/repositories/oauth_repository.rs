@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::entities::oauth::{AuthorizationCode, NewAuthorizationCode, NewOAuthClient, OAuthClient, UserProfile};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OAuthRepository {
+    async fn create_client(&self, client: NewOAuthClient) -> Result<OAuthClient, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_clients(&self) -> Result<Vec<OAuthClient>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_client(&self, client_id: &str) -> Result<Option<OAuthClient>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn delete_client(&self, client_id: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn save_authorization_code(&self, code: NewAuthorizationCode) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Deletes an unexpired code and returns it, so each code can be exchanged once.
+    async fn consume_authorization_code(&self, code_hash: String) -> Result<Option<AuthorizationCode>, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn find_user_profile(&self, user_id: i32) -> Result<Option<UserProfile>, Box<dyn std::error::Error + Send + Sync>>;
+}
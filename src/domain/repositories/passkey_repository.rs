@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use crate::domain::entities::passkey::{Ceremony, NewPasskey, NewWebAuthnChallenge, Passkey};
+
+#[async_trait]
+pub trait PasskeyRepository {
+    async fn create_passkey(&self, passkey: NewPasskey) -> Result<Passkey, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_passkeys(&self, user_id: i32) -> Result<Vec<Passkey>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_passkey_by_credential_id(&self, credential_id: &str) -> Result<Option<Passkey>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn record_passkey_use(&self, passkey_id: i32, sign_count: i64) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn delete_passkey(&self, user_id: i32, passkey_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    async fn save_challenge(&self, challenge: NewWebAuthnChallenge) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Deletes an unexpired challenge so it works once. The inner option is the user a
+    /// registration challenge was issued to.
+    async fn consume_challenge(&self, ceremony: Ceremony, challenge_hash: String) -> Result<Option<Option<i32>>, Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::entities::registration::{Invite, NewInvite, PendingUser};
+
+#[async_trait]
+pub trait RegistrationRepository {
+    async fn create_invite(&self, invite: NewInvite) -> Result<Invite, Box<dyn std::error::Error + Send + Sync>>;
+    /// Every invite, newest first.
+    async fn find_invites(&self) -> Result<Vec<Invite>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn revoke_invite(&self, invite_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Sign-ups waiting for approval, oldest first.
+    async fn find_pending_users(&self) -> Result<Vec<PendingUser>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Lets a waiting user sign in; `None` if the user was not waiting for approval.
+    async fn approve_user(&self, user_id: i32) -> Result<Option<PendingUser>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Deletes a sign-up that is still waiting for approval.
+    async fn reject_user(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
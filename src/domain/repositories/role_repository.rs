@@ -0,0 +1,6 @@
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait RoleRepository {
+    async fn has_any_role(&self, user_id: i32, role_names: &[&str]) -> Result<bool, Box<dyn std::error::Error>>;
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{Algorithm, Validation};
+
+use crate::domain::entities::auth::{Claims, TokenResponse};
+use crate::domain::entities::user::User;
+use crate::domain::services::signing_key::SigningKeys;
+
+const ACCESS_TOKEN_TTL_HOURS: i64 = 24;
+
+/// Issues and checks the bearer tokens of our own API. Tokens must name this service as
+/// issuer and audience, which keeps out ID tokens and tokens minted for other services.
+pub struct AccessTokenService {
+    signing_keys: Arc<SigningKeys>,
+    issuer: String,
+    audience: String,
+    leeway_seconds: u64,
+}
+
+impl AccessTokenService {
+    pub fn new(signing_keys: Arc<SigningKeys>, issuer: String, audience: String, leeway_seconds: u64) -> Self {
+        Self { signing_keys, issuer, audience, leeway_seconds }
+    }
+
+    /// Claims for a token issued now. Their `jti` identifies the session; API keys
+    /// reuse these without signing them.
+    pub fn claims_for(&self, user: &User, roles: Vec<String>) -> Claims {
+        let now = Utc::now().timestamp();
+
+        Claims {
+            sub: user.id,
+            exp: now + Duration::hours(ACCESS_TOKEN_TTL_HOURS).num_seconds(),
+            iat: now,
+            nbf: now,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            username: user.username.clone(),
+            roles,
+            scopes: None,
+            act: None,
+        }
+    }
+
+    pub fn sign(&self, claims: &Claims) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(TokenResponse {
+            access_token: self.signing_keys.sign(claims)?,
+            token_type: "Bearer".to_string(),
+            expires_in: claims.exp - claims.iat,
+        })
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, Box<dyn std::error::Error + Send + Sync>> {
+        // The algorithm is picked per key by `SigningKeys`
+        let mut validation = Validation::new(Algorithm::EdDSA);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        // `sub` is a number here, which jsonwebtoken only reads as a string; deserializing
+        // into `Claims` already requires it
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud"]);
+        validation.validate_nbf = true;
+        validation.leeway = self.leeway_seconds;
+
+        self.signing_keys.verify(token, &validation)
+    }
+}
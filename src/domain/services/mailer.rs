@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct OutgoingEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: OutgoingEmail) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
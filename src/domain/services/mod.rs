@@ -0,0 +1,8 @@
+pub mod access_token;
+pub mod mailer;
+pub mod oidc;
+pub mod signing_key;
+pub mod token_service;
+pub mod totp;
+pub mod webauthn;
+pub mod validation;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::digest;
+use crate::domain::entities::identity::ExternalIdentity;
+
+/// An OAuth2 / OpenID Connect provider users can sign in with.
+#[async_trait]
+pub trait OidcProvider: Send + Sync {
+    /// Where to send the browser to sign in, carrying our `state` and PKCE challenge.
+    fn authorization_url(&self, state: &str, code_challenge: &str, redirect_uri: &str) -> String;
+
+    /// Exchanges the authorization code (proving possession of the PKCE verifier)
+    /// and returns who signed in.
+    async fn exchange_code(&self, code: &str, code_verifier: &str, redirect_uri: &str) -> Result<ExternalIdentity, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Enabled providers by name, as used in the `/auth/oidc/{provider}` routes.
+pub type OidcProviders = HashMap<String, Arc<dyn OidcProvider>>;
+
+/// The S256 PKCE code challenge for a verifier (RFC 7636).
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(digest::digest(&digest::SHA256, code_verifier.as_bytes()))
+}
@@ -0,0 +1,267 @@
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use ring::{digest, hmac};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::domain::entities::oauth::{Jwk, Jwks};
+
+const ED25519_OID: &[u8] = &[0x2b, 0x65, 0x70];
+const RSA_ENCRYPTION_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+/// PKCS#8 v1 wrapping of a raw Ed25519 seed (RFC 8410)
+const ED25519_PKCS8_PREFIX: &[u8] = &[
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// Splits one DER element off `input`, returning its tag, contents and the remaining input.
+fn der_read(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+
+    let (length, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let length = rest[..count].iter().fold(0usize, |length, byte| (length << 8) | *byte as usize);
+        (length, &rest[count..])
+    };
+
+    (rest.len() >= length).then(|| (tag, &rest[..length], &rest[length..]))
+}
+
+fn der_expect(input: &[u8], expected_tag: u8) -> Option<(&[u8], &[u8])> {
+    let (tag, contents, rest) = der_read(input)?;
+    (tag == expected_tag).then_some((contents, rest))
+}
+
+fn unsigned_integer(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+/// Reads the modulus and public exponent from an RSAPublicKey or RSAPrivateKey structure.
+fn rsa_components(rsa_key: &[u8], private: bool) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (mut fields, _) = der_expect(rsa_key, TAG_SEQUENCE)?;
+    if private {
+        fields = der_expect(fields, TAG_INTEGER)?.1;
+    }
+    let (modulus, rest) = der_expect(fields, TAG_INTEGER)?;
+    let (exponent, _) = der_expect(rest, TAG_INTEGER)?;
+    Some((unsigned_integer(modulus), unsigned_integer(exponent)))
+}
+
+fn pem_to_der(pem: &str) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+    Ok(STANDARD.decode(body)?)
+}
+
+enum PublicKey {
+    Ed25519(Vec<u8>),
+    Rsa { modulus: Vec<u8>, exponent: Vec<u8> },
+}
+
+/// One JWT key. Keys kept only to verify tokens issued before a rotation have no
+/// encoding half.
+pub struct SigningKey {
+    algorithm: Algorithm,
+    encoding_key: Option<EncodingKey>,
+    decoding_key: DecodingKey,
+    jwk: Jwk,
+}
+
+impl SigningKey {
+    /// Loads an Ed25519 or RSA key from PEM: a PKCS#8 private key (`openssl genpkey`)
+    /// or, for verification only, a public key (`openssl pkey -pubout`).
+    pub fn from_pem(pem: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if pem.contains("BEGIN PRIVATE KEY") {
+            Self::from_pkcs8_der(&pem_to_der(pem)?)
+        } else if pem.contains("BEGIN PUBLIC KEY") {
+            Self::from_public_key_der(&pem_to_der(pem)?)
+        } else {
+            Err("Expected a PKCS#8 private key or a public key in PEM format".into())
+        }
+    }
+
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid = || "Invalid PKCS#8 private key";
+
+        let (private_key_info, _) = der_expect(der, TAG_SEQUENCE).ok_or_else(invalid)?;
+        let (_version, rest) = der_expect(private_key_info, TAG_INTEGER).ok_or_else(invalid)?;
+        let (algorithm_identifier, rest) = der_expect(rest, TAG_SEQUENCE).ok_or_else(invalid)?;
+        let (private_key, _) = der_expect(rest, TAG_OCTET_STRING).ok_or_else(invalid)?;
+        let (oid, _) = der_expect(algorithm_identifier, TAG_OID).ok_or_else(invalid)?;
+
+        match oid {
+            ED25519_OID => {
+                let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+                    .map_err(|e| format!("Invalid Ed25519 private key: {}", e))?;
+                let public_key = PublicKey::Ed25519(key_pair.public_key().as_ref().to_vec());
+                Self::new(Algorithm::EdDSA, Some(EncodingKey::from_ed_der(der)), public_key)
+            }
+            RSA_ENCRYPTION_OID => {
+                let (modulus, exponent) = rsa_components(private_key, true).ok_or_else(invalid)?;
+                // The wrapped key is the PKCS#1 structure the RSA signer expects
+                Self::new(Algorithm::RS256, Some(EncodingKey::from_rsa_der(private_key)), PublicKey::Rsa { modulus, exponent })
+            }
+            _ => Err("Unsupported key type; use Ed25519 or RSA".into()),
+        }
+    }
+
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let invalid = || "Invalid public key";
+
+        let (subject_public_key_info, _) = der_expect(der, TAG_SEQUENCE).ok_or_else(invalid)?;
+        let (algorithm_identifier, rest) = der_expect(subject_public_key_info, TAG_SEQUENCE).ok_or_else(invalid)?;
+        let (bit_string, _) = der_expect(rest, TAG_BIT_STRING).ok_or_else(invalid)?;
+        let (oid, _) = der_expect(algorithm_identifier, TAG_OID).ok_or_else(invalid)?;
+        // The first byte of a BIT STRING counts the unused bits, always zero here
+        let key_bytes = bit_string.get(1..).ok_or_else(invalid)?;
+
+        match oid {
+            ED25519_OID => Self::new(Algorithm::EdDSA, None, PublicKey::Ed25519(key_bytes.to_vec())),
+            RSA_ENCRYPTION_OID => {
+                let (modulus, exponent) = rsa_components(key_bytes, false).ok_or_else(invalid)?;
+                Self::new(Algorithm::RS256, None, PublicKey::Rsa { modulus, exponent })
+            }
+            _ => Err("Unsupported key type; use Ed25519 or RSA".into()),
+        }
+    }
+
+    /// An Ed25519 key derived from `SECRET_KEY`, for setups without key files. It is only
+    /// as secret as `SECRET_KEY` itself, but survives restarts like the old HS256 tokens did.
+    pub fn derive_from_secret(secret_key: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret_key.as_bytes());
+        let seed = hmac::sign(&key, b"jwt-signing-key");
+
+        let mut der = ED25519_PKCS8_PREFIX.to_vec();
+        der.extend_from_slice(seed.as_ref());
+        Self::from_pkcs8_der(&der)
+    }
+
+    pub fn kid(&self) -> &str {
+        &self.jwk.kid
+    }
+
+    fn new(algorithm: Algorithm, encoding_key: Option<EncodingKey>, public_key: PublicKey) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let (jwk, decoding_key) = match public_key {
+            PublicKey::Ed25519(public_key) => {
+                let x = URL_SAFE_NO_PAD.encode(public_key);
+                let thumbprint = format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#, x);
+                let jwk = Jwk {
+                    kty: "OKP".to_string(),
+                    kid: Self::thumbprint(&thumbprint),
+                    alg: "EdDSA".to_string(),
+                    key_use: "sig".to_string(),
+                    crv: Some("Ed25519".to_string()),
+                    x: Some(x.clone()),
+                    n: None,
+                    e: None,
+                };
+                (jwk, DecodingKey::from_ed_components(&x)?)
+            }
+            PublicKey::Rsa { modulus, exponent } => {
+                let n = URL_SAFE_NO_PAD.encode(modulus);
+                let e = URL_SAFE_NO_PAD.encode(exponent);
+                let thumbprint = format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, e, n);
+                let decoding_key = DecodingKey::from_rsa_components(&n, &e)?;
+                let jwk = Jwk {
+                    kty: "RSA".to_string(),
+                    kid: Self::thumbprint(&thumbprint),
+                    alg: "RS256".to_string(),
+                    key_use: "sig".to_string(),
+                    crv: None,
+                    x: None,
+                    n: Some(n),
+                    e: Some(e),
+                };
+                (jwk, decoding_key)
+            }
+        };
+
+        Ok(Self { algorithm, encoding_key, decoding_key, jwk })
+    }
+
+    /// The RFC 7638 thumbprint, so a key keeps its id across restarts and hosts.
+    fn thumbprint(canonical_jwk: &str) -> String {
+        URL_SAFE_NO_PAD.encode(digest::digest(&digest::SHA256, canonical_jwk.as_bytes()))
+    }
+}
+
+/// The keys behind every JWT we issue: one active key that signs, plus older keys that
+/// are still accepted until the tokens they signed have expired. Tokens name their key
+/// in the `kid` header, and all public halves are published as a JWKS.
+pub struct SigningKeys {
+    active: SigningKey,
+    previous: Vec<SigningKey>,
+}
+
+impl SigningKeys {
+    pub fn new(active: SigningKey, previous: Vec<SigningKey>) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if active.encoding_key.is_none() {
+            return Err("The active signing key must be a private key".into());
+        }
+
+        let previous = previous
+            .into_iter()
+            .filter(|key| key.kid() != active.kid())
+            .collect();
+
+        Ok(Self { active, previous })
+    }
+
+    pub fn active_kid(&self) -> &str {
+        self.active.kid()
+    }
+
+    pub fn active_algorithm(&self) -> &str {
+        &self.active.jwk.alg
+    }
+
+    pub fn sign<C: Serialize>(&self, claims: &C) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let encoding_key = self.active.encoding_key.as_ref().ok_or("The active signing key cannot sign")?;
+
+        let mut header = Header::new(self.active.algorithm);
+        header.kid = Some(self.active.kid().to_string());
+        Ok(encode(&header, claims, encoding_key)?)
+    }
+
+    /// Verifies the signature with the key named by `kid`, then the claims as `validation`
+    /// says; its algorithm list is replaced by the algorithm of that key.
+    pub fn verify<C: DeserializeOwned>(&self, token: &str, validation: &Validation) -> Result<C, Box<dyn std::error::Error + Send + Sync>> {
+        let header = decode_header(token)?;
+        let kid = header.kid.ok_or("Token has no key id")?;
+
+        let key = std::iter::once(&self.active)
+            .chain(&self.previous)
+            .find(|key| key.kid() == kid && key.algorithm == header.alg)
+            .ok_or("Token was signed with an unknown key")?;
+
+        let mut validation = validation.clone();
+        validation.algorithms = vec![key.algorithm];
+        Ok(decode::<C>(token, &key.decoding_key, &validation)?.claims)
+    }
+
+    pub fn jwks(&self) -> Jwks {
+        Jwks {
+            keys: std::iter::once(&self.active)
+                .chain(&self.previous)
+                .map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::{digest, hmac, rand::{SecureRandom, SystemRandom}};
+
+use crate::domain::entities::auth::TokenPurpose;
+
+const TOKEN_BYTES: usize = 32;
+
+/// Issues opaque tokens of the form `<random>.<signature>` that are mailed to users.
+///
+/// The signature binds the token to its purpose so a forged or mistyped token is rejected
+/// before touching the database; only the SHA-256 hash of a token is ever stored.
+pub struct TokenService {
+    key: hmac::Key,
+    rng: SystemRandom,
+}
+
+impl TokenService {
+    pub fn new(secret_key: &str) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret_key.as_bytes()),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// An unsigned random string, e.g. for PKCE verifiers.
+    pub fn random(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut random = [0u8; TOKEN_BYTES];
+        self.rng.fill(&mut random).map_err(|_| "Failed to generate token")?;
+        Ok(URL_SAFE_NO_PAD.encode(random))
+    }
+
+    pub fn generate(&self, purpose: TokenPurpose) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let encoded = self.random()?;
+        let signature = hmac::sign(&self.key, Self::signed_payload(purpose, &encoded).as_bytes());
+
+        Ok(format!("{}.{}", encoded, URL_SAFE_NO_PAD.encode(signature.as_ref())))
+    }
+
+    pub fn verify(&self, purpose: TokenPurpose, token: &str) -> bool {
+        let Some((encoded, signature)) = token.split_once('.') else {
+            return false;
+        };
+
+        match URL_SAFE_NO_PAD.decode(signature) {
+            Ok(signature) => hmac::verify(&self.key, Self::signed_payload(purpose, encoded).as_bytes(), &signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn hash(&self, token: &str) -> String {
+        digest::digest(&digest::SHA256, token.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn signed_payload(purpose: TokenPurpose, encoded: &str) -> String {
+        format!("{}:{}", purpose.as_str(), encoded)
+    }
+}
@@ -0,0 +1,119 @@
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Time-based one-time passwords (RFC 6238) with the parameters every authenticator app
+/// defaults to: HMAC-SHA1, 6 digits and a 30 second step.
+const STEP_SECONDS: i64 = 30;
+const DIGITS: u32 = 6;
+const SECRET_BYTES: usize = 20;
+/// Accept the previous and next step as well to tolerate clock drift.
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+pub fn generate_secret() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let mut secret = [0u8; SECRET_BYTES];
+    SystemRandom::new().fill(&mut secret).map_err(|_| "Failed to generate TOTP secret")?;
+    Ok(base32_encode(&secret))
+}
+
+/// Recovery codes look like `abcde-fghij` and carry 50 bits of entropy each.
+pub fn generate_recovery_code() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let mut random = [0u8; 10];
+    SystemRandom::new().fill(&mut random).map_err(|_| "Failed to generate recovery code")?;
+
+    let code: String = random
+        .iter()
+        .map(|byte| BASE32_ALPHABET[(*byte & 31) as usize].to_ascii_lowercase() as char)
+        .collect();
+    Ok(format!("{}-{}", &code[..5], &code[5..]))
+}
+
+pub fn otpauth_uri(issuer: &str, account_name: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account_name),
+        secret,
+        percent_encode(issuer),
+        DIGITS,
+        STEP_SECONDS
+    )
+}
+
+/// Returns the time step the code matched, so callers can reject replays of the same step.
+pub fn verify(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let current_step = unix_time / STEP_SECONDS;
+
+    (current_step - ALLOWED_DRIFT_STEPS..=current_step + ALLOWED_DRIFT_STEPS)
+        .find(|step| code_at(secret, step * STEP_SECONDS).as_deref() == Some(code))
+}
+
+/// The code an authenticator app shows at `unix_time`.
+pub(crate) fn code_at(secret: &str, unix_time: i64) -> Option<String> {
+    Some(hotp(&base32_decode(secret)?, (unix_time / STEP_SECONDS) as u64))
+}
+
+fn hotp(key: &[u8], counter: u64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key);
+    let digest = hmac::sign(&key, &counter.to_be_bytes());
+    let digest = digest.as_ref();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([digest[offset] & 0x7f, digest[offset + 1], digest[offset + 2], digest[offset + 3]]);
+
+    format!("{:0width$}", binary % 10u32.pow(DIGITS), width = DIGITS as usize)
+}
+
+pub(crate) fn base32_encode(data: &[u8]) -> String {
+    let mut output = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+
+    output
+}
+
+pub(crate) fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.trim_end_matches('=').chars() {
+        let value = BASE32_ALPHABET.iter().position(|a| *a as char == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
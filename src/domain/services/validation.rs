@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use std::fmt;
+use serde::Serialize;
+
+pub const MIN_USERNAME_LENGTH: usize = 3;
+pub const MAX_USERNAME_LENGTH: usize = 32;
+pub const MAX_EMAIL_LENGTH: usize = 254;
+pub const MAX_NAME_LENGTH: usize = 100;
+pub const DEFAULT_MIN_PASSWORD_LENGTH: usize = 8;
+/// bcrypt ignores everything past 72 bytes, so longer passwords are refused rather
+/// than silently truncated
+pub const MAX_PASSWORD_BYTES: usize = 72;
+
+const MAX_EMAIL_LOCAL_PART_LENGTH: usize = 64;
+
+/// A rejected field of a request body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+/// Every field of a request that failed validation, so clients can point out all the
+/// problems at once. Handlers answer these with 400 and the list of field errors.
+#[derive(Debug)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldError>,
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages = self.errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Collects field errors while a request is checked.
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(&mut self, field: &'static str, result: Result<(), String>) -> &mut Self {
+        if let Err(message) = result {
+            self.errors.push(FieldError { field, message });
+        }
+        self
+    }
+
+    /// Checks an optional field only when it is present.
+    pub fn check_optional<V: ?Sized>(
+        &mut self,
+        field: &'static str,
+        value: Option<&V>,
+        rule: impl FnOnce(&V) -> Result<(), String>,
+    ) -> &mut Self {
+        match value {
+            Some(value) => self.check(field, rule(value)),
+            None => self,
+        }
+    }
+
+    pub fn finish(&mut self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors { errors: std::mem::take(&mut self.errors) })
+        }
+    }
+}
+
+/// Letters, digits, `.`, `_` and `-`, starting with a letter or digit. Login treats
+/// anything with an `@` as an email address, so that is never allowed.
+pub fn validate_username(username: &str) -> Result<(), String> {
+    let length = username.chars().count();
+    if !(MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&length) {
+        return Err(format!(
+            "Username must be between {} and {} characters",
+            MIN_USERNAME_LENGTH, MAX_USERNAME_LENGTH
+        ));
+    }
+    if !username.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')) {
+        return Err("Username may only contain letters, digits, '.', '_' and '-'".to_string());
+    }
+    if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err("Username must start with a letter or digit".to_string());
+    }
+    Ok(())
+}
+
+/// A syntax check only: `local@domain` with a dotted domain name. Whether the address
+/// exists is proven by the verification email.
+pub fn validate_email(email: &str) -> Result<(), String> {
+    let invalid = || Err("Email must be a valid email address".to_string());
+
+    if email.len() > MAX_EMAIL_LENGTH {
+        return Err(format!("Email must be at most {} characters", MAX_EMAIL_LENGTH));
+    }
+
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return invalid();
+    };
+
+    let local_valid = !local.is_empty()
+        && local.len() <= MAX_EMAIL_LOCAL_PART_LENGTH
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c));
+
+    let labels = domain.split('.').collect::<Vec<_>>();
+    let domain_valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+
+    if local_valid && domain_valid {
+        Ok(())
+    } else {
+        invalid()
+    }
+}
+
+/// First, middle and last names: free text, but bounded and without control characters.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("Names must be at most {} characters", MAX_NAME_LENGTH));
+    }
+    if name.chars().any(char::is_control) {
+        return Err("Names cannot contain control characters".to_string());
+    }
+    Ok(())
+}
+
+/// The rules new passwords must follow: a minimum length and not being on a list of
+/// passwords known from breaches. The list is compared case-insensitively.
+#[derive(Debug)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    breached_passwords: HashSet<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_PASSWORD_LENGTH, Vec::new())
+    }
+}
+
+impl PasswordPolicy {
+    pub fn new(min_length: usize, breached_passwords: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            min_length,
+            breached_passwords: breached_passwords
+                .into_iter()
+                .map(|password| password.to_lowercase())
+                .collect(),
+        }
+    }
+
+    pub fn breached_password_count(&self) -> usize {
+        self.breached_passwords.len()
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), String> {
+        if password.chars().count() < self.min_length {
+            return Err(format!("Password must be at least {} characters", self.min_length));
+        }
+        if password.len() > MAX_PASSWORD_BYTES {
+            return Err(format!("Password must be at most {} bytes", MAX_PASSWORD_BYTES));
+        }
+        if self.breached_passwords.contains(&password.to_lowercase()) {
+            return Err("Password appears in a list of breached passwords; choose another one".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+use std::io::{Error as IoError, ErrorKind};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::{digest, signature};
+use serde::Deserialize;
+
+// WebAuthn (passkey) ceremonies as specified in Web Authentication Level 2, sections 7.1
+// (registration) and 7.2 (authentication). Only what a relying party needs is implemented:
+// a small CBOR reader, COSE keys for ES256, EdDSA and RS256, and the checks on client data
+// and authenticator data.
+
+pub const COSE_ALG_ES256: i64 = -7;
+pub const COSE_ALG_EDDSA: i64 = -8;
+pub const COSE_ALG_RS256: i64 = -257;
+/// In order of preference, as offered in `pubKeyCredParams`.
+pub const SUPPORTED_ALGORITHMS: &[i64] = &[COSE_ALG_ES256, COSE_ALG_EDDSA, COSE_ALG_RS256];
+
+const CEREMONY_CREATE: &str = "webauthn.create";
+const CEREMONY_GET: &str = "webauthn.get";
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+const RP_ID_HASH_LENGTH: usize = 32;
+const AUTHENTICATOR_DATA_MIN_LENGTH: usize = RP_ID_HASH_LENGTH + 1 + 4;
+const AAGUID_LENGTH: usize = 16;
+const MAX_CREDENTIAL_ID_LENGTH: usize = 1023;
+const MAX_CBOR_DEPTH: usize = 8;
+
+/// This service as a WebAuthn relying party.
+#[derive(Debug, Clone)]
+pub struct RelyingParty {
+    /// A registrable domain, e.g. `example.com`
+    pub id: String,
+    pub name: String,
+    /// The origin of the web app running the ceremonies, e.g. `https://example.com`
+    pub origin: String,
+}
+
+/// A credential accepted by `verify_registration`, ready to be stored.
+#[derive(Debug)]
+pub struct RegisteredCredential {
+    pub credential_id: Vec<u8>,
+    /// The COSE_Key, kept as sent so it can be parsed again for every assertion
+    pub public_key: Vec<u8>,
+    pub algorithm: i64,
+    pub sign_count: u32,
+}
+
+fn invalid(message: &str) -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(IoError::new(ErrorKind::InvalidInput, message.to_string()))
+}
+
+pub fn encode_base64url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Browsers send binary fields base64url-encoded; some clients keep the padding.
+pub fn decode_base64url(value: &str) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| invalid("Invalid base64url encoding"))
+}
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ceremony: String,
+    challenge: String,
+    origin: String,
+    #[serde(rename = "crossOrigin", default)]
+    cross_origin: bool,
+}
+
+fn parse_client_data(client_data_json: &[u8]) -> Result<CollectedClientData, Box<dyn std::error::Error + Send + Sync>> {
+    serde_json::from_slice(client_data_json).map_err(|_| invalid("Invalid client data"))
+}
+
+/// The challenge the client signed, used to find the ceremony it belongs to.
+pub fn client_data_challenge(client_data_json: &[u8]) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(parse_client_data(client_data_json)?.challenge)
+}
+
+fn check_client_data(
+    relying_party: &RelyingParty,
+    ceremony: &str,
+    challenge: &str,
+    client_data_json: &[u8],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let client_data = parse_client_data(client_data_json)?;
+
+    if client_data.ceremony != ceremony {
+        return Err(invalid("Unexpected ceremony type"));
+    }
+    if client_data.challenge != challenge {
+        return Err(invalid("Challenge does not match"));
+    }
+    if client_data.origin != relying_party.origin || client_data.cross_origin {
+        return Err(invalid("Unexpected origin"));
+    }
+    Ok(())
+}
+
+/// Checks the client data, authenticator data and COSE key of a new credential. We ask for
+/// `none` attestation and do not restrict authenticator models, so the attestation
+/// statement itself is not evaluated.
+pub fn verify_registration(
+    relying_party: &RelyingParty,
+    challenge: &str,
+    client_data_json: &[u8],
+    attestation_object: &[u8],
+) -> Result<RegisteredCredential, Box<dyn std::error::Error + Send + Sync>> {
+    check_client_data(relying_party, CEREMONY_CREATE, challenge, client_data_json)?;
+
+    let attestation = CborReader::new(attestation_object).read_all()?;
+    let auth_data = match attestation.get_text("authData") {
+        Some(Cbor::Bytes(bytes)) => bytes,
+        _ => return Err(invalid("Attestation object has no authenticator data")),
+    };
+    if !matches!(attestation.get_text("fmt"), Some(Cbor::Text(_))) {
+        return Err(invalid("Attestation object has no format"));
+    }
+
+    let authenticator_data = AuthenticatorData::parse(auth_data)?;
+    authenticator_data.check(relying_party)?;
+
+    let attested = authenticator_data.attested_credential
+        .ok_or_else(|| invalid("Authenticator data has no credential"))?;
+    let public_key = CoseKey::parse(&attested.public_key)?;
+
+    Ok(RegisteredCredential {
+        credential_id: attested.credential_id,
+        public_key: attested.public_key,
+        algorithm: public_key.algorithm(),
+        sign_count: authenticator_data.sign_count,
+    })
+}
+
+/// Checks an assertion made with a stored credential and returns the authenticator's new
+/// signature counter.
+pub fn verify_assertion(
+    relying_party: &RelyingParty,
+    challenge: &str,
+    public_key: &[u8],
+    stored_sign_count: u32,
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    assertion_signature: &[u8],
+) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+    check_client_data(relying_party, CEREMONY_GET, challenge, client_data_json)?;
+
+    let parsed = AuthenticatorData::parse(authenticator_data)?;
+    parsed.check(relying_party)?;
+
+    let client_data_hash = digest::digest(&digest::SHA256, client_data_json);
+    let signed_data = [authenticator_data, client_data_hash.as_ref()].concat();
+    if !CoseKey::parse(public_key)?.verify(&signed_data, assertion_signature) {
+        return Err(invalid("Invalid signature"));
+    }
+
+    // Authenticators without a counter always report 0; otherwise it must increase, or
+    // the credential has probably been cloned
+    if (parsed.sign_count != 0 || stored_sign_count != 0) && parsed.sign_count <= stored_sign_count {
+        return Err(invalid("Signature counter did not increase"));
+    }
+
+    Ok(parsed.sign_count)
+}
+
+struct AttestedCredential {
+    credential_id: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+struct AuthenticatorData<'a> {
+    rp_id_hash: &'a [u8],
+    flags: u8,
+    sign_count: u32,
+    attested_credential: Option<AttestedCredential>,
+}
+
+impl<'a> AuthenticatorData<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if data.len() < AUTHENTICATOR_DATA_MIN_LENGTH {
+            return Err(invalid("Authenticator data is too short"));
+        }
+
+        let flags = data[RP_ID_HASH_LENGTH];
+        let counter = &data[RP_ID_HASH_LENGTH + 1..AUTHENTICATOR_DATA_MIN_LENGTH];
+        let sign_count = u32::from_be_bytes([counter[0], counter[1], counter[2], counter[3]]);
+
+        let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0 {
+            let mut reader = CborReader::new(data);
+            reader.position = AUTHENTICATOR_DATA_MIN_LENGTH;
+            reader.take(AAGUID_LENGTH)?;
+
+            let length = reader.take(2)?;
+            let credential_id_length = u16::from_be_bytes([length[0], length[1]]) as usize;
+            if credential_id_length == 0 || credential_id_length > MAX_CREDENTIAL_ID_LENGTH {
+                return Err(invalid("Invalid credential id length"));
+            }
+            let credential_id = reader.take(credential_id_length)?.to_vec();
+
+            let key_start = reader.position;
+            reader.read(0)?;
+            Some(AttestedCredential {
+                credential_id,
+                public_key: data[key_start..reader.position].to_vec(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            rp_id_hash: &data[..RP_ID_HASH_LENGTH],
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    /// Passkeys replace the password, so the authenticator must have verified the user
+    /// (PIN or biometrics), not just seen a tap.
+    fn check(&self, relying_party: &RelyingParty) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let expected_hash = digest::digest(&digest::SHA256, relying_party.id.as_bytes());
+        if self.rp_id_hash != expected_hash.as_ref() {
+            return Err(invalid("Credential belongs to another relying party"));
+        }
+        if self.flags & FLAG_USER_PRESENT == 0 {
+            return Err(invalid("User was not present"));
+        }
+        if self.flags & FLAG_USER_VERIFIED == 0 {
+            return Err(invalid("User was not verified"));
+        }
+        Ok(())
+    }
+}
+
+/// The public key of a credential (RFC 8152 section 13).
+enum CoseKey {
+    Es256 { point: Vec<u8> },
+    Ed25519 { x: Vec<u8> },
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+impl CoseKey {
+    fn parse(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let key = CborReader::new(bytes).read_all()?;
+        let int = |label: i64| match key.get_int(label) {
+            Some(Cbor::Unsigned(value)) => i64::try_from(*value).ok(),
+            Some(Cbor::Negative(value)) => Some(*value),
+            _ => None,
+        };
+        let bytes = |label: i64| match key.get_int(label) {
+            Some(Cbor::Bytes(value)) => Some(value.clone()),
+            _ => None,
+        };
+
+        // kty 2 (EC2) with crv 1 (P-256), kty 1 (OKP) with crv 6 (Ed25519), kty 3 (RSA)
+        match (int(1), int(3)) {
+            (Some(2), Some(COSE_ALG_ES256)) if int(-1) == Some(1) => {
+                match (bytes(-2), bytes(-3)) {
+                    (Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => {
+                        Ok(CoseKey::Es256 { point: [&[0x04][..], &x, &y].concat() })
+                    }
+                    _ => Err(invalid("Invalid EC2 key")),
+                }
+            }
+            (Some(1), Some(COSE_ALG_EDDSA)) if int(-1) == Some(6) => match bytes(-2) {
+                Some(x) if x.len() == 32 => Ok(CoseKey::Ed25519 { x }),
+                _ => Err(invalid("Invalid OKP key")),
+            },
+            (Some(3), Some(COSE_ALG_RS256)) => match (bytes(-1), bytes(-2)) {
+                (Some(n), Some(e)) => Ok(CoseKey::Rs256 { n, e }),
+                _ => Err(invalid("Invalid RSA key")),
+            },
+            _ => Err(invalid("Unsupported credential algorithm")),
+        }
+    }
+
+    fn algorithm(&self) -> i64 {
+        match self {
+            CoseKey::Es256 { .. } => COSE_ALG_ES256,
+            CoseKey::Ed25519 { .. } => COSE_ALG_EDDSA,
+            CoseKey::Rs256 { .. } => COSE_ALG_RS256,
+        }
+    }
+
+    fn verify(&self, message: &[u8], assertion_signature: &[u8]) -> bool {
+        match self {
+            CoseKey::Es256 { point } => signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_ASN1, point)
+                .verify(message, assertion_signature)
+                .is_ok(),
+            CoseKey::Ed25519 { x } => signature::UnparsedPublicKey::new(&signature::ED25519, x)
+                .verify(message, assertion_signature)
+                .is_ok(),
+            CoseKey::Rs256 { n, e } => signature::RsaPublicKeyComponents { n, e }
+                .verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, assertion_signature)
+                .is_ok(),
+        }
+    }
+}
+
+/// The subset of CBOR (RFC 8949) used by WebAuthn: definite lengths only, no floats or tags.
+#[derive(Debug, PartialEq)]
+enum Cbor {
+    Unsigned(u64),
+    Negative(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(Cbor, Cbor)>),
+    Bool(bool),
+    Null,
+}
+
+impl Cbor {
+    fn get(&self, matches: impl Fn(&Cbor) -> bool) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(key, _)| matches(key)).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn get_text(&self, label: &str) -> Option<&Cbor> {
+        self.get(|key| matches!(key, Cbor::Text(text) if text == label))
+    }
+
+    fn get_int(&self, label: i64) -> Option<&Cbor> {
+        self.get(|key| match key {
+            Cbor::Unsigned(value) => i64::try_from(*value).ok() == Some(label),
+            Cbor::Negative(value) => *value == label,
+            _ => false,
+        })
+    }
+}
+
+struct CborReader<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Box<dyn std::error::Error + Send + Sync>> {
+        let end = self.position.checked_add(length)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(|| invalid("Unexpected end of CBOR data"))?;
+        let bytes = &self.input[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Reads a single value that must span the whole input.
+    fn read_all(&mut self) -> Result<Cbor, Box<dyn std::error::Error + Send + Sync>> {
+        let value = self.read(0)?;
+        if self.position != self.input.len() {
+            return Err(invalid("Trailing CBOR data"));
+        }
+        Ok(value)
+    }
+
+    fn argument(&mut self, additional: u8) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let length = match additional {
+            0..=23 => return Ok(additional as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(invalid("Unsupported CBOR encoding")),
+        };
+        Ok(self.take(length)?.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    /// A count of items that cannot exceed the remaining input, so a forged length
+    /// cannot make us allocate.
+    fn count(&mut self, additional: u8) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let count = self.argument(additional)?;
+        usize::try_from(count).ok()
+            .filter(|count| *count <= self.input.len() - self.position)
+            .ok_or_else(|| invalid("Unexpected end of CBOR data"))
+    }
+
+    fn read(&mut self, depth: usize) -> Result<Cbor, Box<dyn std::error::Error + Send + Sync>> {
+        if depth > MAX_CBOR_DEPTH {
+            return Err(invalid("CBOR data is nested too deeply"));
+        }
+
+        let initial = self.take(1)?[0];
+        let additional = initial & 0x1f;
+
+        match initial >> 5 {
+            0 => Ok(Cbor::Unsigned(self.argument(additional)?)),
+            1 => {
+                let value = i64::try_from(self.argument(additional)?)
+                    .map_err(|_| invalid("CBOR integer out of range"))?;
+                Ok(Cbor::Negative(-1 - value))
+            }
+            2 => {
+                let length = self.count(additional)?;
+                Ok(Cbor::Bytes(self.take(length)?.to_vec()))
+            }
+            3 => {
+                let length = self.count(additional)?;
+                let text = std::str::from_utf8(self.take(length)?).map_err(|_| invalid("Invalid CBOR text"))?;
+                Ok(Cbor::Text(text.to_string()))
+            }
+            4 => {
+                let count = self.count(additional)?;
+                let mut items = Vec::with_capacity(count);
+                for _ in 0..count {
+                    items.push(self.read(depth + 1)?);
+                }
+                Ok(Cbor::Array(items))
+            }
+            5 => {
+                let count = self.count(additional)?;
+                let mut entries = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let key = self.read(depth + 1)?;
+                    let value = self.read(depth + 1)?;
+                    entries.push((key, value));
+                }
+                Ok(Cbor::Map(entries))
+            }
+            7 => match additional {
+                20 => Ok(Cbor::Bool(false)),
+                21 => Ok(Cbor::Bool(true)),
+                22 => Ok(Cbor::Null),
+                _ => Err(invalid("Unsupported CBOR value")),
+            },
+            _ => Err(invalid("Unsupported CBOR value")),
+        }
+    }
+}
@@ -1 +1,3 @@
-pub mod database;
\ No newline at end of file
+pub mod database;
+pub mod signing_key;
+pub mod password_policy;
\ No newline at end of file
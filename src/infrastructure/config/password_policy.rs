@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use tracing::info;
+use crate::domain::services::validation::{PasswordPolicy, DEFAULT_MIN_PASSWORD_LENGTH};
+
+/// Builds the password policy. `PASSWORD_MIN_LENGTH` overrides the minimum length;
+/// `BREACHED_PASSWORDS_PATH` names a local text file with one known-breached password
+/// per line (e.g. a common-passwords list), which new passwords may not match.
+pub fn password_policy_from_env() -> Arc<PasswordPolicy> {
+    let min_length = std::env::var("PASSWORD_MIN_LENGTH")
+        .ok()
+        .map(|value| value.parse::<usize>().expect("PASSWORD_MIN_LENGTH must be a number"))
+        .unwrap_or(DEFAULT_MIN_PASSWORD_LENGTH);
+
+    let breached_passwords = match std::env::var("BREACHED_PASSWORDS_PATH") {
+        Ok(path) => std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read breached password list {}: {}", path, e))
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let policy = PasswordPolicy::new(min_length, breached_passwords);
+    info!(
+        "Password policy: at least {} characters, {} breached passwords refused",
+        min_length,
+        policy.breached_password_count()
+    );
+
+    Arc::new(policy)
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use tracing::{info, warn};
+use crate::domain::services::signing_key::{SigningKey, SigningKeys};
+
+fn load_key(path: &str) -> SigningKey {
+    let pem = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read signing key {}: {}", path, e));
+    SigningKey::from_pem(&pem)
+        .unwrap_or_else(|e| panic!("Failed to load signing key {}: {}", path, e))
+}
+
+/// Loads the JWT keys. `JWT_SIGNING_KEY_PATH` is the PKCS#8 PEM private key (Ed25519 or
+/// RSA) that signs new tokens; `JWT_VERIFICATION_KEY_PATHS` lists, comma separated, the
+/// PEM keys of earlier rotations that are still accepted.
+///
+/// To rotate, point `JWT_SIGNING_KEY_PATH` at a new key and move the old one to
+/// `JWT_VERIFICATION_KEY_PATHS` until the tokens it signed have expired.
+pub fn signing_keys_from_env(secret_key: &str) -> Arc<SigningKeys> {
+    let active = match std::env::var("JWT_SIGNING_KEY_PATH") {
+        Ok(path) => load_key(&path),
+        Err(_) => {
+            warn!("JWT_SIGNING_KEY_PATH is not set; deriving the signing key from SECRET_KEY");
+            SigningKey::derive_from_secret(secret_key).expect("Failed to derive signing key")
+        }
+    };
+
+    let previous = std::env::var("JWT_VERIFICATION_KEY_PATHS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(load_key)
+        .collect::<Vec<_>>();
+
+    let signing_keys = SigningKeys::new(active, previous).expect("Invalid JWT signing keys");
+    info!(
+        "Signing tokens with {} key {}",
+        signing_keys.active_algorithm(),
+        signing_keys.active_kid()
+    );
+
+    Arc::new(signing_keys)
+}
@@ -0,0 +1,22 @@
+pub mod json;
+pub mod redact;
+
+use tracing_subscriber::EnvFilter;
+
+use self::json::{JsonFields, JsonFormat};
+use self::redact::RedactingFields;
+
+const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Installs the global subscriber. `RUST_LOG` picks the levels (`info` when unset) and
+/// `LOG_FORMAT=json` writes one JSON object per line for log collectors. Either way,
+/// fields with sensitive names are redacted.
+pub fn init_from_env() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.fmt_fields(JsonFields).event_format(JsonFormat).init(),
+        _ => builder.fmt_fields(RedactingFields).init(),
+    }
+}
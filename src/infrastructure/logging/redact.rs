@@ -0,0 +1,59 @@
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::FormatFields;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Name segments (split on `_` and `.`) that mark a field as sensitive, e.g.
+/// `password`, `password_hash` or `access_token`.
+const SENSITIVE_NAME_SEGMENTS: &[&str] = &[
+    "password", "passwd", "hash", "salt", "secret", "token", "authorization", "cookie", "credential", "credentials", "totp",
+];
+
+pub fn is_sensitive(field_name: &str) -> bool {
+    field_name
+        .split(['_', '.'])
+        .any(|segment| SENSITIVE_NAME_SEGMENTS.iter().any(|sensitive| segment.eq_ignore_ascii_case(sensitive)))
+}
+
+/// Fields added by the `log` compatibility layer, already shown as the target.
+pub(crate) fn is_log_metadata(field_name: &str) -> bool {
+    field_name.starts_with("log.")
+}
+
+/// Formats fields as `name=value` like the default formatter, but never prints the
+/// value of a sensitive field.
+pub struct RedactingFields;
+
+impl<'writer> FormatFields<'writer> for RedactingFields {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = TextVisitor { writer, result: Ok(()), first: true };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+struct TextVisitor<'writer> {
+    writer: Writer<'writer>,
+    result: fmt::Result,
+    first: bool,
+}
+
+impl Visit for TextVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.result.is_err() || is_log_metadata(field.name()) {
+            return;
+        }
+
+        let separator = if self.first { "" } else { " " };
+        self.first = false;
+
+        self.result = match field.name() {
+            "message" => write!(self.writer, "{}{:?}", separator, value),
+            name if is_sensitive(name) => write!(self.writer, "{}{}={}", separator, name, REDACTED),
+            name => write!(self.writer, "{}{}={:?}", separator, name, value),
+        };
+    }
+}
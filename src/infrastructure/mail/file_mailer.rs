@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tracing::info;
+use crate::domain::services::mailer::{Mailer, OutgoingEmail};
+
+/// Writes every email to a file in the outbox directory instead of sending it.
+/// Meant for local development and tests.
+pub struct FileMailer {
+    outbox: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(outbox: impl Into<PathBuf>) -> Self {
+        Self { outbox: outbox.into() }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: OutgoingEmail) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        std::fs::create_dir_all(&self.outbox)?;
+
+        let file_name = format!("{}-{}.eml", chrono::Utc::now().format("%Y%m%d%H%M%S"), uuid::Uuid::new_v4());
+        let path = self.outbox.join(file_name);
+        let contents = format!("To: {}\nSubject: {}\n\n{}\n", email.to, email.subject, email.body);
+
+        std::fs::write(&path, contents)?;
+        info!("Email to {} written to {:?}", email.to, path);
+        Ok(())
+    }
+}
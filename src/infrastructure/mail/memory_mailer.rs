@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use std::sync::Mutex;
+use crate::domain::services::mailer::{Mailer, OutgoingEmail};
+
+/// Keeps sent emails in memory so tests can read the links in them.
+#[derive(Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<OutgoingEmail>>,
+}
+
+impl MemoryMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sent(&self) -> Vec<OutgoingEmail> {
+        self.sent.lock().map(|sent| sent.clone()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, email: OutgoingEmail) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.sent.lock().map_err(|_| "Mailer lock poisoned")?.push(email);
+        Ok(())
+    }
+}
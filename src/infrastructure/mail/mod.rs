@@ -0,0 +1,25 @@
+pub mod file_mailer;
+pub mod smtp_mailer;
+#[cfg(test)]
+pub mod memory_mailer;
+
+use std::sync::Arc;
+use tracing::info;
+use crate::domain::services::mailer::Mailer;
+use self::file_mailer::FileMailer;
+use self::smtp_mailer::SmtpMailer;
+
+/// Picks the mailer from `MAILER` (`smtp` or `file`, default `file`).
+pub fn mailer_from_env() -> Arc<dyn Mailer> {
+    match std::env::var("MAILER").unwrap_or_default().as_str() {
+        "smtp" => {
+            info!("Using SMTP mailer");
+            Arc::new(SmtpMailer::from_env())
+        }
+        _ => {
+            let outbox = std::env::var("MAIL_OUTBOX_DIR").unwrap_or_else(|_| "outbox".to_string());
+            info!("Using file mailer, writing emails to {}", outbox);
+            Arc::new(FileMailer::new(outbox))
+        }
+    }
+}
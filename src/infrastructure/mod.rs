@@ -1,3 +1,7 @@
 pub mod config;
+pub mod logging;
+pub mod mail;
+pub mod oidc;
 pub mod repositories;
+pub mod scheduler;
 pub mod websocket;
\ No newline at end of file
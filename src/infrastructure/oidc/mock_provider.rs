@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use crate::domain::entities::identity::ExternalIdentity;
+use crate::domain::services::oidc::{pkce_challenge, OidcProvider};
+
+/// An in-process provider that signs in a fixed identity without any user interaction.
+/// Its authorization URL points straight back at the callback with a fresh code, and
+/// codes can only be exchanged once with the verifier matching their PKCE challenge.
+pub struct MockOidcProvider {
+    identity: ExternalIdentity,
+    issued_codes: Mutex<HashMap<String, String>>,
+}
+
+impl MockOidcProvider {
+    pub fn new(identity: ExternalIdentity) -> Self {
+        Self {
+            identity,
+            issued_codes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Signs in `OIDC_MOCK_SUBJECT` / `OIDC_MOCK_EMAIL` / `OIDC_MOCK_USERNAME`.
+    pub fn from_env() -> Self {
+        let var = |key: &str, default: &str| std::env::var(key).unwrap_or_else(|_| default.to_string());
+
+        Self::new(ExternalIdentity {
+            provider: "mock".to_string(),
+            subject: var("OIDC_MOCK_SUBJECT", "mock-user-1"),
+            email: Some(var("OIDC_MOCK_EMAIL", "mock.user@example.com")),
+            email_verified: true,
+            preferred_username: Some(var("OIDC_MOCK_USERNAME", "mock_user")),
+            given_name: Some("Mock".to_string()),
+            family_name: Some("User".to_string()),
+        })
+    }
+
+    /// Issues a code directly, as if the user had approved the request.
+    pub fn issue_code(&self, code_challenge: &str) -> String {
+        let code = uuid::Uuid::new_v4().to_string();
+        self.issued_codes
+            .lock()
+            .expect("mock OIDC provider lock poisoned")
+            .insert(code.clone(), code_challenge.to_string());
+        code
+    }
+}
+
+#[async_trait]
+impl OidcProvider for MockOidcProvider {
+    fn authorization_url(&self, state: &str, code_challenge: &str, redirect_uri: &str) -> String {
+        let code = self.issue_code(code_challenge);
+        format!("{}?code={}&state={}", redirect_uri, code, state)
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str, _redirect_uri: &str) -> Result<ExternalIdentity, Box<dyn std::error::Error + Send + Sync>> {
+        let code_challenge = self.issued_codes
+            .lock()
+            .expect("mock OIDC provider lock poisoned")
+            .remove(code)
+            .ok_or("Unknown or already used authorization code")?;
+
+        if pkce_challenge(code_verifier) != code_challenge {
+            return Err("PKCE verification failed".into());
+        }
+
+        Ok(self.identity.clone())
+    }
+}
@@ -0,0 +1,35 @@
+pub mod http_provider;
+pub mod mock_provider;
+
+use std::sync::Arc;
+use tracing::{info, warn};
+use crate::domain::services::oidc::{OidcProvider, OidcProviders};
+use self::http_provider::HttpOidcProvider;
+use self::mock_provider::MockOidcProvider;
+
+/// Builds the providers listed in `OIDC_PROVIDERS` (comma separated, e.g. `google,github`).
+/// Each one is configured through `OIDC_<NAME>_*` variables; `mock` is an in-process
+/// provider for local development and tests.
+pub fn providers_from_env() -> OidcProviders {
+    let mut providers = OidcProviders::new();
+
+    let names = std::env::var("OIDC_PROVIDERS").unwrap_or_default();
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let provider: Arc<dyn OidcProvider> = if name == "mock" {
+            Arc::new(MockOidcProvider::from_env())
+        } else {
+            match HttpOidcProvider::from_env(name) {
+                Some(provider) => Arc::new(provider),
+                None => {
+                    warn!("Skipping OIDC provider {}: incomplete configuration", name);
+                    continue;
+                }
+            }
+        };
+
+        info!("OIDC provider enabled: {}", name);
+        providers.insert(name.to_string(), provider);
+    }
+
+    providers
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::dsl::{exists, not};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::announcement::Announcement;
+use crate::domain::repositories::announcement_repository::AnnouncementRepository;
+use crate::schema::{announcement_receipts, announcements, users};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = announcements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AnnouncementRecord {
+    pub id: i32,
+    pub author_id: Option<i32>,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<AnnouncementRecord> for Announcement {
+    fn from(record: AnnouncementRecord) -> Self {
+        Announcement {
+            id: record.id,
+            author_id: record.author_id,
+            content: record.content,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AnnouncementRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl AnnouncementRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AnnouncementRepository for AnnouncementRepositoryImpl {
+    async fn create(&self, author_id: i32, content: String) -> Result<Announcement, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(announcements::table)
+            .values((
+                announcements::author_id.eq(Some(author_id)),
+                announcements::content.eq(content),
+                announcements::created_at.eq(diesel::dsl::now),
+            ))
+            .returning(AnnouncementRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn find_recent(&self, limit: i64) -> Result<Vec<Announcement>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = announcements::table
+            .order(announcements::created_at.desc())
+            .limit(limit)
+            .select(AnnouncementRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(Announcement::from).collect())
+    }
+
+    async fn find_undelivered(&self, user_id: i32, since: NaiveDateTime) -> Result<Vec<Announcement>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = announcements::table
+            .filter(announcements::created_at.ge(since))
+            .filter(not(exists(
+                announcement_receipts::table
+                    .filter(announcement_receipts::announcement_id.eq(announcements::id))
+                    .filter(announcement_receipts::user_id.eq(user_id))
+            )))
+            .order(announcements::created_at.asc())
+            .select(AnnouncementRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(Announcement::from).collect())
+    }
+
+    async fn mark_delivered(&self, announcement_ids: Vec<i32>, user_ids: Vec<i32>) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        // Live connections are keyed by an unchecked user id, so one made-up id would
+        // otherwise fail the foreign key and lose every other receipt with it
+        let user_ids: Vec<i32> = users::table
+            .filter(users::id.eq_any(&user_ids))
+            .select(users::id)
+            .load(conn)?;
+
+        let receipts: Vec<_> = announcement_ids
+            .iter()
+            .flat_map(|announcement_id| {
+                user_ids.iter().map(move |user_id| (
+                    announcement_receipts::announcement_id.eq(*announcement_id),
+                    announcement_receipts::user_id.eq(*user_id),
+                ))
+            })
+            .collect();
+
+        if receipts.is_empty() {
+            return Ok(());
+        }
+
+        diesel::insert_into(announcement_receipts::table)
+            .values(receipts)
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::api_key::{ApiKey, NewApiKey};
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::schema::api_keys;
+
+/// `last_used_at` is written at most this often per key, not on every request.
+const LAST_USED_RESOLUTION_SECONDS: i64 = 60;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiKeyRecord {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ApiKeyRecord> for ApiKey {
+    fn from(record: ApiKeyRecord) -> Self {
+        ApiKey {
+            id: record.id,
+            user_id: record.user_id,
+            name: record.name,
+            prefix: record.prefix,
+            scopes: record.scopes,
+            expires_at: record.expires_at,
+            last_used_at: record.last_used_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiKeyRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ApiKeyRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Revokes every key the user still has, on a password reset or a suspension.
+pub(crate) fn revoke_user_api_keys(conn: &mut PgConnection, user_id: i32) -> QueryResult<usize> {
+    diesel::update(
+        api_keys::table
+            .filter(api_keys::user_id.eq(user_id))
+            .filter(api_keys::revoked_at.is_null())
+    )
+        .set(api_keys::revoked_at.eq(diesel::dsl::now))
+        .execute(conn)
+}
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {
+    async fn create(&self, api_key: NewApiKey) -> Result<ApiKey, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(api_keys::table)
+            .values((
+                api_keys::user_id.eq(api_key.user_id),
+                api_keys::name.eq(api_key.name),
+                api_keys::prefix.eq(api_key.prefix),
+                api_keys::key_hash.eq(api_key.key_hash),
+                api_keys::scopes.eq(api_key.scopes),
+                api_keys::expires_at.eq(api_key.expires_at),
+            ))
+            .returning(ApiKeyRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ApiKey>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = api_keys::table
+            .filter(api_keys::user_id.eq(user_id))
+            .filter(api_keys::revoked_at.is_null())
+            .order(api_keys::created_at.desc())
+            .select(ApiKeyRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(ApiKey::from).collect())
+    }
+
+    async fn count_active(&self, user_id: i32) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let count = api_keys::table
+            .filter(api_keys::user_id.eq(user_id))
+            .filter(api_keys::revoked_at.is_null())
+            .filter(api_keys::expires_at.is_null().or(api_keys::expires_at.gt(diesel::dsl::now)))
+            .count()
+            .get_result(conn)?;
+
+        Ok(count)
+    }
+
+    async fn find_active_by_hash(&self, key_hash: String) -> Result<Option<ApiKey>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = api_keys::table
+            .filter(api_keys::key_hash.eq(key_hash))
+            .filter(api_keys::revoked_at.is_null())
+            .filter(api_keys::expires_at.is_null().or(api_keys::expires_at.gt(diesel::dsl::now)))
+            .select(ApiKeyRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(ApiKey::from))
+    }
+
+    async fn record_use(&self, key_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+        let cutoff = (Utc::now() - Duration::seconds(LAST_USED_RESOLUTION_SECONDS)).naive_utc();
+
+        diesel::update(
+            api_keys::table
+                .find(key_id)
+                .filter(api_keys::last_used_at.is_null().or(api_keys::last_used_at.lt(cutoff)))
+        )
+            .set(api_keys::last_used_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, user_id: i32, key_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let revoked = diesel::update(
+            api_keys::table
+                .find(key_id)
+                .filter(api_keys::user_id.eq(user_id))
+                .filter(api_keys::revoked_at.is_null())
+        )
+            .set(api_keys::revoked_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+
+        Ok(revoked > 0)
+    }
+}
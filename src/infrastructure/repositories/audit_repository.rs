@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::audit::{AuditEvent, AuditEventQuery, NewAuditEvent};
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::schema::audit_events;
+
+const MAX_AUDIT_DETAILS_LENGTH: usize = 500;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = audit_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditEventRecord {
+    pub id: i32,
+    pub action: String,
+    pub outcome: String,
+    pub actor_id: Option<i32>,
+    pub target_user_id: Option<i32>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<AuditEventRecord> for AuditEvent {
+    fn from(record: AuditEventRecord) -> Self {
+        AuditEvent {
+            id: record.id,
+            action: record.action,
+            outcome: record.outcome,
+            actor_id: record.actor_id,
+            target_user_id: record.target_user_id,
+            ip_address: record.ip_address,
+            user_agent: record.user_agent,
+            details: record.details,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuditRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl AuditRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Writes one audit event on `conn`, so callers can record it inside their own transaction.
+pub(crate) fn insert_audit_event(conn: &mut PgConnection, event: NewAuditEvent) -> QueryResult<()> {
+    diesel::insert_into(audit_events::table)
+        .values((
+            audit_events::action.eq(event.action.as_str()),
+            audit_events::outcome.eq(event.outcome.as_str()),
+            audit_events::actor_id.eq(event.actor_id),
+            audit_events::target_user_id.eq(event.target_user_id),
+            audit_events::ip_address.eq(event.ip_address),
+            audit_events::user_agent.eq(event.user_agent),
+            audit_events::details.eq(event.details.map(|details| details.chars().take(MAX_AUDIT_DETAILS_LENGTH).collect::<String>())),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl AuditRepository for AuditRepositoryImpl {
+    async fn record_event(&self, event: NewAuditEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+        insert_audit_event(conn, event)?;
+        Ok(())
+    }
+
+    async fn find_events(&self, query: &AuditEventQuery, limit: i64) -> Result<Vec<AuditEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let mut events = audit_events::table
+            .order(audit_events::id.desc())
+            .limit(limit)
+            .select(AuditEventRecord::as_select())
+            .into_boxed();
+
+        if let Some(action) = query.action {
+            events = events.filter(audit_events::action.eq(action.as_str()));
+        }
+        if let Some(outcome) = query.outcome {
+            events = events.filter(audit_events::outcome.eq(outcome.as_str()));
+        }
+        if let Some(actor_id) = query.actor_id {
+            events = events.filter(audit_events::actor_id.eq(actor_id));
+        }
+        if let Some(target_user_id) = query.target_user_id {
+            events = events.filter(audit_events::target_user_id.eq(target_user_id));
+        }
+        if let Some(ip_address) = &query.ip_address {
+            events = events.filter(audit_events::ip_address.eq(ip_address));
+        }
+        if let Some(since) = query.since {
+            events = events.filter(audit_events::created_at.ge(since));
+        }
+        if let Some(until) = query.until {
+            events = events.filter(audit_events::created_at.lt(until));
+        }
+        if let Some(before_id) = query.before_id {
+            events = events.filter(audit_events::id.lt(before_id));
+        }
+
+        let records = events.load(conn)?;
+
+        Ok(records.into_iter().map(AuditEvent::from).collect())
+    }
+}
@@ -1,14 +1,60 @@
 use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::PgConnection;
 use tracing::debug;
 use bcrypt::{hash_with_salt, DEFAULT_COST};
 
-use crate::domain::entities::auth::{AuthUser, RegisterUserDto};
+use crate::domain::entities::audit::{AuditAction, AuditOutcome, NewAuditEvent};
+use crate::domain::entities::auth::{AuthUser, RegisterUserDto, TokenPurpose};
+use crate::domain::entities::mfa::UserTotp;
+use crate::domain::entities::registration::Admission;
+use crate::domain::entities::session::{ClientInfo, NewSession, Session};
 use crate::domain::entities::user::User;
 use crate::domain::repositories::auth_repository::AuthRepository;
-use crate::schema::{users, accounts};
+use crate::infrastructure::repositories::api_key_repository::revoke_user_api_keys;
+use crate::infrastructure::repositories::audit_repository::insert_audit_event;
+use crate::infrastructure::repositories::registration_repository::redeem_invite;
+use crate::schema::{users, accounts, user_suspensions, user_tokens, user_totp, user_recovery_codes, roles, user_roles, user_sessions};
+
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
+/// Same message for unknown users and wrong passwords, so logins cannot be used to find accounts.
+const INVALID_CREDENTIALS: &str = "Invalid username or password";
+pub(crate) const EMAIL_ALREADY_REGISTERED: &str = "An account with this email address already exists";
+/// `last_active_at` is written at most this often per session, not on every request.
+const LAST_ACTIVE_RESOLUTION_SECONDS: i64 = 60;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = user_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SessionRecord {
+    pub id: i32,
+    pub token_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub last_active_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub impersonator_id: Option<i32>,
+}
+
+impl From<SessionRecord> for Session {
+    fn from(record: SessionRecord) -> Self {
+        Session {
+            id: record.id,
+            token_id: record.token_id,
+            user_agent: record.user_agent,
+            ip_address: record.ip_address,
+            expires_at: record.expires_at,
+            last_active_at: record.last_active_at,
+            created_at: record.created_at,
+            impersonated: record.impersonator_id.is_some(),
+            current: false,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AuthRepositoryImpl {
@@ -19,65 +65,193 @@ pub struct AuthRepositoryImpl {
 impl AuthRepositoryImpl {
     #[allow(dead_code)]
     pub fn new(pool: Pool<ConnectionManager<PgConnection>>, secret_key: String) -> Self {
-        let salt = secret_key.as_bytes()[..16]
-            .try_into()
-            .expect("SECRET_KEY must be at least 16 bytes");
+        Self { pool, salt: password_salt(&secret_key) }
+    }
+
+    fn hash_password(&self, raw_password: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        hash_password(raw_password, self.salt)
+    }
+}
+
+/// The salt every password hash is made with, taken from the first 16 bytes of `SECRET_KEY`.
+pub(crate) fn password_salt(secret_key: &str) -> [u8; 16] {
+    secret_key.as_bytes()[..16]
+        .try_into()
+        .expect("SECRET_KEY must be at least 16 bytes")
+}
+
+/// Hashes a password the same way registration does, so the result can be compared to the stored hash.
+pub(crate) fn hash_password(raw_password: &str, salt: [u8; 16]) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(hash_with_salt(raw_password.as_bytes(), DEFAULT_COST, salt)
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
+        .to_string())
+}
+
+/// Creates the user and its `accounts` row. Callers run this inside a transaction so
+/// a failure leaves neither behind.
+pub(crate) fn insert_user_with_account(
+    conn: &mut PgConnection,
+    register_dto: &RegisterUserDto,
+    hashed_password: &str,
+    verified_at: Option<NaiveDateTime>,
+    pending: bool,
+) -> QueryResult<User> {
+    use self::users::dsl::*;
+
+    // Create user with all fields selected
+    let user = diesel::insert_into(users)
+        .values((
+            username.eq(&register_dto.username),
+            email.eq(&register_dto.email),
+            password.eq(hashed_password),
+            email_verified_at.eq(verified_at),
+            pending_approval.eq(pending),
+        ))
+        .returning((id, username, email, password, email_verified_at))
+        .get_result::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)?;
+
+    // Create associated account
+    diesel::insert_into(accounts::table)
+        .values((
+            accounts::user_id.eq(user.0),
+            accounts::first_name.eq(&register_dto.first_name),
+            accounts::middle_name.eq(&register_dto.middle_name),
+            accounts::last_name.eq(&register_dto.last_name),
+            accounts::created_at.eq(diesel::dsl::now),
+            accounts::updated_at.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+
+    debug!("User and account created successfully for: {}", register_dto.username);
+
+    Ok(User {
+        id: user.0,
+        username: user.1,
+        email: user.2,
+        password: user.3,
+        email_verified_at: user.4,
+    })
+}
+
+/// The user a sign-in identifier names. Anything with an @ is tried as an email address
+/// first, matched case-insensitively, then as a username: new usernames cannot contain
+/// an @, but accounts from before that rule may.
+fn find_user_by_login(conn: &mut PgConnection, login: &str) -> QueryResult<Option<User>> {
+    use self::users::dsl::*;
 
-        debug!("Initializing AuthRepositoryImpl with salt: {:?}", salt);
-        Self { pool, salt }
+    let mut user = None;
+    if login.contains('@') {
+        user = users
+            .filter(lower(email).eq(login.to_lowercase()))
+            .select((id, username, email, password, email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()?;
     }
+    if user.is_none() {
+        user = users
+            .filter(username.eq(login))
+            .select((id, username, email, password, email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()?;
+    }
+
+    Ok(user.map(|user| User {
+        id: user.0,
+        username: user.1,
+        email: user.2,
+        password: user.3,
+        email_verified_at: user.4,
+    }))
+}
+
+fn has_active_suspension(conn: &mut PgConnection, user_id: i32) -> QueryResult<bool> {
+    let active_suspensions = user_suspensions::table
+        .filter(user_suspensions::user_id.eq(user_id))
+        .filter(user_suspensions::lifted_at.is_null())
+        .filter(
+            user_suspensions::suspended_until.is_null()
+                .or(user_suspensions::suspended_until.gt(diesel::dsl::now.nullable()))
+        )
+        .count()
+        .get_result::<i64>(conn)?;
+
+    Ok(active_suspensions > 0)
+}
+
+pub(crate) fn map_registration_error(e: diesel::result::Error) -> Box<dyn std::error::Error + Send + Sync> {
+    match e {
+        // Lost a race with a concurrent registration for the same username or email
+        diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "Username or email already registered"
+            ))
+        }
+        e => Box::new(e),
+    }
+}
+
+/// Revokes every session of the user that is still open.
+pub(crate) fn revoke_user_sessions(conn: &mut PgConnection, user_id: i32) -> QueryResult<usize> {
+    diesel::update(
+        user_sessions::table
+            .filter(user_sessions::user_id.eq(user_id))
+            .filter(user_sessions::revoked_at.is_null())
+    )
+        .set(user_sessions::revoked_at.eq(diesel::dsl::now.nullable()))
+        .execute(conn)
 }
 
 #[async_trait]
 impl AuthRepository for AuthRepositoryImpl {
     async fn authenticate(&self, auth: AuthUser) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
-        use self::users::dsl::*;
-
         debug!("Starting authentication for user: {}", auth.username);
 
         let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
-        // Get user with all fields
-        let user_result = users
-            .filter(username.eq(&auth.username))
-            .select((id, username, email, password))
-            .first::<(i32, String, String, String)>(conn)
+        let user_result = find_user_by_login(conn, &auth.username)
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
-        debug!("Found stored hash for user {}: {}", auth.username, user_result.3);
+        // Hash the provided password with the same salt for comparison. This also runs for
+        // unknown usernames so both failures take the same time.
+        let hashed_input = self.hash_password(&auth.password)?;
 
-        // Hash the provided password with the same salt for comparison
-        let hashed_input = hash_with_salt(
-            auth.password.as_bytes(),
-            DEFAULT_COST,
-            self.salt
-        ).map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
-            .to_string();
-
-        debug!("Generated hash for login attempt: {}", hashed_input);
-        debug!("Hash comparison for {}", auth.username);
-        debug!("  Stored hash  : {}", user_result.3);
-        debug!("  Login hash   : {}", hashed_input);
-        debug!("  Match result : {}", user_result.3 == hashed_input);
+        let user = match user_result {
+            Some(user) => user,
+            None => {
+                debug!("Authentication failed, no user named: {}", auth.username);
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    INVALID_CREDENTIALS
+                )));
+            }
+        };
 
-        if user_result.3 == hashed_input {
+        if user.password == hashed_input {
             debug!("Password verification successful for user: {}", auth.username);
-            Ok(User {
-                id: user_result.0,
-                username: user_result.1,
-                email: user_result.2,
-                password: user_result.3,
-            })
+
+            let suspended = has_active_suspension(conn, user.id)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+            if suspended {
+                debug!("Login rejected for suspended user: {}", auth.username);
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "Account is suspended"
+                )));
+            }
+
+            Ok(user)
         } else {
             debug!("Password verification failed for user: {}", auth.username);
             Err(Box::new(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "Invalid password"
+                INVALID_CREDENTIALS
             )))
         }
     }
 
-    async fn register(&self, register_dto: RegisterUserDto) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
+    async fn register(&self, register_dto: RegisterUserDto, admission: Admission) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
         use self::users::dsl::*;
         debug!("Starting registration for user: {}", register_dto.username);
 
@@ -86,8 +260,8 @@ impl AuthRepository for AuthRepositoryImpl {
         // Check if username already exists
         let existing_user = users
             .filter(username.eq(&register_dto.username))
-            .select((id, username, email, password))
-            .first::<(i32, String, String, String)>(conn)
+            .select((id, username, email, password, email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
             .optional()
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
@@ -99,6 +273,20 @@ impl AuthRepository for AuthRepositoryImpl {
             )));
         }
 
+        let email_taken = users
+            .filter(lower(email).eq(register_dto.email.to_lowercase()))
+            .count()
+            .get_result::<i64>(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        if email_taken > 0 {
+            debug!("Registration failed: Email already registered for: {}", register_dto.username);
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                EMAIL_ALREADY_REGISTERED
+            )));
+        }
+
         // Hash the password with our consistent salt
         let hashed_password = hash_with_salt(
             register_dto.password.as_bytes(),
@@ -107,47 +295,434 @@ impl AuthRepository for AuthRepositoryImpl {
         ).map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
             .to_string();
 
-        debug!("Generated hash for registration: {}", hashed_password);
-
         // Start transaction
-        conn.transaction(|conn| {
-            // Create user with all fields selected
-            let user_result = diesel::insert_into(users)
+        conn.transaction::<_, Box<dyn std::error::Error + Send + Sync>, _>(|conn| {
+            if let Some(code_hash) = &admission.invite_code_hash {
+                redeem_invite(conn, code_hash)?;
+            }
+
+            insert_user_with_account(conn, &register_dto, &hashed_password, None, admission.pending_approval)
+                .map_err(map_registration_error)
+        })
+    }
+
+    async fn find_by_id(&self, user_id: i32) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        use self::users::dsl::*;
+
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let user_result = users
+            .filter(id.eq(user_id))
+            .select((id, username, email, password, email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(user_result.map(|user| User {
+            id: user.0,
+            username: user.1,
+            email: user.2,
+            password: user.3,
+            email_verified_at: user.4,
+        }))
+    }
+
+    async fn find_role_names(&self, user_id: i32) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let names = user_roles::table
+            .inner_join(roles::table)
+            .filter(user_roles::user_id.eq(user_id))
+            .order(roles::name.asc())
+            .select(roles::name)
+            .load::<String>(conn)?;
+
+        Ok(names)
+    }
+
+    async fn find_by_email(&self, user_email: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        use self::users::dsl::*;
+
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let user_result = users
+            .filter(lower(email).eq(user_email.to_lowercase()))
+            .select((id, username, email, password, email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(user_result.map(|user| User {
+            id: user.0,
+            username: user.1,
+            email: user.2,
+            password: user.3,
+            email_verified_at: user.4,
+        }))
+    }
+
+    async fn find_by_login(&self, login: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        Ok(find_user_by_login(conn, login)?)
+    }
+
+    async fn is_suspended(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+        Ok(has_active_suspension(conn, user_id)?)
+    }
+
+    async fn is_pending_approval(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let pending = users::table
+            .filter(users::id.eq(user_id))
+            .select(users::pending_approval)
+            .first::<bool>(conn)
+            .optional()?;
+
+        Ok(pending.unwrap_or(false))
+    }
+
+    async fn mark_email_verified(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use self::users::dsl::*;
+
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        diesel::update(users.filter(id.eq(user_id)))
+            .filter(email_verified_at.is_null())
+            .set(email_verified_at.eq(diesel::dsl::now.nullable()))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn create_token(&self, user_id: i32, purpose: TokenPurpose, token_hash: String, expires_at: NaiveDateTime) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        diesel::insert_into(user_tokens::table)
+            .values((
+                user_tokens::user_id.eq(user_id),
+                user_tokens::purpose.eq(purpose.as_str()),
+                user_tokens::token_hash.eq(token_hash),
+                user_tokens::expires_at.eq(expires_at),
+                user_tokens::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn consume_token(&self, purpose: TokenPurpose, token_hash: String) -> Result<Option<i32>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        // Marking the token used in the same statement that finds it makes it single-use
+        // even when two requests race with the same link
+        let owner = diesel::update(
+            user_tokens::table
+                .filter(user_tokens::purpose.eq(purpose.as_str()))
+                .filter(user_tokens::token_hash.eq(token_hash))
+                .filter(user_tokens::used_at.is_null())
+                .filter(user_tokens::expires_at.gt(diesel::dsl::now))
+        )
+            .set(user_tokens::used_at.eq(diesel::dsl::now.nullable()))
+            .returning(user_tokens::user_id)
+            .get_result::<i32>(conn)
+            .optional()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(owner)
+    }
+
+    async fn revoke_tokens(&self, user_id: i32, purpose: TokenPurpose) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        diesel::update(
+            user_tokens::table
+                .filter(user_tokens::user_id.eq(user_id))
+                .filter(user_tokens::purpose.eq(purpose.as_str()))
+                .filter(user_tokens::used_at.is_null())
+        )
+            .set(user_tokens::used_at.eq(diesel::dsl::now.nullable()))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn count_tokens_since(&self, user_id: i32, purpose: TokenPurpose, since: NaiveDateTime) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let count = user_tokens::table
+            .filter(user_tokens::user_id.eq(user_id))
+            .filter(user_tokens::purpose.eq(purpose.as_str()))
+            .filter(user_tokens::created_at.ge(since))
+            .count()
+            .get_result::<i64>(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(count)
+    }
+
+    async fn verify_password(&self, user_id: i32, candidate: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        use self::users::dsl::*;
+
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let stored_hash = users
+            .filter(id.eq(user_id))
+            .select(password)
+            .first::<String>(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(stored_hash == self.hash_password(candidate)?)
+    }
+
+    async fn update_password(&self, user_id: i32, new_password: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use self::users::dsl::*;
+
+        let hashed_password = self.hash_password(new_password)?;
+
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let updated = diesel::update(users.filter(id.eq(user_id)))
+            .set(password.eq(hashed_password))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        if updated == 0 {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "User not found"
+            )));
+        }
+
+        debug!("Password updated for user {}", user_id);
+        Ok(())
+    }
+
+    async fn revoke_sessions(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        revoke_user_sessions(conn, user_id)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn revoke_api_keys(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        revoke_user_api_keys(conn, user_id)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn create_session(&self, session: NewSession) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        // Every sign-in method ends here, so this is where successful logins are audited
+        let action = if session.impersonator_id.is_some() { AuditAction::Impersonation } else { AuditAction::Login };
+        let client = ClientInfo { user_agent: session.user_agent.clone(), ip_address: session.ip_address.clone() };
+        let event = NewAuditEvent {
+            actor_id: Some(session.impersonator_id.unwrap_or(session.user_id)),
+            target_user_id: Some(session.user_id),
+            ..NewAuditEvent::new(action, AuditOutcome::Success, &client)
+        };
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::insert_into(user_sessions::table)
                 .values((
-                    username.eq(&register_dto.username),
-                    email.eq(&register_dto.email),
-                    password.eq(&hashed_password),
+                    user_sessions::user_id.eq(session.user_id),
+                    user_sessions::token_id.eq(session.token_id),
+                    user_sessions::user_agent.eq(session.user_agent),
+                    user_sessions::ip_address.eq(session.ip_address),
+                    user_sessions::expires_at.eq(session.expires_at),
+                    user_sessions::impersonator_id.eq(session.impersonator_id),
                 ))
-                .returning((id, username, email, password))
-                .get_result::<(i32, String, String, String)>(conn);
-
-            match user_result {
-                Ok(user) => {
-                    // Create associated account
-                    diesel::insert_into(accounts::table)
-                        .values((
-                            accounts::user_id.eq(user.0),
-                            accounts::first_name.eq(&register_dto.first_name),
-                            accounts::middle_name.eq(&register_dto.middle_name),
-                            accounts::last_name.eq(&register_dto.last_name),
-                            accounts::created_at.eq(diesel::dsl::now),
-                            accounts::updated_at.eq(diesel::dsl::now),
-                        ))
-                        .execute(conn)?;
-
-                    debug!("User and account created successfully for: {}", register_dto.username);
-                    debug!("Stored hash in database: {}", user.3);
-
-                    Ok(User {
-                        id: user.0,
-                        username: user.1,
-                        email: user.2,
-                        password: user.3,
-                    })
-                }
-                Err(e) => Err(e),
-            }
+                .execute(conn)?;
+
+            insert_audit_event(conn, event)
+        })
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn find_sessions(&self, user_id: i32) -> Result<Vec<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let records = user_sessions::table
+            .filter(user_sessions::user_id.eq(user_id))
+            .filter(user_sessions::revoked_at.is_null())
+            .filter(user_sessions::expires_at.gt(diesel::dsl::now))
+            .order(user_sessions::last_active_at.desc())
+            .select(SessionRecord::as_select())
+            .load(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(records.into_iter().map(Session::from).collect())
+    }
+
+    async fn find_active_session(&self, user_id: i32, token_id: &str) -> Result<Option<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let record = user_sessions::table
+            .filter(user_sessions::token_id.eq(token_id))
+            .filter(user_sessions::user_id.eq(user_id))
+            .filter(user_sessions::revoked_at.is_null())
+            .filter(user_sessions::expires_at.gt(diesel::dsl::now))
+            .select(SessionRecord::as_select())
+            .first(conn)
+            .optional()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(record.map(Session::from))
+    }
+
+    async fn touch_session(&self, session_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        let cutoff = (Utc::now() - Duration::seconds(LAST_ACTIVE_RESOLUTION_SECONDS)).naive_utc();
+
+        diesel::update(
+            user_sessions::table
+                .find(session_id)
+                .filter(user_sessions::last_active_at.lt(cutoff))
+        )
+            .set(user_sessions::last_active_at.eq(diesel::dsl::now))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn revoke_session(&self, user_id: i32, session_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let revoked = diesel::update(
+            user_sessions::table
+                .find(session_id)
+                .filter(user_sessions::user_id.eq(user_id))
+                .filter(user_sessions::revoked_at.is_null())
+        )
+            .set(user_sessions::revoked_at.eq(diesel::dsl::now.nullable()))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(revoked > 0)
+    }
+
+    async fn find_totp(&self, user_id: i32) -> Result<Option<UserTotp>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let totp = user_totp::table
+            .find(user_id)
+            .select((user_totp::secret, user_totp::confirmed_at))
+            .first::<(String, Option<NaiveDateTime>)>(conn)
+            .optional()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(totp.map(|totp| UserTotp {
+            secret: totp.0,
+            confirmed_at: totp.1,
+        }))
+    }
+
+    async fn save_totp_secret(&self, user_id: i32, secret: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        diesel::insert_into(user_totp::table)
+            .values((
+                user_totp::user_id.eq(user_id),
+                user_totp::secret.eq(&secret),
+                user_totp::created_at.eq(diesel::dsl::now),
+            ))
+            .on_conflict(user_totp::user_id)
+            .do_update()
+            .set((
+                user_totp::secret.eq(&secret),
+                user_totp::confirmed_at.eq(None::<NaiveDateTime>),
+                user_totp::last_used_step.eq(None::<i64>),
+                user_totp::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn confirm_totp(&self, user_id: i32, recovery_code_hashes: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::update(user_totp::table.find(user_id))
+                .set(user_totp::confirmed_at.eq(diesel::dsl::now.nullable()))
+                .execute(conn)?;
+
+            diesel::delete(user_recovery_codes::table.filter(user_recovery_codes::user_id.eq(user_id)))
+                .execute(conn)?;
+
+            let codes: Vec<_> = recovery_code_hashes
+                .into_iter()
+                .map(|code_hash| (
+                    user_recovery_codes::user_id.eq(user_id),
+                    user_recovery_codes::code_hash.eq(code_hash),
+                ))
+                .collect();
+
+            diesel::insert_into(user_recovery_codes::table)
+                .values(codes)
+                .execute(conn)?;
+
+            Ok(())
         })
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
-}
\ No newline at end of file
+
+    async fn record_totp_step(&self, user_id: i32, step: i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let updated = diesel::update(
+            user_totp::table
+                .find(user_id)
+                .filter(user_totp::last_used_step.is_null().or(user_totp::last_used_step.lt(step)))
+        )
+            .set(user_totp::last_used_step.eq(step))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(updated > 0)
+    }
+
+    async fn delete_totp(&self, user_id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::delete(user_recovery_codes::table.filter(user_recovery_codes::user_id.eq(user_id)))
+                .execute(conn)?;
+            diesel::delete(user_totp::table.find(user_id))
+                .execute(conn)?;
+            Ok(())
+        })
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn consume_recovery_code(&self, user_id: i32, code_hash: String) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let updated = diesel::update(
+            user_recovery_codes::table
+                .filter(user_recovery_codes::user_id.eq(user_id))
+                .filter(user_recovery_codes::code_hash.eq(code_hash))
+                .filter(user_recovery_codes::used_at.is_null())
+        )
+            .set(user_recovery_codes::used_at.eq(diesel::dsl::now.nullable()))
+            .execute(conn)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(updated > 0)
+    }
+}
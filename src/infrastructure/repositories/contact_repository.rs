@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::contact::{ContactRequest, ContactRequestStatus, ContactSettings};
+use crate::domain::repositories::contact_repository::ContactRepository;
+use crate::schema::{accounts, contact_requests};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = contact_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ContactRequestRecord {
+    pub id: i32,
+    pub requester_id: i32,
+    pub addressee_id: i32,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ContactRequestRecord> for ContactRequest {
+    fn from(record: ContactRequestRecord) -> Self {
+        ContactRequest {
+            id: record.id,
+            requester_id: record.requester_id,
+            addressee_id: record.addressee_id,
+            status: ContactRequestStatus::parse(&record.status).unwrap_or(ContactRequestStatus::Pending),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ContactRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ContactRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ContactRepository for ContactRepositoryImpl {
+    async fn create_request(&self, requester_id: i32, addressee_id: i32) -> Result<ContactRequest, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(contact_requests::table)
+            .values((
+                contact_requests::requester_id.eq(requester_id),
+                contact_requests::addressee_id.eq(addressee_id),
+                contact_requests::status.eq(ContactRequestStatus::Pending.as_str()),
+                contact_requests::created_at.eq(diesel::dsl::now),
+                contact_requests::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(ContactRequestRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn find_request(&self, request_id: i32) -> Result<Option<ContactRequest>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = contact_requests::table
+            .find(request_id)
+            .select(ContactRequestRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(ContactRequest::from))
+    }
+
+    async fn find_request_between(&self, user1_id: i32, user2_id: i32) -> Result<Option<ContactRequest>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = contact_requests::table
+            .filter(
+                contact_requests::requester_id.eq(user1_id)
+                    .and(contact_requests::addressee_id.eq(user2_id))
+                    .or(contact_requests::requester_id.eq(user2_id)
+                        .and(contact_requests::addressee_id.eq(user1_id)))
+            )
+            .select(ContactRequestRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(ContactRequest::from))
+    }
+
+    async fn find_pending_for_user(&self, user_id: i32) -> Result<Vec<ContactRequest>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = contact_requests::table
+            .filter(contact_requests::addressee_id.eq(user_id))
+            .filter(contact_requests::status.eq(ContactRequestStatus::Pending.as_str()))
+            .order(contact_requests::created_at.desc())
+            .select(ContactRequestRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(ContactRequest::from).collect())
+    }
+
+    async fn update_status(&self, request_id: i32, status: ContactRequestStatus) -> Result<ContactRequest, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::update(contact_requests::table.find(request_id))
+            .set((
+                contact_requests::status.eq(status.as_str()),
+                contact_requests::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(ContactRequestRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_request(&self, request_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::delete(contact_requests::table.find(request_id))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn find_contacts(&self, user_id: i32) -> Result<Vec<(i32, NaiveDateTime)>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = contact_requests::table
+            .filter(contact_requests::status.eq(ContactRequestStatus::Accepted.as_str()))
+            .filter(
+                contact_requests::requester_id.eq(user_id)
+                    .or(contact_requests::addressee_id.eq(user_id))
+            )
+            .order(contact_requests::updated_at.desc())
+            .select(ContactRequestRecord::as_select())
+            .load(conn)?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let contact_id = if record.requester_id == user_id {
+                    record.addressee_id
+                } else {
+                    record.requester_id
+                };
+                (contact_id, record.updated_at)
+            })
+            .collect())
+    }
+
+    async fn are_contacts(&self, user1_id: i32, user2_id: i32) -> Result<bool, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let count = contact_requests::table
+            .filter(contact_requests::status.eq(ContactRequestStatus::Accepted.as_str()))
+            .filter(
+                contact_requests::requester_id.eq(user1_id)
+                    .and(contact_requests::addressee_id.eq(user2_id))
+                    .or(contact_requests::requester_id.eq(user2_id)
+                        .and(contact_requests::addressee_id.eq(user1_id)))
+            )
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(count > 0)
+    }
+
+    async fn get_settings(&self, user_id: i32) -> Result<ContactSettings, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        // Users created without an account row fall back to the column default
+        let allow_messages_from_strangers = accounts::table
+            .filter(accounts::user_id.eq(user_id))
+            .select(accounts::allow_messages_from_strangers)
+            .first::<bool>(conn)
+            .optional()?
+            .unwrap_or(true);
+
+        Ok(ContactSettings {
+            user_id,
+            allow_messages_from_strangers,
+        })
+    }
+
+    async fn update_settings(&self, user_id: i32, allow_messages_from_strangers: bool) -> Result<ContactSettings, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let allow_messages_from_strangers = diesel::update(accounts::table.filter(accounts::user_id.eq(user_id)))
+            .set((
+                accounts::allow_messages_from_strangers.eq(allow_messages_from_strangers),
+                accounts::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(accounts::allow_messages_from_strangers)
+            .get_result::<bool>(conn)?;
+
+        Ok(ContactSettings {
+            user_id,
+            allow_messages_from_strangers,
+        })
+    }
+}
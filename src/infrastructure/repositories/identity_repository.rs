@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::auth::RegisterUserDto;
+use crate::domain::entities::identity::{ExternalIdentity, OidcLoginState};
+use crate::domain::entities::user::User;
+use crate::domain::repositories::identity_repository::IdentityRepository;
+use crate::infrastructure::repositories::auth_repository::{hash_password, insert_user_with_account, map_registration_error, password_salt};
+use crate::schema::{users, user_identities, oidc_login_states};
+
+#[derive(Clone)]
+pub struct IdentityRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+    salt: [u8; 16],
+}
+
+impl IdentityRepositoryImpl {
+    /// Takes `SECRET_KEY` so placeholder passwords of new accounts are hashed like any other.
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>, secret_key: String) -> Self {
+        Self { pool, salt: password_salt(&secret_key) }
+    }
+}
+
+#[async_trait]
+impl IdentityRepository for IdentityRepositoryImpl {
+    async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let user_result = user_identities::table
+            .inner_join(users::table)
+            .filter(user_identities::provider.eq(provider))
+            .filter(user_identities::subject.eq(subject))
+            .select((users::id, users::username, users::email, users::password, users::email_verified_at))
+            .first::<(i32, String, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()?;
+
+        Ok(user_result.map(|user| User {
+            id: user.0,
+            username: user.1,
+            email: user.2,
+            password: user.3,
+            email_verified_at: user.4,
+        }))
+    }
+
+    async fn link_identity(&self, user_id: i32, identity: &ExternalIdentity) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(user_identities::table)
+            .values((
+                user_identities::user_id.eq(user_id),
+                user_identities::provider.eq(&identity.provider),
+                user_identities::subject.eq(&identity.subject),
+                user_identities::email.eq(&identity.email),
+                user_identities::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn register_with_identity(&self, register_dto: RegisterUserDto, identity: &ExternalIdentity, pending_approval: bool) -> Result<User, Box<dyn std::error::Error + Send + Sync>> {
+        let hashed_password = hash_password(&register_dto.password, self.salt)?;
+        let verified_at = identity.email_verified.then(|| chrono::Utc::now().naive_utc());
+
+        let conn = &mut self.pool.get()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let user = insert_user_with_account(conn, &register_dto, &hashed_password, verified_at, pending_approval)?;
+
+            diesel::insert_into(user_identities::table)
+                .values((
+                    user_identities::user_id.eq(user.id),
+                    user_identities::provider.eq(&identity.provider),
+                    user_identities::subject.eq(&identity.subject),
+                    user_identities::email.eq(&identity.email),
+                    user_identities::created_at.eq(diesel::dsl::now),
+                ))
+                .execute(conn)?;
+
+            Ok(user)
+        })
+            .map_err(map_registration_error)
+    }
+
+    async fn username_exists(&self, name: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let count = users::table
+            .filter(users::username.eq(name))
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(count > 0)
+    }
+
+    async fn save_login_state(&self, state: OidcLoginState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(oidc_login_states::table)
+            .values((
+                oidc_login_states::provider.eq(state.provider),
+                oidc_login_states::state_hash.eq(state.state_hash),
+                oidc_login_states::code_verifier.eq(state.code_verifier),
+                oidc_login_states::expires_at.eq(state.expires_at),
+                oidc_login_states::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn consume_login_state(&self, provider: &str, state_hash: String) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let code_verifier = diesel::delete(
+            oidc_login_states::table
+                .filter(oidc_login_states::provider.eq(provider))
+                .filter(oidc_login_states::state_hash.eq(state_hash))
+                .filter(oidc_login_states::expires_at.gt(diesel::dsl::now))
+        )
+            .returning(oidc_login_states::code_verifier)
+            .get_result::<String>(conn)
+            .optional()?;
+
+        Ok(code_verifier)
+    }
+}
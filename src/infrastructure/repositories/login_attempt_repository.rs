@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::login_attempt::{FailureStats, LoginLockout, NewLoginLockout};
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::schema::{login_attempts, login_lockouts};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = login_lockouts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginLockoutRecord {
+    pub id: i32,
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+    pub failed_attempts: i32,
+    pub locked_until: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<LoginLockoutRecord> for LoginLockout {
+    fn from(record: LoginLockoutRecord) -> Self {
+        LoginLockout {
+            id: record.id,
+            username: record.username,
+            ip_address: record.ip_address,
+            failed_attempts: record.failed_attempts,
+            locked_until: record.locked_until,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LoginAttemptRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl LoginAttemptRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AttemptKey<'a> {
+    Username(&'a str),
+    IpAddress(&'a str),
+}
+
+/// Counts failures after the most recent success, looking no further back than `since`.
+fn failure_stats(conn: &mut PgConnection, key: AttemptKey<'_>, since: NaiveDateTime) -> QueryResult<FailureStats> {
+    let attempts_for_key = || {
+        let query = login_attempts::table.into_boxed();
+        match key {
+            AttemptKey::Username(username) => query.filter(login_attempts::username.eq(username)),
+            AttemptKey::IpAddress(ip_address) => query.filter(login_attempts::ip_address.eq(ip_address)),
+        }
+    };
+
+    let last_success = attempts_for_key()
+        .filter(login_attempts::succeeded.eq(true))
+        .filter(login_attempts::created_at.ge(since))
+        .select(diesel::dsl::max(login_attempts::created_at))
+        .first::<Option<NaiveDateTime>>(conn)?;
+
+    let window_start = last_success.map_or(since, |success| success.max(since));
+
+    let (failed_attempts, last_failed_at) = attempts_for_key()
+        .filter(login_attempts::succeeded.eq(false))
+        .filter(login_attempts::created_at.gt(window_start))
+        .select((diesel::dsl::count_star(), diesel::dsl::max(login_attempts::created_at)))
+        .first::<(i64, Option<NaiveDateTime>)>(conn)?;
+
+    Ok(FailureStats { failed_attempts, last_failed_at })
+}
+
+#[async_trait]
+impl LoginAttemptRepository for LoginAttemptRepositoryImpl {
+    async fn record_attempt(&self, username: &str, ip_address: Option<&str>, succeeded: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(login_attempts::table)
+            .values((
+                login_attempts::username.eq(username),
+                login_attempts::ip_address.eq(ip_address),
+                login_attempts::succeeded.eq(succeeded),
+                login_attempts::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn failures_for_username(&self, username: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+        Ok(failure_stats(conn, AttemptKey::Username(username), since)?)
+    }
+
+    async fn failures_for_ip(&self, ip_address: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+        Ok(failure_stats(conn, AttemptKey::IpAddress(ip_address), since)?)
+    }
+
+    async fn record_lockout(&self, lockout: NewLoginLockout) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(login_lockouts::table)
+            .values((
+                login_lockouts::username.eq(lockout.username),
+                login_lockouts::ip_address.eq(lockout.ip_address),
+                login_lockouts::failed_attempts.eq(lockout.failed_attempts),
+                login_lockouts::locked_until.eq(lockout.locked_until),
+                login_lockouts::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn find_lockouts(&self, limit: i64) -> Result<Vec<LoginLockout>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = login_lockouts::table
+            .order(login_lockouts::created_at.desc())
+            .limit(limit)
+            .select(LoginLockoutRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(LoginLockout::from).collect())
+    }
+}
@@ -2,6 +2,7 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::{PgConnection, RunQueryDsl};
 use diesel::prelude::*;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use crate::domain::entities::message::DatabaseMessage;
 use crate::domain::repositories::message_repository::MessageRepository;
 use crate::schema::messages;
@@ -32,6 +33,9 @@ impl MessageRepository for MessageRepositoryImpl {
                     messages::content.eq(message.content),
                     messages::is_read.eq(message.is_read),
                     messages::created_at.eq(message.created_at),
+                    messages::deliver_at.eq(message.deliver_at),
+                    messages::expires_at.eq(message.expires_at),
+                    messages::is_delivered.eq(message.is_delivered),
                 ))
                 .get_result::<DatabaseMessage>(&mut conn)
         }).await
@@ -53,6 +57,8 @@ impl MessageRepository for MessageRepositoryImpl {
                         .or(messages::sender_id.eq(user2_id)
                             .and(messages::receiver_id.eq(user1_id)))
                 )
+                .filter(messages::is_hidden.eq(false))
+                .filter(messages::is_delivered.eq(true))
                 .order(messages::created_at.asc())
                 .load::<DatabaseMessage>(&mut conn)
         }).await
@@ -80,4 +86,86 @@ impl MessageRepository for MessageRepositoryImpl {
 
         Ok(())
     }
+
+    async fn get_scheduled_messages(&self, sender_id: i32) -> Result<Vec<DatabaseMessage>, String> {
+        let mut conn = self.pool.get()
+            .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+
+        let result = tokio::task::spawn_blocking(move || {
+            messages::table
+                .filter(messages::sender_id.eq(sender_id))
+                .filter(messages::is_delivered.eq(false))
+                .order(messages::deliver_at.asc())
+                .load::<DatabaseMessage>(&mut conn)
+        }).await
+            .map_err(|e| format!("Task failed: {}", e))?
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(result)
+    }
+
+    async fn find_due_messages(&self, now: NaiveDateTime) -> Result<Vec<DatabaseMessage>, String> {
+        let mut conn = self.pool.get()
+            .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+
+        let result = tokio::task::spawn_blocking(move || {
+            messages::table
+                .filter(messages::is_delivered.eq(false))
+                .filter(messages::deliver_at.le(now))
+                .order(messages::deliver_at.asc())
+                .load::<DatabaseMessage>(&mut conn)
+        }).await
+            .map_err(|e| format!("Task failed: {}", e))?
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(result)
+    }
+
+    async fn mark_as_delivered(&self, message_id: i32) -> Result<bool, String> {
+        let mut conn = self.pool.get()
+            .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+
+        // Only the tick that flips the flag gets to push the message
+        let result = tokio::task::spawn_blocking(move || {
+            diesel::update(
+                messages::table
+                    .find(message_id)
+                    .filter(messages::is_delivered.eq(false))
+            )
+                .set(messages::is_delivered.eq(true))
+                .execute(&mut conn)
+        }).await
+            .map_err(|e| format!("Task failed: {}", e))?
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(result == 1)
+    }
+
+    async fn delete_message(&self, message_id: i32) -> Result<(), String> {
+        let mut conn = self.pool.get()
+            .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+
+        tokio::task::spawn_blocking(move || {
+            diesel::delete(messages::table.find(message_id))
+                .execute(&mut conn)
+        }).await
+            .map_err(|e| format!("Task failed: {}", e))?
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: NaiveDateTime) -> Result<usize, String> {
+        let mut conn = self.pool.get()
+            .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+
+        let result = tokio::task::spawn_blocking(move || {
+            diesel::delete(messages::table.filter(messages::expires_at.le(now)))
+                .execute(&mut conn)
+        }).await
+            .map_err(|e| format!("Task failed: {}", e))?
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(result)
+    }
 }
\ No newline at end of file
@@ -1,5 +1,17 @@
 pub mod user_repository;
 pub(crate) mod auth_repository;
+pub mod identity_repository;
+pub mod passkey_repository;
+pub mod registration_repository;
+pub mod audit_repository;
 pub mod account_repository;
 pub mod message_repository;
-pub mod avatar_repository;
\ No newline at end of file
+pub mod avatar_repository;
+pub mod contact_repository;
+pub mod moderation_repository;
+pub mod role_repository;
+pub mod announcement_repository;
+pub mod notification_repository;
+pub mod login_attempt_repository;
+pub mod oauth_repository;
+pub mod api_key_repository;
\ No newline at end of file
@@ -0,0 +1,342 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::PgConnection;
+
+use crate::domain::entities::message::DatabaseMessage;
+use crate::domain::entities::moderation::{
+    CreateReportDto, MessageReport, ModerationAction, ModerationActionType, NewModerationAction,
+    ReportReason, ReportStatus, ReportedMessage, UserSuspension,
+};
+use crate::domain::repositories::moderation_repository::ModerationRepository;
+use crate::infrastructure::repositories::api_key_repository::revoke_user_api_keys;
+use crate::infrastructure::repositories::auth_repository::revoke_user_sessions;
+use crate::schema::{message_reports, messages, moderation_actions, user_suspensions};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = message_reports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MessageReportRecord {
+    pub id: i32,
+    pub message_id: i32,
+    pub reporter_id: i32,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<MessageReportRecord> for MessageReport {
+    fn from(record: MessageReportRecord) -> Self {
+        MessageReport {
+            id: record.id,
+            message_id: record.message_id,
+            reporter_id: record.reporter_id,
+            reason: ReportReason::parse(&record.reason).unwrap_or(ReportReason::Other),
+            details: record.details,
+            status: ReportStatus::parse(&record.status).unwrap_or(ReportStatus::Open),
+            resolved_by: record.resolved_by,
+            resolved_at: record.resolved_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = user_suspensions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserSuspensionRecord {
+    pub id: i32,
+    pub user_id: i32,
+    pub suspended_by: Option<i32>,
+    pub reason: Option<String>,
+    pub suspended_until: Option<NaiveDateTime>,
+    pub lifted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<UserSuspensionRecord> for UserSuspension {
+    fn from(record: UserSuspensionRecord) -> Self {
+        UserSuspension {
+            id: record.id,
+            user_id: record.user_id,
+            suspended_by: record.suspended_by,
+            reason: record.reason,
+            suspended_until: record.suspended_until,
+            lifted_at: record.lifted_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = moderation_actions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ModerationActionRecord {
+    pub id: i32,
+    pub moderator_id: Option<i32>,
+    pub action: String,
+    pub target_user_id: Option<i32>,
+    pub target_message_id: Option<i32>,
+    pub report_id: Option<i32>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<ModerationActionRecord> for ModerationAction {
+    type Error = String;
+
+    fn try_from(record: ModerationActionRecord) -> Result<Self, Self::Error> {
+        let action = ModerationActionType::parse(&record.action)
+            .ok_or_else(|| format!("Unknown moderation action: {}", record.action))?;
+
+        Ok(ModerationAction {
+            id: record.id,
+            moderator_id: record.moderator_id,
+            action,
+            target_user_id: record.target_user_id,
+            target_message_id: record.target_message_id,
+            report_id: record.report_id,
+            note: record.note,
+            created_at: record.created_at,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ModerationRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ModerationRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn insert_action(conn: &mut PgConnection, action: NewModerationAction) -> Result<ModerationAction, Box<dyn std::error::Error>> {
+    let record = diesel::insert_into(moderation_actions::table)
+        .values((
+            moderation_actions::moderator_id.eq(Some(action.moderator_id)),
+            moderation_actions::action.eq(action.action.as_str()),
+            moderation_actions::target_user_id.eq(action.target_user_id),
+            moderation_actions::target_message_id.eq(action.target_message_id),
+            moderation_actions::report_id.eq(action.report_id),
+            moderation_actions::note.eq(action.note),
+            moderation_actions::created_at.eq(diesel::dsl::now),
+        ))
+        .returning(ModerationActionRecord::as_select())
+        .get_result(conn)?;
+
+    Ok(ModerationAction::try_from(record)?)
+}
+
+#[async_trait]
+impl ModerationRepository for ModerationRepositoryImpl {
+    async fn find_message(&self, message_id: i32) -> Result<Option<DatabaseMessage>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let message = messages::table
+            .find(message_id)
+            .first::<DatabaseMessage>(conn)
+            .optional()?;
+
+        Ok(message)
+    }
+
+    async fn set_message_hidden(&self, message_id: i32, hidden: bool, action: NewModerationAction) -> Result<ModerationAction, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        conn.transaction::<_, Box<dyn std::error::Error>, _>(|conn| {
+            let updated = diesel::update(messages::table.find(message_id))
+                .set(messages::is_hidden.eq(hidden))
+                .execute(conn)?;
+
+            if updated == 0 {
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Message not found"
+                )));
+            }
+
+            if hidden {
+                diesel::update(
+                    message_reports::table
+                        .filter(message_reports::message_id.eq(message_id))
+                        .filter(message_reports::status.eq(ReportStatus::Open.as_str()))
+                )
+                    .set((
+                        message_reports::status.eq(ReportStatus::Actioned.as_str()),
+                        message_reports::resolved_by.eq(Some(action.moderator_id)),
+                        message_reports::resolved_at.eq(diesel::dsl::now.nullable()),
+                    ))
+                    .execute(conn)?;
+            }
+
+            insert_action(conn, action)
+        })
+    }
+
+    async fn create_report(&self, reporter_id: i32, report: CreateReportDto) -> Result<MessageReport, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(message_reports::table)
+            .values((
+                message_reports::message_id.eq(report.message_id),
+                message_reports::reporter_id.eq(reporter_id),
+                message_reports::reason.eq(report.reason.as_str()),
+                message_reports::details.eq(report.details),
+                message_reports::status.eq(ReportStatus::Open.as_str()),
+                message_reports::created_at.eq(diesel::dsl::now),
+            ))
+            .returning(MessageReportRecord::as_select())
+            .get_result(conn)
+            .map_err(|e| match e {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    Box::new(std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        "You have already reported this message"
+                    )) as Box<dyn std::error::Error>
+                }
+                e => Box::new(e),
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn find_report(&self, report_id: i32) -> Result<Option<MessageReport>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = message_reports::table
+            .find(report_id)
+            .select(MessageReportRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(MessageReport::from))
+    }
+
+    async fn find_reports(&self, status: Option<ReportStatus>) -> Result<Vec<ReportedMessage>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let mut query = message_reports::table
+            .inner_join(messages::table)
+            .select((MessageReportRecord::as_select(), messages::all_columns))
+            .order(message_reports::created_at.asc())
+            .into_boxed();
+
+        if let Some(status) = status {
+            query = query.filter(message_reports::status.eq(status.as_str()));
+        }
+
+        let records = query.load::<(MessageReportRecord, DatabaseMessage)>(conn)?;
+
+        Ok(records
+            .into_iter()
+            .map(|(report, message)| ReportedMessage {
+                report: report.into(),
+                message,
+            })
+            .collect())
+    }
+
+    async fn resolve_report(&self, report_id: i32, status: ReportStatus, action: NewModerationAction) -> Result<MessageReport, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        conn.transaction::<_, Box<dyn std::error::Error>, _>(|conn| {
+            let record = diesel::update(message_reports::table.find(report_id))
+                .set((
+                    message_reports::status.eq(status.as_str()),
+                    message_reports::resolved_by.eq(Some(action.moderator_id)),
+                    message_reports::resolved_at.eq(diesel::dsl::now.nullable()),
+                ))
+                .returning(MessageReportRecord::as_select())
+                .get_result(conn)?;
+
+            insert_action(conn, action)?;
+
+            Ok(record.into())
+        })
+    }
+
+    async fn suspend_user(&self, user_id: i32, reason: Option<String>, suspended_until: Option<NaiveDateTime>, action: NewModerationAction) -> Result<UserSuspension, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        conn.transaction::<_, Box<dyn std::error::Error>, _>(|conn| {
+            let record = diesel::insert_into(user_suspensions::table)
+                .values((
+                    user_suspensions::user_id.eq(user_id),
+                    user_suspensions::suspended_by.eq(Some(action.moderator_id)),
+                    user_suspensions::reason.eq(reason),
+                    user_suspensions::suspended_until.eq(suspended_until),
+                    user_suspensions::created_at.eq(diesel::dsl::now),
+                ))
+                .returning(UserSuspensionRecord::as_select())
+                .get_result(conn)?;
+
+            // Signed-in sessions and API keys would otherwise outlive the suspension's start
+            revoke_user_sessions(conn, user_id)?;
+            revoke_user_api_keys(conn, user_id)?;
+            insert_action(conn, action)?;
+
+            Ok(record.into())
+        })
+    }
+
+    async fn lift_suspensions(&self, user_id: i32, action: NewModerationAction) -> Result<Option<ModerationAction>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        conn.transaction::<_, Box<dyn std::error::Error>, _>(|conn| {
+            let updated = diesel::update(
+                user_suspensions::table
+                    .filter(user_suspensions::user_id.eq(user_id))
+                    .filter(user_suspensions::lifted_at.is_null())
+            )
+                .set(user_suspensions::lifted_at.eq(diesel::dsl::now.nullable()))
+                .execute(conn)?;
+
+            if updated == 0 {
+                return Ok(None);
+            }
+
+            insert_action(conn, action).map(Some)
+        })
+    }
+
+    async fn find_active_suspension(&self, user_id: i32) -> Result<Option<UserSuspension>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = user_suspensions::table
+            .filter(user_suspensions::user_id.eq(user_id))
+            .filter(user_suspensions::lifted_at.is_null())
+            .filter(
+                user_suspensions::suspended_until.is_null()
+                    .or(user_suspensions::suspended_until.gt(diesel::dsl::now.nullable()))
+            )
+            .order(user_suspensions::created_at.desc())
+            .select(UserSuspensionRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(UserSuspension::from))
+    }
+
+    async fn find_actions(&self, limit: i64) -> Result<Vec<ModerationAction>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = moderation_actions::table
+            .order(moderation_actions::created_at.desc())
+            .limit(limit)
+            .select(ModerationActionRecord::as_select())
+            .load(conn)?;
+
+        records
+            .into_iter()
+            .map(|record| ModerationAction::try_from(record).map_err(|e| e.into()))
+            .collect()
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+use diesel::upsert::DecoratableTarget;
+
+use crate::domain::entities::notification::{NotificationMode, NotificationPreference};
+use crate::domain::repositories::notification_repository::NotificationRepository;
+use crate::schema::notification_preferences;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = notification_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationPreferenceRecord {
+    pub id: i32,
+    pub user_id: i32,
+    pub counterpart_user_id: Option<i32>,
+    pub group_id: Option<i32>,
+    pub mode: String,
+    pub muted_until: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<NotificationPreferenceRecord> for NotificationPreference {
+    fn from(record: NotificationPreferenceRecord) -> Self {
+        NotificationPreference {
+            id: record.id,
+            user_id: record.user_id,
+            counterpart_user_id: record.counterpart_user_id,
+            group_id: record.group_id,
+            mode: NotificationMode::parse(&record.mode).unwrap_or(NotificationMode::All),
+            muted_until: record.muted_until,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NotificationRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl NotificationRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationRepositoryImpl {
+    async fn find_all_for_user(&self, user_id: i32) -> Result<Vec<NotificationPreference>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = notification_preferences::table
+            .filter(notification_preferences::user_id.eq(user_id))
+            .order(notification_preferences::updated_at.desc())
+            .select(NotificationPreferenceRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(NotificationPreference::from).collect())
+    }
+
+    async fn find_for_counterpart(&self, user_id: i32, counterpart_user_id: i32) -> Result<Option<NotificationPreference>, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = notification_preferences::table
+            .filter(notification_preferences::user_id.eq(user_id))
+            .filter(notification_preferences::counterpart_user_id.eq(counterpart_user_id))
+            .select(NotificationPreferenceRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(NotificationPreference::from))
+    }
+
+    async fn upsert_for_counterpart(&self, user_id: i32, counterpart_user_id: i32, mode: NotificationMode, muted_until: Option<NaiveDateTime>) -> Result<NotificationPreference, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        // A single statement, so two requests setting the same preference cannot both insert.
+        // The unique index is partial, so the conflict target repeats its predicate
+        let record = diesel::insert_into(notification_preferences::table)
+            .values((
+                notification_preferences::user_id.eq(user_id),
+                notification_preferences::counterpart_user_id.eq(Some(counterpart_user_id)),
+                notification_preferences::mode.eq(mode.as_str()),
+                notification_preferences::muted_until.eq(muted_until),
+                notification_preferences::created_at.eq(diesel::dsl::now),
+                notification_preferences::updated_at.eq(diesel::dsl::now),
+            ))
+            .on_conflict((notification_preferences::user_id, notification_preferences::counterpart_user_id))
+            .filter_target(notification_preferences::counterpart_user_id.is_not_null())
+            .do_update()
+            .set((
+                notification_preferences::mode.eq(mode.as_str()),
+                notification_preferences::muted_until.eq(muted_until),
+                notification_preferences::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(NotificationPreferenceRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_for_counterpart(&self, user_id: i32, counterpart_user_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::delete(
+            notification_preferences::table
+                .filter(notification_preferences::user_id.eq(user_id))
+                .filter(notification_preferences::counterpart_user_id.eq(counterpart_user_id))
+        )
+            .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::oauth::{AuthorizationCode, NewAuthorizationCode, NewOAuthClient, OAuthClient, UserProfile};
+use crate::domain::repositories::oauth_repository::OAuthRepository;
+use crate::schema::{accounts, oauth_authorization_codes, oauth_clients, users};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = oauth_clients)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthClientRecord {
+    pub id: i32,
+    pub client_id: String,
+    pub client_secret_hash: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub scopes: Vec<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<OAuthClientRecord> for OAuthClient {
+    fn from(record: OAuthClientRecord) -> Self {
+        OAuthClient {
+            id: record.id,
+            client_id: record.client_id,
+            client_secret_hash: record.client_secret_hash,
+            name: record.name,
+            redirect_uris: record.redirect_uris,
+            grant_types: record.grant_types,
+            scopes: record.scopes,
+            created_by: record.created_by,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = oauth_authorization_codes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuthorizationCodeRecord {
+    pub client_id: String,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: String,
+    pub nonce: Option<String>,
+}
+
+impl From<AuthorizationCodeRecord> for AuthorizationCode {
+    fn from(record: AuthorizationCodeRecord) -> Self {
+        AuthorizationCode {
+            client_id: record.client_id,
+            user_id: record.user_id,
+            redirect_uri: record.redirect_uri,
+            scope: record.scope,
+            code_challenge: record.code_challenge,
+            nonce: record.nonce,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OAuthRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl OAuthRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for OAuthRepositoryImpl {
+    async fn create_client(&self, client: NewOAuthClient) -> Result<OAuthClient, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(oauth_clients::table)
+            .values((
+                oauth_clients::client_id.eq(client.client_id),
+                oauth_clients::client_secret_hash.eq(client.client_secret_hash),
+                oauth_clients::name.eq(client.name),
+                oauth_clients::redirect_uris.eq(client.redirect_uris),
+                oauth_clients::grant_types.eq(client.grant_types),
+                oauth_clients::scopes.eq(client.scopes),
+                oauth_clients::created_by.eq(Some(client.created_by)),
+            ))
+            .returning(OAuthClientRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn find_clients(&self) -> Result<Vec<OAuthClient>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = oauth_clients::table
+            .order(oauth_clients::created_at.desc())
+            .select(OAuthClientRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(OAuthClient::from).collect())
+    }
+
+    async fn find_client(&self, client_id: &str) -> Result<Option<OAuthClient>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = oauth_clients::table
+            .filter(oauth_clients::client_id.eq(client_id))
+            .select(OAuthClientRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(OAuthClient::from))
+    }
+
+    async fn delete_client(&self, client_id: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let deleted = diesel::delete(oauth_clients::table.filter(oauth_clients::client_id.eq(client_id)))
+            .execute(conn)?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn save_authorization_code(&self, code: NewAuthorizationCode) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(oauth_authorization_codes::table)
+            .values((
+                oauth_authorization_codes::code_hash.eq(code.code_hash),
+                oauth_authorization_codes::client_id.eq(code.client_id),
+                oauth_authorization_codes::user_id.eq(code.user_id),
+                oauth_authorization_codes::redirect_uri.eq(code.redirect_uri),
+                oauth_authorization_codes::scope.eq(code.scope),
+                oauth_authorization_codes::code_challenge.eq(code.code_challenge),
+                oauth_authorization_codes::nonce.eq(code.nonce),
+                oauth_authorization_codes::expires_at.eq(code.expires_at),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn consume_authorization_code(&self, code_hash: String) -> Result<Option<AuthorizationCode>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::delete(
+            oauth_authorization_codes::table
+                .filter(oauth_authorization_codes::code_hash.eq(code_hash))
+                .filter(oauth_authorization_codes::expires_at.gt(diesel::dsl::now))
+        )
+            .returning(AuthorizationCodeRecord::as_select())
+            .get_result(conn)
+            .optional()?;
+
+        Ok(record.map(AuthorizationCode::from))
+    }
+
+    async fn find_user_profile(&self, user_id: i32) -> Result<Option<UserProfile>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let row = users::table
+            .left_join(accounts::table)
+            .filter(users::id.eq(user_id))
+            .select((
+                users::username,
+                users::email,
+                users::email_verified_at,
+                accounts::first_name.nullable(),
+                accounts::middle_name.nullable(),
+                accounts::last_name.nullable(),
+                accounts::updated_at.nullable(),
+            ))
+            .first::<(
+                String,
+                String,
+                Option<NaiveDateTime>,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+                Option<NaiveDateTime>,
+            )>(conn)
+            .optional()?;
+
+        Ok(row.map(|(username, email, email_verified_at, first_name, middle_name, last_name, updated_at)| UserProfile {
+            user_id,
+            username,
+            email,
+            email_verified: email_verified_at.is_some(),
+            first_name,
+            middle_name,
+            last_name,
+            updated_at,
+        }))
+    }
+}
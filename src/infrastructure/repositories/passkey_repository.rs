@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::passkey::{Ceremony, NewPasskey, NewWebAuthnChallenge, Passkey};
+use crate::domain::repositories::passkey_repository::PasskeyRepository;
+use crate::schema::{webauthn_credentials, webauthn_challenges};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = webauthn_credentials)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PasskeyRecord {
+    pub id: i32,
+    pub user_id: i32,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    pub sign_count: i64,
+    pub name: String,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<PasskeyRecord> for Passkey {
+    fn from(record: PasskeyRecord) -> Self {
+        Passkey {
+            id: record.id,
+            user_id: record.user_id,
+            credential_id: record.credential_id,
+            public_key: record.public_key,
+            algorithm: record.algorithm,
+            sign_count: record.sign_count,
+            name: record.name,
+            last_used_at: record.last_used_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PasskeyRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl PasskeyRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for PasskeyRepositoryImpl {
+    async fn create_passkey(&self, passkey: NewPasskey) -> Result<Passkey, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(webauthn_credentials::table)
+            .values((
+                webauthn_credentials::user_id.eq(passkey.user_id),
+                webauthn_credentials::credential_id.eq(passkey.credential_id),
+                webauthn_credentials::public_key.eq(passkey.public_key),
+                webauthn_credentials::algorithm.eq(passkey.algorithm),
+                webauthn_credentials::sign_count.eq(passkey.sign_count),
+                webauthn_credentials::name.eq(passkey.name),
+            ))
+            .returning(PasskeyRecord::as_select())
+            .get_result(conn)
+            .map_err(|e| match e {
+                diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                    Box::new(std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        "This passkey is already registered",
+                    )) as Box<dyn std::error::Error + Send + Sync>
+                }
+                e => Box::new(e),
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn find_passkeys(&self, user_id: i32) -> Result<Vec<Passkey>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = webauthn_credentials::table
+            .filter(webauthn_credentials::user_id.eq(user_id))
+            .order(webauthn_credentials::created_at.desc())
+            .select(PasskeyRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(Passkey::from).collect())
+    }
+
+    async fn find_passkey_by_credential_id(&self, credential_id: &str) -> Result<Option<Passkey>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = webauthn_credentials::table
+            .filter(webauthn_credentials::credential_id.eq(credential_id))
+            .select(PasskeyRecord::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(record.map(Passkey::from))
+    }
+
+    async fn record_passkey_use(&self, passkey_id: i32, sign_count: i64) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::update(webauthn_credentials::table.find(passkey_id))
+            .set((
+                webauthn_credentials::sign_count.eq(sign_count),
+                webauthn_credentials::last_used_at.eq(diesel::dsl::now.nullable()),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn delete_passkey(&self, user_id: i32, passkey_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let deleted = diesel::delete(
+            webauthn_credentials::table
+                .find(passkey_id)
+                .filter(webauthn_credentials::user_id.eq(user_id))
+        )
+            .execute(conn)?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn save_challenge(&self, challenge: NewWebAuthnChallenge) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        diesel::insert_into(webauthn_challenges::table)
+            .values((
+                webauthn_challenges::user_id.eq(challenge.user_id),
+                webauthn_challenges::ceremony.eq(challenge.ceremony.as_str()),
+                webauthn_challenges::challenge_hash.eq(challenge.challenge_hash),
+                webauthn_challenges::expires_at.eq(challenge.expires_at),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    async fn consume_challenge(&self, ceremony: Ceremony, challenge_hash: String) -> Result<Option<Option<i32>>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let user_id = diesel::delete(
+            webauthn_challenges::table
+                .filter(webauthn_challenges::ceremony.eq(ceremony.as_str()))
+                .filter(webauthn_challenges::challenge_hash.eq(challenge_hash))
+                .filter(webauthn_challenges::expires_at.gt(diesel::dsl::now))
+        )
+            .returning(webauthn_challenges::user_id)
+            .get_result::<Option<i32>>(conn)
+            .optional()?;
+
+        Ok(user_id)
+    }
+}
@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::entities::registration::{Invite, NewInvite, PendingUser};
+use crate::domain::repositories::registration_repository::RegistrationRepository;
+use crate::schema::{users, invites};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = invites)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InviteRecord {
+    pub id: i32,
+    pub created_by: Option<i32>,
+    pub note: Option<String>,
+    pub max_uses: i32,
+    pub use_count: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<InviteRecord> for Invite {
+    fn from(record: InviteRecord) -> Self {
+        Invite {
+            id: record.id,
+            created_by: record.created_by,
+            note: record.note,
+            max_uses: record.max_uses,
+            use_count: record.use_count,
+            expires_at: record.expires_at,
+            revoked_at: record.revoked_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RegistrationRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl RegistrationRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Uses up one use of an unrevoked, unexpired invite. The conditions sit in the UPDATE
+/// itself so concurrent sign-ups cannot use an invite more often than allowed.
+pub(crate) fn redeem_invite(conn: &mut PgConnection, code_hash: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let redeemed = diesel::update(
+        invites::table
+            .filter(invites::code_hash.eq(code_hash))
+            .filter(invites::revoked_at.is_null())
+            .filter(invites::use_count.lt(invites::max_uses))
+            .filter(invites::expires_at.is_null().or(invites::expires_at.gt(diesel::dsl::now.nullable())))
+    )
+        .set(invites::use_count.eq(invites::use_count + 1))
+        .execute(conn)?;
+
+    if redeemed == 0 {
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid or expired invite code"
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl RegistrationRepository for RegistrationRepositoryImpl {
+    async fn create_invite(&self, invite: NewInvite) -> Result<Invite, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let record = diesel::insert_into(invites::table)
+            .values((
+                invites::code_hash.eq(invite.code_hash),
+                invites::created_by.eq(invite.created_by),
+                invites::note.eq(invite.note),
+                invites::max_uses.eq(invite.max_uses),
+                invites::expires_at.eq(invite.expires_at),
+                invites::created_at.eq(diesel::dsl::now),
+            ))
+            .returning(InviteRecord::as_select())
+            .get_result(conn)?;
+
+        Ok(record.into())
+    }
+
+    async fn find_invites(&self) -> Result<Vec<Invite>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let records = invites::table
+            .order(invites::created_at.desc())
+            .select(InviteRecord::as_select())
+            .load(conn)?;
+
+        Ok(records.into_iter().map(Invite::from).collect())
+    }
+
+    async fn revoke_invite(&self, invite_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let revoked = diesel::update(invites::table.filter(invites::id.eq(invite_id)))
+            .filter(invites::revoked_at.is_null())
+            .set(invites::revoked_at.eq(diesel::dsl::now.nullable()))
+            .execute(conn)?;
+
+        Ok(revoked > 0)
+    }
+
+    async fn find_pending_users(&self) -> Result<Vec<PendingUser>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let pending = users::table
+            .filter(users::pending_approval.eq(true))
+            .order(users::id.asc())
+            .select((users::id, users::username, users::email, users::email_verified_at))
+            .load::<(i32, String, String, Option<NaiveDateTime>)>(conn)?;
+
+        Ok(pending
+            .into_iter()
+            .map(|(id, username, email, email_verified_at)| PendingUser { id, username, email, email_verified_at })
+            .collect())
+    }
+
+    async fn approve_user(&self, user_id: i32) -> Result<Option<PendingUser>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let approved = diesel::update(users::table.filter(users::id.eq(user_id)))
+            .filter(users::pending_approval.eq(true))
+            .set(users::pending_approval.eq(false))
+            .returning((users::id, users::username, users::email, users::email_verified_at))
+            .get_result::<(i32, String, String, Option<NaiveDateTime>)>(conn)
+            .optional()?;
+
+        Ok(approved.map(|(id, username, email, email_verified_at)| PendingUser { id, username, email, email_verified_at }))
+    }
+
+    async fn reject_user(&self, user_id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = &mut self.pool.get()?;
+
+        let deleted = diesel::delete(users::table.filter(users::id.eq(user_id)))
+            .filter(users::pending_approval.eq(true))
+            .execute(conn)?;
+
+        Ok(deleted > 0)
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::schema::{roles, user_roles};
+
+#[derive(Clone)]
+pub struct RoleRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl RoleRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for RoleRepositoryImpl {
+    async fn has_any_role(&self, user_id: i32, role_names: &[&str]) -> Result<bool, Box<dyn std::error::Error>> {
+        let conn = &mut self.pool.get()?;
+
+        let count = user_roles::table
+            .inner_join(roles::table)
+            .filter(user_roles::user_id.eq(user_id))
+            .filter(roles::name.eq_any(role_names.to_vec()))
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(count > 0)
+    }
+}
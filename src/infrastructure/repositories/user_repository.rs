@@ -4,10 +4,13 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::PgConnection;
 
 use crate::domain::{
-    entities::user::{User, CreateUserDto},
+    entities::user::User,
     repositories::user_repository::UserRepository,
 };
 use crate::domain::entities::user::UpdateUserDto;
+use crate::infrastructure::repositories::auth_repository::{map_registration_error, EMAIL_ALREADY_REGISTERED};
+
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
 
 #[derive(Clone)]
 pub struct UserRepositoryImpl {
@@ -26,9 +29,18 @@ table! {
         username -> Varchar,
         email -> Varchar,
         password -> Varchar,
+        email_verified_at -> Nullable<Timestamp>,
     }
 }
 
+#[derive(AsChangeset)]
+#[diesel(table_name = users)]
+struct UserChangeset<'a> {
+    username: Option<&'a str>,
+    email: Option<&'a str>,
+    email_verified_at: Option<Option<chrono::NaiveDateTime>>,
+}
+
 #[async_trait]
 impl UserRepository for UserRepositoryImpl {
     async fn find_by_id(&self, user_id: i32) -> Result<User, Box<dyn std::error::Error>> {
@@ -37,34 +49,14 @@ impl UserRepository for UserRepositoryImpl {
         let conn = &mut self.pool.get()?;
         let user = users
             .filter(id.eq(user_id))
-            .first::<(i32, String, String, String)>(conn)?;
+            .first::<(i32, String, String, String, Option<chrono::NaiveDateTime>)>(conn)?;
 
         Ok(User {
             id: user.0,
             username: user.1,
             email: user.2,
             password: user.3,
-        })
-    }
-
-    async fn create(&self, user_dto: CreateUserDto) -> Result<User, Box<dyn std::error::Error>> {
-        use self::users::dsl::*;
-
-        let conn = &mut self.pool.get()?;
-        let new_user = diesel::insert_into(users)
-            .values((
-                username.eq(&user_dto.username),
-                email.eq(&user_dto.email),
-                password.eq(&user_dto.password),
-            ))
-            .returning((id, username, email, password))
-            .get_result::<(i32, String, String, String)>(conn)?;
-
-        Ok(User {
-            id: new_user.0,
-            username: new_user.1,
-            email: new_user.2,
-            password: new_user.3,
+            email_verified_at: user.4,
         })
     }
 
@@ -73,16 +65,17 @@ impl UserRepository for UserRepositoryImpl {
 
         let conn = &mut self.pool.get()?;
         let results = users
-            .select((id, username, email, password))
-            .load::<(i32, String, String, String)>(conn)?;
+            .select((id, username, email, password, email_verified_at))
+            .load::<(i32, String, String, String, Option<chrono::NaiveDateTime>)>(conn)?;
 
         Ok(results
             .into_iter()
-            .map(|(user_id, user_username, user_email, user_password)| User {
+            .map(|(user_id, user_username, user_email, user_password, user_email_verified_at)| User {
                 id: user_id,
                 username: user_username,
                 email: user_email,
                 password: user_password,
+                email_verified_at: user_email_verified_at,
             })
             .collect())
     }
@@ -90,22 +83,52 @@ impl UserRepository for UserRepositoryImpl {
     async fn update(&self, user_id: i32, user_dto: UpdateUserDto) -> Result<User, Box<dyn std::error::Error>> {
         use self::users::dsl::*;
 
+        let current = self.find_by_id(user_id).await?;
+        if user_dto.username.is_none() && user_dto.email.is_none() {
+            return Ok(current);
+        }
+
+        // A new address has to be verified again; a change of case is not a new address
+        let email_changed = user_dto.email.as_ref()
+            .is_some_and(|new_email| !new_email.eq_ignore_ascii_case(&current.email));
+
         let conn = &mut self.pool.get()?;
+
+        if let Some(new_email) = user_dto.email.as_ref().filter(|_| email_changed) {
+            let email_taken = users
+                .filter(lower(email).eq(new_email.to_lowercase()))
+                .filter(id.ne(user_id))
+                .count()
+                .get_result::<i64>(conn)?;
+
+            if email_taken > 0 {
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    EMAIL_ALREADY_REGISTERED
+                )));
+            }
+        }
+
+        let changeset = UserChangeset {
+            username: user_dto.username.as_deref(),
+            email: user_dto.email.as_deref(),
+            email_verified_at: email_changed.then_some(None),
+        };
+
+        // The unique indexes still catch a username taken or an address claimed concurrently
         let updated_user = diesel::update(users)
             .filter(id.eq(user_id))
-            .set((
-                username.eq(&user_dto.username),
-                email.eq(&user_dto.email),
-                password.eq(&user_dto.password),
-            ))
-            .returning((id, username, email, password))
-            .get_result::<(i32, String, String, String)>(conn)?;
+            .set(&changeset)
+            .returning((id, username, email, password, email_verified_at))
+            .get_result::<(i32, String, String, String, Option<chrono::NaiveDateTime>)>(conn)
+            .map_err(|e| map_registration_error(e) as Box<dyn std::error::Error>)?;
 
         Ok(User {
             id: updated_user.0,
             username: updated_user.1,
             email: updated_user.2,
             password: updated_user.3,
+            email_verified_at: updated_user.4,
         })
     }
 
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error};
+use crate::application::use_cases::message_use_cases::{DeliverDueMessagesUseCase, PurgeExpiredMessagesUseCase};
+use crate::domain::repositories::contact_repository::ContactRepository;
+use crate::domain::repositories::message_repository::MessageRepository;
+use crate::domain::repositories::moderation_repository::ModerationRepository;
+use crate::infrastructure::websocket::realtime_message_manager::RealtimeMessageManager;
+
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+
+pub struct MessageScheduler<T: MessageRepository, C: ContactRepository, M: ModerationRepository> {
+    deliver_due_messages_use_case: DeliverDueMessagesUseCase<T, C, M>,
+    purge_expired_messages_use_case: PurgeExpiredMessagesUseCase<T>,
+    realtime_message_manager: RealtimeMessageManager,
+    interval: Duration,
+}
+
+impl<T, C, M> MessageScheduler<T, C, M>
+where
+    T: MessageRepository + Clone + 'static,
+    C: ContactRepository + 'static,
+    M: ModerationRepository + 'static,
+{
+    pub fn new(
+        message_repository: T,
+        contact_repository: Arc<C>,
+        moderation_repository: Arc<M>,
+        realtime_message_manager: RealtimeMessageManager,
+    ) -> Self {
+        let interval_secs = std::env::var("MESSAGE_SCHEDULER_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_INTERVAL_SECS);
+
+        Self {
+            deliver_due_messages_use_case: DeliverDueMessagesUseCase::new(
+                message_repository.clone(),
+                contact_repository,
+                moderation_repository,
+            ),
+            purge_expired_messages_use_case: PurgeExpiredMessagesUseCase::new(message_repository),
+            realtime_message_manager,
+            interval: Duration::from_secs(interval_secs),
+        }
+    }
+
+    /// Runs the scheduler on the current actix runtime until the server shuts down.
+    pub fn start(self) {
+        actix_web::rt::spawn(async move {
+            let mut ticker = actix_web::rt::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+                self.tick().await;
+            }
+        });
+    }
+
+    async fn tick(&self) {
+        let now = chrono::Utc::now().naive_utc();
+
+        match self.deliver_due_messages_use_case.execute(now).await {
+            Ok(messages) => {
+                for message in messages {
+                    // Offline recipients pick the message up from get_messages instead
+                    if let Err(e) = self.realtime_message_manager
+                        .send_message(message.sender_id, message.receiver_id, message.content)
+                        .await
+                    {
+                        debug!("Scheduled message {} not pushed: {}", message.id, e);
+                    }
+                }
+            }
+            Err(e) => error!("Failed to deliver scheduled messages: {}", e),
+        }
+
+        match self.purge_expired_messages_use_case.execute(now).await {
+            Ok(0) => {}
+            Ok(purged) => debug!("Purged {} expired messages", purged),
+            Err(e) => error!("Failed to purge expired messages: {}", e),
+        }
+    }
+}
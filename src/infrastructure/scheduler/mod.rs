@@ -0,0 +1 @@
+pub mod message_scheduler;
\ No newline at end of file
@@ -1,24 +1,31 @@
 use std::sync::Arc;
+use tracing::warn;
 use crate::infrastructure::websocket::user_status_manager::UserStatusManager;
 use crate::domain::entities::message::WebSocketMessage;
+use crate::domain::entities::notification::{NotificationHint, NotificationMode};
+use crate::domain::repositories::notification_repository::NotificationRepository;
 
 #[derive(Clone)]
 pub struct RealtimeMessageManager {
     user_status_manager: Arc<UserStatusManager>,
+    notification_repository: Arc<dyn NotificationRepository>,
 }
 
 impl RealtimeMessageManager {
-    pub fn new(user_status_manager: Arc<UserStatusManager>) -> Self {
+    pub fn new(user_status_manager: Arc<UserStatusManager>, notification_repository: Arc<dyn NotificationRepository>) -> Self {
         Self {
-            user_status_manager
+            user_status_manager,
+            notification_repository,
         }
     }
 
-    pub async fn send_message(&self, _from_user_id: i32, to_user_id: i32, content: String) -> Result<(), String> {
+    pub async fn send_message(&self, from_user_id: i32, to_user_id: i32, content: String) -> Result<(), String> {
         if let Some(addr) = self.user_status_manager.get_connection(to_user_id).await {
             let message = WebSocketMessage::Chat {
                 to_user_id,
                 content,
+                from_user_id: Some(from_user_id),
+                notification: Some(self.notification_hint(to_user_id, from_user_id).await),
             };
             addr.try_send(message)
                 .map_err(|e| format!("Failed to send message: {}", e))
@@ -27,14 +34,42 @@ impl RealtimeMessageManager {
         }
     }
 
-    pub async fn broadcast_to_all(&self, message: WebSocketMessage) -> Result<(), String> {
+    /// Sends `message` to every connected user and returns the ids it reached.
+    /// A failing connection is logged and skipped so one bad client cannot block the rest.
+    pub async fn broadcast_to_all(&self, message: WebSocketMessage) -> Vec<i32> {
         let connections = self.user_status_manager.get_online_status().await;
+        let mut delivered = Vec::with_capacity(connections.len());
         for (user_id, _) in connections {
             if let Some(addr) = self.user_status_manager.get_connection(user_id).await {
-                addr.try_send(message.clone())
-                    .map_err(|e| format!("Failed to broadcast to user {}: {}", user_id, e))?;
+                match addr.try_send(message.clone()) {
+                    Ok(()) => delivered.push(user_id),
+                    Err(e) => warn!("Failed to broadcast to user {}: {}", user_id, e),
+                }
             }
         }
-        Ok(())
+        delivered
+    }
+
+    /// Resolves the recipient's preference for this conversation, falling back to
+    /// `All` when none is stored or it cannot be loaded.
+    pub(crate) async fn notification_hint(&self, user_id: i32, counterpart_user_id: i32) -> NotificationHint {
+        let preference = self.notification_repository
+            .find_for_counterpart(user_id, counterpart_user_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load notification preference for user {}: {}", user_id, e);
+                None
+            });
+
+        match preference {
+            Some(preference) => NotificationHint {
+                mode: preference.effective_mode(chrono::Utc::now().naive_utc()),
+                muted_until: preference.muted_until,
+            },
+            None => NotificationHint {
+                mode: NotificationMode::All,
+                muted_until: None,
+            },
+        }
     }
 }
\ No newline at end of file
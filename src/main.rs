@@ -7,27 +7,64 @@ mod schema;
 use actix_files::Files;
 use tracing::info;
 use actix_cors::Cors;
-use actix_web::{http::header, web, App, HttpServer};
+use actix_web::{http::header, middleware::from_fn, web, App, HttpServer};
 use actix_web_httpauth::middleware::HttpAuthentication;
-use tracing::{Level};
-use tracing_subscriber::FmtSubscriber;
 use std::path::PathBuf;
 use std::sync::Arc;
 use infrastructure::{
-    config::database,
+    config::{database, signing_key::signing_keys_from_env, password_policy::password_policy_from_env},
+    logging,
+    mail::mailer_from_env,
+    oidc::providers_from_env,
     repositories::{
         user_repository::UserRepositoryImpl,
         auth_repository::AuthRepositoryImpl,
+        identity_repository::IdentityRepositoryImpl,
+        passkey_repository::PasskeyRepositoryImpl,
+        registration_repository::RegistrationRepositoryImpl,
+        audit_repository::AuditRepositoryImpl,
         account_repository::AccountRepositoryImpl,
         avatar_repository::AvatarRepositoryImpl,
+        announcement_repository::AnnouncementRepositoryImpl,
+        contact_repository::ContactRepositoryImpl,
+        moderation_repository::ModerationRepositoryImpl,
+        role_repository::RoleRepositoryImpl,
+        notification_repository::NotificationRepositoryImpl,
+        login_attempt_repository::LoginAttemptRepositoryImpl,
+        oauth_repository::OAuthRepositoryImpl,
+        api_key_repository::ApiKeyRepositoryImpl,
     },
 };
 
 use crate::application::use_cases::{
     account_use_cases::{GetAccountUseCase, UpdateAccountUseCase, GetAllAccountsUseCase},
     avatar_use_cases::UploadAvatarUseCase,
+    announcement_use_cases::{PublishAnnouncementUseCase, ListAnnouncementsUseCase, GetPendingAnnouncementsUseCase},
+    notification_use_cases::NotificationPreferencesUseCase,
+    contact_use_cases::{
+        SendContactRequestUseCase, RespondContactRequestUseCase, ListContactRequestsUseCase,
+        ListContactsUseCase, RemoveContactUseCase, ContactSettingsUseCase, CheckMessagePermissionUseCase,
+    },
+    moderation_use_cases::{
+        ReportMessageUseCase, ListReportsUseCase, DismissReportUseCase, HideMessageUseCase,
+        SuspendUserUseCase, ListModerationActionsUseCase,
+    },
+    mfa_use_cases::{CompleteMfaLoginUseCase, TotpEnrollmentUseCase},
+    login_attempt_use_cases::ListLockoutsUseCase,
+    audit_use_cases::ListAuditEventsUseCase,
+    oidc_use_cases::{StartOidcLoginUseCase, CompleteOidcLoginUseCase, OidcLoginSettings},
+    api_key_use_cases::{ManageApiKeysUseCase, AuthenticateApiKeyUseCase},
+    session_use_cases::ManageSessionsUseCase,
+    passkey_use_cases::{ManagePasskeysUseCase, PasskeyLoginUseCase},
+    magic_link_use_cases::{RequestMagicLinkUseCase, VerifyMagicLinkUseCase},
+    registration_use_cases::{ManageInvitesUseCase, ReviewRegistrationsUseCase},
+    impersonation_use_cases::ImpersonateUserUseCase,
+    oauth_use_cases::{ManageOAuthClientsUseCase, AuthorizeUseCase, IssueOAuthTokenUseCase, OpenIdDiscoveryUseCase},
     user_use_cases::{GetUserByIdUseCase, CreateUserUseCase, ListUsersUseCase, DeleteUserUseCase, UpdateUserUseCase},
-    auth_use_cases::{LoginUseCase, RegisterUseCase},
+    auth_use_cases::{
+        LoginUseCase, RegisterUseCase, SendVerificationEmailUseCase, VerifyEmailUseCase,
+        RequestPasswordResetUseCase, ConfirmPasswordResetUseCase, ChangePasswordUseCase, ValidateSessionUseCase,
+    },
 };
 
 use presentation::{
@@ -36,12 +73,34 @@ use presentation::{
         auth_handlers::{AuthHandlers, configure as auth_configure},
         account_handlers::{AccountHandlers, configure as account_configure},
         avatar_handlers::{AvatarHandlers, configure as avatar_configure},
+        announcement_handlers::{AnnouncementHandlers, configure as announcement_configure},
+        notification_handlers::{NotificationHandlers, configure as notification_configure},
+        contact_handlers::{ContactHandlers, configure as contact_configure},
+        moderation_handlers::{ModerationHandlers, configure as moderation_configure},
+        security_handlers::{SecurityHandlers, configure as security_configure},
+        oidc_handlers::{OidcHandlers, configure as oidc_configure},
+        oauth_handlers::{OAuthHandlers, configure as oauth_configure, configure_well_known},
+        api_key_handlers::{ApiKeyHandlers, configure as api_key_configure},
+        session_handlers::{SessionHandlers, configure as session_configure},
+        passkey_handlers::{PasskeyHandlers, configure as passkey_configure},
+        magic_link_handlers::{MagicLinkHandlers, configure as magic_link_configure},
+        mfa_handlers::{MfaHandlers, configure as mfa_configure},
+        registration_handlers::{RegistrationHandlers, configure as registration_configure},
+        impersonation_handlers::{ImpersonationHandlers, configure as impersonation_configure},
+        client_info::TrustedProxies,
     },
     middleware::auth::validator,
+    middleware::request_id::{request_id, REQUEST_ID_HEADER},
 };
 use presentation::handlers::ws_handlers;
-use crate::application::use_cases::message_use_cases::{GetMessagesUseCase, SendMessageUseCase};
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::webauthn::RelyingParty;
+use crate::domain::entities::registration::RegistrationMode;
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::application::use_cases::message_use_cases::{GetMessagesUseCase, GetScheduledMessagesUseCase, SendMessageUseCase};
 use crate::infrastructure::repositories::message_repository::MessageRepositoryImpl;
+use crate::infrastructure::scheduler::message_scheduler::MessageScheduler;
 use crate::infrastructure::websocket::realtime_message_manager::RealtimeMessageManager;
 use crate::infrastructure::websocket::user_status_manager::UserStatusManager;
 use crate::presentation::handlers::message_handlers;
@@ -49,13 +108,12 @@ use crate::presentation::handlers::message_handlers::MessageHandlers;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let _subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::DEBUG)
-        .init();
+    // Before logging starts, so RUST_LOG and LOG_FORMAT can come from .env
+    dotenvy::dotenv().ok();
+    logging::init_from_env();
 
     info!("Starting application...");
 
-    dotenvy::dotenv().ok();
     let pool = database::establish_connection();
 
     info!("Database connection established");
@@ -63,8 +121,12 @@ async fn main() -> std::io::Result<()> {
     let secret_key = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set");
 
     // Initialize WebSocket managers
+    let notification_repository = Arc::new(NotificationRepositoryImpl::new(pool.clone()));
     let user_status_manager = Arc::new(UserStatusManager::new());
-    let realtime_message_manager = RealtimeMessageManager::new(user_status_manager.clone());
+    let realtime_message_manager = RealtimeMessageManager::new(
+        user_status_manager.clone(),
+        notification_repository.clone(),
+    );
 
     // Create uploads directory if it doesn't exist
     let upload_dir = PathBuf::from("uploads");
@@ -73,23 +135,220 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize repositories with properly cloned pools
     let user_repository = UserRepositoryImpl::new(pool.clone());
-    let auth_repository = AuthRepositoryImpl::new(pool.clone(), secret_key);
+    let auth_repository = AuthRepositoryImpl::new(pool.clone(), secret_key.clone());
+    let identity_repository = IdentityRepositoryImpl::new(pool.clone(), secret_key.clone());
+    let passkey_repository = PasskeyRepositoryImpl::new(pool.clone());
+    let registration_repository = RegistrationRepositoryImpl::new(pool.clone());
     let account_repository = AccountRepositoryImpl::new(pool.clone());
     let avatar_repository = AvatarRepositoryImpl::new(pool.clone());
     let message_repository = MessageRepositoryImpl::new(pool.clone());
+    let contact_repository = Arc::new(ContactRepositoryImpl::new(pool.clone()));
+    let moderation_repository = Arc::new(ModerationRepositoryImpl::new(pool.clone()));
+    let role_repository = Arc::new(RoleRepositoryImpl::new(pool.clone()));
+    let announcement_repository = Arc::new(AnnouncementRepositoryImpl::new(pool.clone()));
+    let login_attempt_repository = Arc::new(LoginAttemptRepositoryImpl::new(pool.clone()));
+    let oauth_repository = Arc::new(OAuthRepositoryImpl::new(pool.clone()));
+    let api_key_repository = Arc::new(ApiKeyRepositoryImpl::new(pool.clone()));
+    // Sign-ins are still audited by AuthRepositoryImpl, inside the transaction that creates their session
+    let audit_repository: Arc<dyn AuditRepository> = Arc::new(AuditRepositoryImpl::new(pool.clone()));
+
+    let password_policy = password_policy_from_env();
 
     // Initialize use cases
     let get_user_use_case = GetUserByIdUseCase::new(user_repository.clone());
-    let create_user_use_case = CreateUserUseCase::new(user_repository.clone());
+    let create_user_use_case = CreateUserUseCase::new(auth_repository.clone(), role_repository.clone(), password_policy.clone(), audit_repository.clone());
     let list_users_use_case = ListUsersUseCase::new(user_repository.clone());
-    let update_user_use_case = UpdateUserUseCase::new(user_repository.clone());
-    let delete_user_use_case = DeleteUserUseCase::new(user_repository);
+    let delete_user_use_case = DeleteUserUseCase::new(user_repository.clone(), role_repository.clone(), audit_repository.clone());
+
+    let send_message_use_case = SendMessageUseCase::new(
+        message_repository.clone(),
+        contact_repository.clone(),
+        moderation_repository.clone(),
+    );
+    let get_messages_use_case = GetMessagesUseCase::new(message_repository.clone());
+    let get_scheduled_messages_use_case = GetScheduledMessagesUseCase::new(message_repository.clone());
+
+    // Deliver scheduled messages and purge expired ones in the background
+    MessageScheduler::new(
+        message_repository,
+        contact_repository.clone(),
+        moderation_repository.clone(),
+        realtime_message_manager.clone(),
+    ).start();
+
+    // Access tokens, ID tokens and OAuth tokens are all signed with these keys
+    let signing_keys = signing_keys_from_env(&secret_key);
+
+    // Email verification
+    let token_service = Arc::new(TokenService::new(&secret_key));
+    let mailer = mailer_from_env();
+    let app_base_url = std::env::var("APP_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let require_email_verification = std::env::var("REQUIRE_EMAIL_VERIFICATION")
+        .map(|value| value == "true" || value == "1")
+        .unwrap_or(false);
+    // open, invite_only, approval_required or closed
+    let registration_mode = std::env::var("REGISTRATION_MODE")
+        .map(|value| value.parse::<RegistrationMode>().expect("Invalid REGISTRATION_MODE"))
+        .unwrap_or(RegistrationMode::Open);
+    info!("Registration mode: {}", registration_mode.as_str());
+
+    // Our own API only accepts tokens naming this service as issuer and audience
+    let access_token_service = Arc::new(AccessTokenService::new(
+        signing_keys.clone(),
+        std::env::var("JWT_ISSUER").unwrap_or_else(|_| app_base_url.clone()),
+        std::env::var("JWT_AUDIENCE").unwrap_or_else(|_| "rust-clean-arch".to_string()),
+        std::env::var("JWT_LEEWAY_SECONDS").ok().and_then(|value| value.parse().ok()).unwrap_or(60),
+    ));
+
+    let send_verification_email_use_case = Arc::new(SendVerificationEmailUseCase::new(
+        auth_repository.clone(),
+        token_service.clone(),
+        mailer.clone(),
+        app_base_url.clone(),
+    ));
+    let verify_email_use_case = VerifyEmailUseCase::new(auth_repository.clone(), token_service.clone());
+    let update_user_use_case = UpdateUserUseCase::new(
+        user_repository,
+        role_repository.clone(),
+        send_verification_email_use_case.clone(),
+        audit_repository.clone(),
+    );
+
+    // Password reset links point at the frontend, which posts the token back to the API
+    let password_reset_url = std::env::var("PASSWORD_RESET_URL")
+        .unwrap_or_else(|_| "http://localhost:3000/reset-password".to_string());
+    let request_password_reset_use_case = RequestPasswordResetUseCase::new(
+        auth_repository.clone(),
+        token_service.clone(),
+        mailer.clone(),
+        password_reset_url,
+    );
+    // Sign-in links, also opened through the frontend
+    let magic_link_url = std::env::var("MAGIC_LINK_URL")
+        .unwrap_or_else(|_| "http://localhost:3000/magic-link".to_string());
+    let request_magic_link_use_case = RequestMagicLinkUseCase::new(
+        auth_repository.clone(),
+        token_service.clone(),
+        mailer.clone(),
+        magic_link_url,
+    );
+    let verify_magic_link_use_case = VerifyMagicLinkUseCase::new(
+        auth_repository.clone(),
+        token_service.clone(),
+        access_token_service.clone(),
+    );
+    let confirm_password_reset_use_case = ConfirmPasswordResetUseCase::new(
+        auth_repository.clone(),
+        token_service.clone(),
+        password_policy.clone(),
+        audit_repository.clone(),
+    );
+    let change_password_use_case = ChangePasswordUseCase::new(auth_repository.clone(), password_policy.clone(), audit_repository.clone());
+    let validate_session_use_case = ValidateSessionUseCase::new(auth_repository.clone());
+    let manage_sessions_use_case = ManageSessionsUseCase::new(auth_repository.clone());
+    let login_use_case = LoginUseCase::new(
+        auth_repository.clone(),
+        login_attempt_repository.clone(),
+        token_service.clone(),
+        access_token_service.clone(),
+        audit_repository.clone(),
+        require_email_verification,
+    );
+    // Social login
+    let oidc_providers = Arc::new(providers_from_env());
+    let start_oidc_login_use_case = StartOidcLoginUseCase::new(
+        identity_repository.clone(),
+        oidc_providers.clone(),
+        token_service.clone(),
+        app_base_url.clone(),
+    );
+    let complete_oidc_login_use_case = CompleteOidcLoginUseCase::new(
+        auth_repository.clone(),
+        identity_repository,
+        oidc_providers,
+        token_service.clone(),
+        access_token_service.clone(),
+        OidcLoginSettings {
+            base_url: app_base_url.clone(),
+            require_email_verification,
+            registration_mode,
+        },
+    );
+
+    // Authorization server for our other services
+    let oauth_issuer = std::env::var("OAUTH_ISSUER").unwrap_or_else(|_| app_base_url.clone());
+    let oauth_consent_url = std::env::var("OAUTH_CONSENT_URL")
+        .unwrap_or_else(|_| "http://localhost:3000/oauth/consent".to_string());
+    let manage_oauth_clients_use_case = ManageOAuthClientsUseCase::new(
+        oauth_repository.clone(),
+        token_service.clone(),
+        role_repository.clone(),
+    );
+    let authorize_use_case = AuthorizeUseCase::new(oauth_repository.clone(), token_service.clone(), oauth_consent_url);
+    let issue_oauth_token_use_case = IssueOAuthTokenUseCase::new(
+        oauth_repository.clone(),
+        token_service.clone(),
+        signing_keys.clone(),
+        oauth_issuer.clone(),
+    );
+    let openid_discovery_use_case = OpenIdDiscoveryUseCase::new(signing_keys, oauth_issuer);
+
+    // Passkeys (WebAuthn); the origin is the web app's, like the CORS allow list below
+    let relying_party = Arc::new(RelyingParty {
+        id: std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string()),
+        name: std::env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "rust-clean-arch".to_string()),
+        origin: std::env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:3000".to_string()),
+    });
+    let manage_passkeys_use_case = ManagePasskeysUseCase::new(
+        auth_repository.clone(),
+        passkey_repository.clone(),
+        token_service.clone(),
+        relying_party.clone(),
+    );
+    let passkey_login_use_case = PasskeyLoginUseCase::new(
+        auth_repository.clone(),
+        passkey_repository,
+        token_service.clone(),
+        access_token_service.clone(),
+        relying_party,
+        require_email_verification,
+    );
+
+    // Personal API keys for scripts and integrations
+    let manage_api_keys_use_case = ManageApiKeysUseCase::new(api_key_repository.clone(), token_service.clone());
+    let authenticate_api_key_use_case = AuthenticateApiKeyUseCase::new(
+        api_key_repository,
+        auth_repository.clone(),
+        token_service.clone(),
+        access_token_service.clone(),
+    );
+
+    // Invites and approvals for the non-open registration modes
+    let manage_invites_use_case = ManageInvitesUseCase::new(registration_repository.clone(), token_service.clone(), role_repository.clone());
+    let review_registrations_use_case = ReviewRegistrationsUseCase::new(registration_repository, mailer.clone(), role_repository.clone());
+
+    // Short-lived tokens superusers use to act as another user
+    let impersonate_user_use_case = ImpersonateUserUseCase::new(auth_repository.clone(), access_token_service.clone(), role_repository.clone());
 
-    let send_message_use_case = SendMessageUseCase::new(message_repository.clone());
-    let get_messages_use_case = GetMessagesUseCase::new(message_repository);
+    let list_lockouts_use_case = ListLockoutsUseCase::new(login_attempt_repository.clone(), role_repository.clone());
+    let list_audit_events_use_case = ListAuditEventsUseCase::new(audit_repository.clone(), role_repository.clone());
 
-    let login_use_case = LoginUseCase::new(auth_repository.clone());
-    let register_use_case = RegisterUseCase::new(auth_repository);
+    // Two-factor authentication
+    let totp_issuer = std::env::var("TOTP_ISSUER").unwrap_or_else(|_| "rust-clean-arch".to_string());
+    let complete_mfa_login_use_case = CompleteMfaLoginUseCase::new(
+        auth_repository.clone(),
+        login_attempt_repository.clone(),
+        token_service.clone(),
+        access_token_service.clone(),
+    );
+    let totp_enrollment_use_case = TotpEnrollmentUseCase::new(auth_repository.clone(), token_service.clone(), totp_issuer, audit_repository.clone());
+    let register_use_case = RegisterUseCase::new(
+        auth_repository,
+        send_verification_email_use_case.clone(),
+        password_policy,
+        token_service.clone(),
+        registration_mode,
+    );
 
 
     let account_repository = Arc::new(AccountRepositoryImpl::new(pool.clone()));
@@ -99,6 +358,33 @@ async fn main() -> std::io::Result<()> {
     let update_account_use_case = UpdateAccountUseCase::new(account_repository.clone());
     let get_all_accounts_use_case = GetAllAccountsUseCase::new(account_repository.clone());
 
+    // Initialize contact use cases
+    let send_contact_request_use_case = SendContactRequestUseCase::new(contact_repository.clone());
+    let respond_contact_request_use_case = RespondContactRequestUseCase::new(contact_repository.clone());
+    let list_contact_requests_use_case = ListContactRequestsUseCase::new(contact_repository.clone());
+    let list_contacts_use_case = ListContactsUseCase::new(contact_repository.clone(), account_repository.clone());
+    let remove_contact_use_case = RemoveContactUseCase::new(contact_repository.clone());
+    let contact_settings_use_case = ContactSettingsUseCase::new(contact_repository.clone());
+    let message_permission_use_case = CheckMessagePermissionUseCase::new(
+        contact_repository.clone(),
+        moderation_repository.clone(),
+    );
+
+    // Initialize moderation use cases
+    let report_message_use_case = ReportMessageUseCase::new(moderation_repository.clone());
+    let list_reports_use_case = ListReportsUseCase::new(moderation_repository.clone(), role_repository.clone());
+    let dismiss_report_use_case = DismissReportUseCase::new(moderation_repository.clone(), role_repository.clone());
+    let hide_message_use_case = HideMessageUseCase::new(moderation_repository.clone(), role_repository.clone());
+    let suspend_user_use_case = SuspendUserUseCase::new(moderation_repository.clone(), role_repository.clone());
+    let list_moderation_actions_use_case = ListModerationActionsUseCase::new(moderation_repository.clone(), role_repository.clone());
+
+    // Initialize announcement use cases
+    let publish_announcement_use_case = PublishAnnouncementUseCase::new(announcement_repository.clone(), role_repository.clone());
+    let list_announcements_use_case = ListAnnouncementsUseCase::new(announcement_repository.clone());
+    let get_pending_announcements_use_case = GetPendingAnnouncementsUseCase::new(announcement_repository.clone());
+
+    let notification_preferences_use_case = NotificationPreferencesUseCase::new(notification_repository.clone());
+
     let upload_avatar_use_case = UploadAvatarUseCase::new(
         avatar_repository.clone(),
         account_repository.clone(),
@@ -117,6 +403,16 @@ async fn main() -> std::io::Result<()> {
     let auth_handlers = web::Data::new(AuthHandlers::new(
         login_use_case,
         register_use_case,
+        verify_email_use_case,
+        send_verification_email_use_case,
+        request_password_reset_use_case,
+        confirm_password_reset_use_case,
+        change_password_use_case,
+    ));
+
+    let mfa_handlers = web::Data::new(MfaHandlers::new(
+        complete_mfa_login_use_case,
+        totp_enrollment_use_case,
     ));
 
     let account_handlers = web::Data::new(AccountHandlers::new(
@@ -129,9 +425,83 @@ async fn main() -> std::io::Result<()> {
         upload_avatar_use_case,
     ));
 
+    let contact_handlers = web::Data::new(ContactHandlers::new(
+        send_contact_request_use_case,
+        respond_contact_request_use_case,
+        list_contact_requests_use_case,
+        list_contacts_use_case,
+        remove_contact_use_case,
+        contact_settings_use_case,
+    ));
+
+    let moderation_handlers = web::Data::new(ModerationHandlers::new(
+        report_message_use_case,
+        list_reports_use_case,
+        dismiss_report_use_case,
+        hide_message_use_case,
+        suspend_user_use_case,
+        list_moderation_actions_use_case,
+    ));
+
+    let announcement_handlers = web::Data::new(AnnouncementHandlers::new(
+        publish_announcement_use_case,
+        list_announcements_use_case,
+        get_pending_announcements_use_case,
+        realtime_message_manager.clone(),
+    ));
+
+    let oidc_handlers = web::Data::new(OidcHandlers::new(
+        start_oidc_login_use_case,
+        complete_oidc_login_use_case,
+    ));
+
+    let oauth_handlers = web::Data::new(OAuthHandlers::new(
+        manage_oauth_clients_use_case,
+        authorize_use_case,
+        issue_oauth_token_use_case,
+        openid_discovery_use_case,
+    ));
+
+    let api_key_handlers = web::Data::new(ApiKeyHandlers::new(
+        manage_api_keys_use_case,
+    ));
+
+    let magic_link_handlers = web::Data::new(MagicLinkHandlers::new(
+        request_magic_link_use_case,
+        verify_magic_link_use_case,
+    ));
+
+    let passkey_handlers = web::Data::new(PasskeyHandlers::new(
+        manage_passkeys_use_case,
+        passkey_login_use_case,
+    ));
+
+    let registration_handlers = web::Data::new(RegistrationHandlers::new(
+        manage_invites_use_case,
+        review_registrations_use_case,
+    ));
+
+    let impersonation_handlers = web::Data::new(ImpersonationHandlers::new(
+        impersonate_user_use_case,
+    ));
+
+    let session_handlers = web::Data::new(SessionHandlers::new(
+        manage_sessions_use_case,
+    ));
+
+    let security_handlers = web::Data::new(SecurityHandlers::new(
+        list_lockouts_use_case,
+        list_audit_events_use_case,
+    ));
+
+    let notification_handlers = web::Data::new(NotificationHandlers::new(
+        notification_preferences_use_case,
+    ));
+
     let message_handlers = web::Data::new(MessageHandlers::new(
         send_message_use_case,
         get_messages_use_case,
+        get_scheduled_messages_use_case,
         realtime_message_manager.clone(),
     ));
 
@@ -139,6 +509,12 @@ async fn main() -> std::io::Result<()> {
 
     let user_status_manager_data = web::Data::new(user_status_manager);
     let realtime_message_manager_data = web::Data::new(realtime_message_manager);
+    let message_permission_data = web::Data::new(message_permission_use_case);
+    let session_validation_data = web::Data::new(validate_session_use_case);
+    let access_token_data = web::Data::from(access_token_service);
+    let api_key_authentication_data = web::Data::new(authenticate_api_key_use_case);
+    // Needed when running behind a reverse proxy, see TrustedProxies
+    let trusted_proxies_data = web::Data::new(TrustedProxies::from_env());
 
     HttpServer::new(move || {
         let cors = Cors::default()
@@ -156,28 +532,66 @@ async fn main() -> std::io::Result<()> {
                 header::ACCEPT,
                 header::CONTENT_TYPE,
             ])
+            .expose_headers(vec![REQUEST_ID_HEADER])
             .supports_credentials()
             .max_age(3600);
 
         App::new()
             .wrap(cors)
+            .wrap(from_fn(request_id))
             .app_data(user_handlers.clone())
             .app_data(auth_handlers.clone())
             .app_data(account_handlers.clone())
             .app_data(avatar_handlers.clone())
+            .app_data(contact_handlers.clone())
+            .app_data(moderation_handlers.clone())
+            .app_data(announcement_handlers.clone())
+            .app_data(notification_handlers.clone())
+            .app_data(security_handlers.clone())
+            .app_data(oidc_handlers.clone())
+            .app_data(oauth_handlers.clone())
+            .app_data(api_key_handlers.clone())
+            .app_data(session_handlers.clone())
+            .app_data(registration_handlers.clone())
+            .app_data(impersonation_handlers.clone())
+            .app_data(passkey_handlers.clone())
+            .app_data(magic_link_handlers.clone())
+            .app_data(mfa_handlers.clone())
+            .app_data(message_handlers.clone())
             .app_data(user_status_manager_data.clone())
             .app_data(realtime_message_manager_data.clone())
+            .app_data(message_permission_data.clone())
+            .app_data(session_validation_data.clone())
+            .app_data(access_token_data.clone())
+            .app_data(api_key_authentication_data.clone())
+            .app_data(trusted_proxies_data.clone())
             .configure(ws_handlers::configure)
+            .configure(|cfg| configure_well_known(cfg, oauth_handlers.clone()))
             .service(Files::new("/uploads", "uploads").show_files_listing())
             .service(
                 web::scope("/api/v1")
+                    // Before /auth so the longer prefix gets the first chance to match
+                    .configure(|cfg| oidc_configure(cfg, oidc_handlers.clone()))
+                    .configure(|cfg| passkey_configure(cfg, passkey_handlers.clone()))
+                    .configure(|cfg| magic_link_configure(cfg, magic_link_handlers.clone()))
+                    .configure(|cfg| mfa_configure(cfg, mfa_handlers.clone()))
                     .configure(|cfg| auth_configure(cfg, auth_handlers.clone()))
+                    .configure(|cfg| oauth_configure(cfg, oauth_handlers.clone()))
                     .service(
                         web::scope("")
                             .wrap(auth.clone())
                             .configure(|cfg| user_configure(cfg, user_handlers.clone()))
                             .configure(|cfg| account_configure(cfg, account_handlers.clone()))
                             .configure(|cfg| avatar_configure(cfg, avatar_handlers.clone()))
+                            .configure(|cfg| contact_configure(cfg, contact_handlers.clone()))
+                            .configure(|cfg| moderation_configure(cfg, moderation_handlers.clone()))
+                            .configure(|cfg| announcement_configure(cfg, announcement_handlers.clone()))
+                            .configure(|cfg| notification_configure(cfg, notification_handlers.clone()))
+                            .configure(|cfg| security_configure(cfg, security_handlers.clone()))
+                            .configure(|cfg| api_key_configure(cfg, api_key_handlers.clone()))
+                            .configure(|cfg| session_configure(cfg, session_handlers.clone()))
+                            .configure(|cfg| registration_configure(cfg, registration_handlers.clone()))
+                            .configure(|cfg| impersonation_configure(cfg, impersonation_handlers.clone()))
                             .configure(|cfg| message_handlers::configure(cfg, message_handlers.clone()))
                     )
             )
@@ -2,6 +2,7 @@ use actix_web::{web, HttpResponse, Responder};
 use crate::domain::repositories::account_repository::AccountRepository;
 use crate::application::use_cases::account_use_cases::{GetAccountUseCase, UpdateAccountUseCase, GetAllAccountsUseCase};
 use crate::domain::entities::account::UpdateAccountDto;
+use crate::presentation::handlers::errors::error_response;
 
 pub struct AccountHandlers<T: AccountRepository> {
     get_account_use_case: GetAccountUseCase<T>,
@@ -45,10 +46,7 @@ impl<T: AccountRepository> AccountHandlers<T> {
     pub async fn update_account(&self, user_id: web::Path<i32>, account_dto: web::Json<UpdateAccountDto>) -> impl Responder {
         match self.update_account_use_case.execute(user_id.into_inner(), account_dto.into_inner()).await {
             Ok(account) => HttpResponse::Ok().json(account),
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to update account",
-                "message": e.to_string()
-            })),
+            Err(e) => error_response("Failed to update account", &*e),
         }
     }
 }
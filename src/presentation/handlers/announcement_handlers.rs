@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::error;
+use crate::application::use_cases::announcement_use_cases::{
+    PublishAnnouncementUseCase, ListAnnouncementsUseCase, GetPendingAnnouncementsUseCase,
+};
+use crate::domain::entities::announcement::{AnnouncementPublished, CreateAnnouncementDto};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::message::WebSocketMessage;
+use crate::domain::repositories::announcement_repository::AnnouncementRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::infrastructure::websocket::realtime_message_manager::RealtimeMessageManager;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct AnnouncementHandlers<A: AnnouncementRepository, R: RoleRepository> {
+    publish_announcement_use_case: PublishAnnouncementUseCase<A, R>,
+    list_announcements_use_case: ListAnnouncementsUseCase<A>,
+    get_pending_announcements_use_case: GetPendingAnnouncementsUseCase<A>,
+    realtime_message_manager: RealtimeMessageManager,
+}
+
+impl<A: AnnouncementRepository, R: RoleRepository> AnnouncementHandlers<A, R> {
+    pub fn new(
+        publish_announcement_use_case: PublishAnnouncementUseCase<A, R>,
+        list_announcements_use_case: ListAnnouncementsUseCase<A>,
+        get_pending_announcements_use_case: GetPendingAnnouncementsUseCase<A>,
+        realtime_message_manager: RealtimeMessageManager,
+    ) -> Self {
+        Self {
+            publish_announcement_use_case,
+            list_announcements_use_case,
+            get_pending_announcements_use_case,
+            realtime_message_manager,
+        }
+    }
+
+    pub async fn publish(&self, claims: Claims, announcement_dto: web::Json<CreateAnnouncementDto>) -> impl Responder {
+        let announcement = match self.publish_announcement_use_case.execute(claims.sub, announcement_dto.into_inner()).await {
+            Ok(announcement) => announcement,
+            Err(e) => return error_response("Failed to publish announcement", &*e),
+        };
+
+        let delivered = self.realtime_message_manager
+            .broadcast_to_all(WebSocketMessage::Announcement {
+                id: announcement.id,
+                content: announcement.content.clone(),
+                created_at: announcement.created_at,
+            })
+            .await;
+        let delivered_to = delivered.len();
+
+        // The announcement is already stored; a failed receipt only means it is replayed on fetch
+        if let Err(e) = self.publish_announcement_use_case.mark_delivered(announcement.id, delivered).await {
+            error!("Failed to record announcement {} receipts: {}", announcement.id, e);
+        }
+
+        HttpResponse::Created().json(AnnouncementPublished {
+            announcement,
+            delivered_to,
+        })
+    }
+
+    pub async fn list(&self) -> impl Responder {
+        match self.list_announcements_use_case.execute().await {
+            Ok(announcements) => HttpResponse::Ok().json(announcements),
+            Err(e) => error_response("Failed to get announcements", &*e),
+        }
+    }
+
+    pub async fn pending(&self, claims: Claims) -> impl Responder {
+        match self.get_pending_announcements_use_case.execute(claims.sub).await {
+            Ok(announcements) => HttpResponse::Ok().json(announcements),
+            Err(e) => error_response("Failed to get pending announcements", &*e),
+        }
+    }
+}
+
+pub fn configure<A: AnnouncementRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<AnnouncementHandlers<A, R>>,
+) {
+    cfg.service(
+        web::scope("/announcements")
+            .route("", web::get().to(move |handlers: web::Data<AnnouncementHandlers<A, R>>| async move {
+                handlers.list().await
+            }))
+            .route("", web::post().to(move |handlers: web::Data<AnnouncementHandlers<A, R>>, claims: Claims, announcement_dto: web::Json<CreateAnnouncementDto>| async move {
+                handlers.publish(claims, announcement_dto).await
+            }))
+            .route("/pending", web::get().to(move |handlers: web::Data<AnnouncementHandlers<A, R>>, claims: Claims| async move {
+                handlers.pending(claims).await
+            }))
+    );
+}
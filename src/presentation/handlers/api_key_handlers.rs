@@ -0,0 +1,78 @@
+use actix_web::{web, HttpResponse, Responder};
+use serde_json::json;
+use crate::application::use_cases::api_key_use_cases::ManageApiKeysUseCase;
+use crate::domain::entities::api_key::CreateApiKeyDto;
+use crate::domain::entities::auth::Claims;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+
+pub struct ApiKeyHandlers<A: ApiKeyRepository> {
+    manage_api_keys_use_case: ManageApiKeysUseCase<A>,
+}
+
+/// Keys are managed with a signed-in session, so a leaked key cannot mint more keys.
+fn requires_session(claims: &Claims) -> Option<HttpResponse> {
+    claims.scopes.as_ref().map(|_| HttpResponse::Forbidden().json(json!({
+        "error": "API keys cannot be managed with an API key"
+    })))
+}
+
+impl<A: ApiKeyRepository> ApiKeyHandlers<A> {
+    pub fn new(manage_api_keys_use_case: ManageApiKeysUseCase<A>) -> Self {
+        Self { manage_api_keys_use_case }
+    }
+
+    pub async fn create(&self, claims: Claims, api_key_dto: web::Json<CreateApiKeyDto>) -> HttpResponse {
+        if let Some(response) = requires_session(&claims) {
+            return response;
+        }
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.manage_api_keys_use_case.create(claims.sub, api_key_dto.into_inner()).await {
+            Ok(api_key) => HttpResponse::Created().json(api_key),
+            Err(e) => error_response("Failed to create API key", &*e),
+        }
+    }
+
+    pub async fn list(&self, claims: Claims) -> impl Responder {
+        match self.manage_api_keys_use_case.list(claims.sub).await {
+            Ok(api_keys) => HttpResponse::Ok().json(api_keys),
+            Err(e) => error_response("Failed to get API keys", &*e),
+        }
+    }
+
+    pub async fn revoke(&self, claims: Claims, key_id: web::Path<i32>) -> HttpResponse {
+        if let Some(response) = requires_session(&claims) {
+            return response;
+        }
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.manage_api_keys_use_case.revoke(claims.sub, key_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to revoke API key", &*e),
+        }
+    }
+}
+
+pub fn configure<A: ApiKeyRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<ApiKeyHandlers<A>>,
+) {
+    cfg.service(
+        web::scope("/api-keys")
+            .route("", web::get().to(move |handlers: web::Data<ApiKeyHandlers<A>>, claims: Claims| async move {
+                handlers.list(claims).await
+            }))
+            .route("", web::post().to(move |handlers: web::Data<ApiKeyHandlers<A>>, claims: Claims, api_key_dto: web::Json<CreateApiKeyDto>| async move {
+                handlers.create(claims, api_key_dto).await
+            }))
+            .route("/{id}", web::delete().to(move |handlers: web::Data<ApiKeyHandlers<A>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.revoke(claims, id).await
+            }))
+    );
+}
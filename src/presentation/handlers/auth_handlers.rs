@@ -1,59 +1,125 @@
-use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web_httpauth::middleware::HttpAuthentication;
 use serde_json::json;
-use crate::application::use_cases::auth_use_cases::{LoginUseCase, RegisterUseCase};
+use crate::application::use_cases::auth_use_cases::{
+    LoginUseCase, RegisterUseCase, SendVerificationEmailUseCase, VerifyEmailUseCase,
+    RequestPasswordResetUseCase, ConfirmPasswordResetUseCase, ChangePasswordUseCase,
+};
+use crate::application::use_cases::login_attempt_use_cases::LOGIN_LOCKED;
+use crate::domain::entities::registration::Registered;
 use crate::domain::repositories::auth_repository::AuthRepository;
-use crate::domain::entities::auth::{AuthUser, RegisterUserDto};
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::services::validation::ValidationErrors;
+use crate::domain::entities::auth::{
+    AuthUser, RegisterUserDto, ResendVerificationDto, VerifyEmailDto,
+    RequestPasswordResetDto, ConfirmPasswordResetDto, ChangePasswordDto, Claims,
+};
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+use crate::presentation::middleware::auth::validator;
 use tracing::debug;
 
-pub struct AuthHandlers<T: AuthRepository> {
-    login_use_case: LoginUseCase<T>,
+const INVALID_CREDENTIALS: &str = "invalid credentials";
+
+pub struct AuthHandlers<T: AuthRepository, L: LoginAttemptRepository> {
+    login_use_case: LoginUseCase<T, L>,
     register_use_case: RegisterUseCase<T>,
+    verify_email_use_case: VerifyEmailUseCase<T>,
+    send_verification_email_use_case: Arc<SendVerificationEmailUseCase<T>>,
+    request_password_reset_use_case: RequestPasswordResetUseCase<T>,
+    confirm_password_reset_use_case: ConfirmPasswordResetUseCase<T>,
+    change_password_use_case: ChangePasswordUseCase<T>,
 }
 
 #[allow(dead_code)]
-impl<T: AuthRepository> AuthHandlers<T> {
-    pub fn new(login_use_case: LoginUseCase<T>, register_use_case: RegisterUseCase<T>) -> Self {
+impl<T: AuthRepository, L: LoginAttemptRepository> AuthHandlers<T, L> {
+    pub fn new(
+        login_use_case: LoginUseCase<T, L>,
+        register_use_case: RegisterUseCase<T>,
+        verify_email_use_case: VerifyEmailUseCase<T>,
+        send_verification_email_use_case: Arc<SendVerificationEmailUseCase<T>>,
+        request_password_reset_use_case: RequestPasswordResetUseCase<T>,
+        confirm_password_reset_use_case: ConfirmPasswordResetUseCase<T>,
+        change_password_use_case: ChangePasswordUseCase<T>,
+    ) -> Self {
         Self {
             login_use_case,
             register_use_case,
+            verify_email_use_case,
+            send_verification_email_use_case,
+            request_password_reset_use_case,
+            confirm_password_reset_use_case,
+            change_password_use_case,
         }
     }
 
-    pub async fn login(&self, auth: web::Json<AuthUser>) -> impl Responder {
+    pub async fn login(&self, req: HttpRequest, auth: web::Json<AuthUser>) -> impl Responder {
         let username = auth.username.clone();
         debug!("Login attempt for user: {}", username);
 
-        match self.login_use_case.execute(auth.into_inner()).await {
+        match self.login_use_case.execute(auth.into_inner(), &client_info(&req)).await {
             Ok(token) => {
                 debug!("Login successful for user: {}", username);
                 HttpResponse::Ok().json(token)
             }
-            Err(e) => {
+            Err(e) if e.to_string() == LOGIN_LOCKED => {
+                debug!("Login rejected while locked out: {}", username);
+                HttpResponse::TooManyRequests().json(json!({
+                    "error": "Authentication failed",
+                    "message": LOGIN_LOCKED
+                }))
+            }
+            // Unknown users and wrong passwords are told apart by nothing, not even the message
+            Err(e) if e.downcast_ref::<std::io::Error>().is_some_and(|io_error| io_error.kind() == std::io::ErrorKind::InvalidInput) => {
                 debug!("Login failed: {}", e);
                 HttpResponse::Unauthorized().json(json!({
                     "error": "Authentication failed",
-                    "message": e.to_string()
+                    "message": INVALID_CREDENTIALS
                 }))
             }
+            Err(e) => error_response("Authentication failed", &*e),
         }
     }
 
     pub async fn register(&self, register_dto: web::Json<RegisterUserDto>) -> impl Responder {
         match self.register_use_case.execute(register_dto.into_inner()).await {
-            Ok(user) => {
+            Ok(Registered { user, pending_approval }) => {
+                let message = if pending_approval {
+                    "User registered successfully; an administrator has to approve the account before you can sign in"
+                } else {
+                    "User registered successfully"
+                };
                 HttpResponse::Created().json(json!({
                     "status": "success",
-                    "message": "User registered successfully",
+                    "message": message,
                     "data": {
                         "id": user.id,
                         "username": user.username,
-                        "email": user.email
+                        "email": user.email,
+                        "email_verified": user.email_verified_at.is_some(),
+                        "pending_approval": pending_approval
                     }
                 }))
             }
             Err(e) => {
+                if let Some(validation) = e.downcast_ref::<ValidationErrors>() {
+                    return HttpResponse::BadRequest().json(json!({
+                        "status": "error",
+                        "message": "Registration failed",
+                        "error": validation.to_string(),
+                        "errors": validation.errors
+                    }));
+                }
+
                 let error_message = e.to_string();
-                HttpResponse::BadRequest().json(json!({
+                let mut response = match e.downcast_ref::<std::io::Error>().map(|io_error| io_error.kind()) {
+                    Some(std::io::ErrorKind::AlreadyExists) => HttpResponse::Conflict(),
+                    Some(std::io::ErrorKind::PermissionDenied) => HttpResponse::Forbidden(),
+                    _ => HttpResponse::BadRequest(),
+                };
+                response.json(json!({
                     "status": "error",
                     "message": "Registration failed",
                     "error": error_message
@@ -61,24 +127,108 @@ impl<T: AuthRepository> AuthHandlers<T> {
             }
         }
     }
+
+    pub async fn verify_email(&self, query: web::Query<VerifyEmailDto>) -> impl Responder {
+        match self.verify_email_use_case.execute(&query.token).await {
+            Ok(()) => HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "Email address verified"
+            })),
+            Err(e) => error_response("Email verification failed", &*e),
+        }
+    }
+
+    pub async fn resend_verification(&self, resend_dto: web::Json<ResendVerificationDto>) -> impl Responder {
+        match self.send_verification_email_use_case.resend(&resend_dto.email).await {
+            Ok(()) => HttpResponse::Accepted().json(json!({
+                "status": "success",
+                "message": "If the address belongs to an unverified account, a new link has been sent"
+            })),
+            Err(e) => error_response("Failed to resend verification email", &*e),
+        }
+    }
+
+    pub async fn request_password_reset(&self, reset_dto: web::Json<RequestPasswordResetDto>) -> impl Responder {
+        match self.request_password_reset_use_case.execute(&reset_dto.email).await {
+            Ok(()) => HttpResponse::Accepted().json(json!({
+                "status": "success",
+                "message": "If the address belongs to an account, a reset link has been sent"
+            })),
+            Err(e) => error_response("Failed to request password reset", &*e),
+        }
+    }
+
+    pub async fn confirm_password_reset(&self, req: HttpRequest, reset_dto: web::Json<ConfirmPasswordResetDto>) -> impl Responder {
+        match self.confirm_password_reset_use_case.execute(reset_dto.into_inner(), &client_info(&req)).await {
+            Ok(()) => HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "Password has been reset"
+            })),
+            Err(e) => error_response("Password reset failed", &*e),
+        }
+    }
+
+    pub async fn change_password(&self, req: HttpRequest, claims: Claims, change_dto: web::Json<ChangePasswordDto>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.change_password_use_case.execute(claims.sub, change_dto.into_inner(), &client_info(&req)).await {
+            Ok(()) => HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "Password changed"
+            })),
+            Err(e) => error_response("Failed to change password", &*e),
+        }
+    }
 }
 
 #[allow(dead_code)]
-pub fn configure<T: AuthRepository + 'static>(
+pub fn configure<T: AuthRepository + 'static, L: LoginAttemptRepository + 'static>(
     cfg: &mut web::ServiceConfig,
-    _handlers: web::Data<AuthHandlers<T>>,  // Removed underscore
+    _handlers: web::Data<AuthHandlers<T, L>>,  // Removed underscore
 ) {
     cfg.service(
         web::scope("/auth")
             .route("/login", web::post().to(
-                |handlers: web::Data<AuthHandlers<T>>, auth: web::Json<AuthUser>| async move {
-                    handlers.login(auth).await
+                |handlers: web::Data<AuthHandlers<T, L>>, req: HttpRequest, auth: web::Json<AuthUser>| async move {
+                    handlers.login(req, auth).await
                 }
             ))
             .route("/register", web::post().to(
-                |handlers: web::Data<AuthHandlers<T>>, register_dto: web::Json<RegisterUserDto>| async move {
+                |handlers: web::Data<AuthHandlers<T, L>>, register_dto: web::Json<RegisterUserDto>| async move {
                     handlers.register(register_dto).await
                 }
             ))
+            .route("/verify-email", web::get().to(
+                |handlers: web::Data<AuthHandlers<T, L>>, query: web::Query<VerifyEmailDto>| async move {
+                    handlers.verify_email(query).await
+                }
+            ))
+            .route("/resend-verification", web::post().to(
+                |handlers: web::Data<AuthHandlers<T, L>>, resend_dto: web::Json<ResendVerificationDto>| async move {
+                    handlers.resend_verification(resend_dto).await
+                }
+            ))
+            .route("/password-reset/request", web::post().to(
+                |handlers: web::Data<AuthHandlers<T, L>>, reset_dto: web::Json<RequestPasswordResetDto>| async move {
+                    handlers.request_password_reset(reset_dto).await
+                }
+            ))
+            .route("/password-reset/confirm", web::post().to(
+                |handlers: web::Data<AuthHandlers<T, L>>, req: HttpRequest, reset_dto: web::Json<ConfirmPasswordResetDto>| async move {
+                    handlers.confirm_password_reset(req, reset_dto).await
+                }
+            ))
+            // The rest of /auth is public; changing the password needs a signed-in user
+            .service(
+                web::resource("/change-password")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::post().to(
+                        |handlers: web::Data<AuthHandlers<T, L>>, req: HttpRequest, claims: Claims, change_dto: web::Json<ChangePasswordDto>| async move {
+                            handlers.change_password(req, claims, change_dto).await
+                        }
+                    ))
+            )
     );
 }
\ No newline at end of file
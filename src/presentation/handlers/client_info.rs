@@ -0,0 +1,76 @@
+use std::net::{IpAddr, SocketAddr};
+use actix_web::{http::header, web, HttpRequest};
+use tracing::warn;
+use crate::domain::entities::session::ClientInfo;
+
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Reverse proxies whose `Forwarded` / `X-Forwarded-For` headers are believed, read from
+/// `TRUSTED_PROXIES` (comma-separated IP addresses).
+///
+/// With none configured the socket address is taken as the client's, so a server behind a
+/// proxy must list it; otherwise every client shares the proxy's address and its login lockout.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<IpAddr>);
+
+impl TrustedProxies {
+    pub fn new(addresses: Vec<IpAddr>) -> Self {
+        Self(addresses)
+    }
+
+    pub fn from_env() -> Self {
+        let addresses = std::env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .filter_map(|address| match address.parse() {
+                Ok(address) => Some(address),
+                Err(_) => {
+                    warn!("Ignoring invalid TRUSTED_PROXIES entry: {}", address);
+                    None
+                }
+            })
+            .collect();
+
+        Self::new(addresses)
+    }
+
+    fn trusts(&self, address: IpAddr) -> bool {
+        self.0.contains(&address)
+    }
+}
+
+/// Describes the client making a sign-in request, for the session list.
+pub fn client_info(req: &HttpRequest) -> ClientInfo {
+    let user_agent = req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+    ClientInfo {
+        user_agent,
+        ip_address: client_ip(req).map(|ip| ip.to_string()),
+    }
+}
+
+/// The socket address, unless it belongs to a trusted proxy: forwarding headers can be set
+/// by any client, so they are only read from a proxy that overwrites them.
+fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    let behind_trusted_proxy = req.app_data::<web::Data<TrustedProxies>>()
+        .is_some_and(|trusted_proxies| trusted_proxies.trusts(peer));
+    if !behind_trusted_proxy {
+        return Some(peer);
+    }
+
+    // actix takes the first forwarded address, so the proxy must replace the header
+    // rather than append to what the client sent
+    req.connection_info()
+        .realip_remote_addr()
+        .and_then(|address| {
+            address.parse::<IpAddr>().ok()
+                .or_else(|| address.parse::<SocketAddr>().ok().map(|address| address.ip()))
+        })
+        .or(Some(peer))
+}
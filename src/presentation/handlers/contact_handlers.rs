@@ -0,0 +1,121 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::contact_use_cases::{
+    SendContactRequestUseCase, RespondContactRequestUseCase, ListContactRequestsUseCase,
+    ListContactsUseCase, RemoveContactUseCase, ContactSettingsUseCase,
+};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::contact::{SendContactRequestDto, UpdateContactSettingsDto};
+use crate::domain::repositories::account_repository::AccountRepository;
+use crate::domain::repositories::contact_repository::ContactRepository;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct ContactHandlers<C: ContactRepository, A: AccountRepository> {
+    send_contact_request_use_case: SendContactRequestUseCase<C>,
+    respond_contact_request_use_case: RespondContactRequestUseCase<C>,
+    list_contact_requests_use_case: ListContactRequestsUseCase<C>,
+    list_contacts_use_case: ListContactsUseCase<C, A>,
+    remove_contact_use_case: RemoveContactUseCase<C>,
+    contact_settings_use_case: ContactSettingsUseCase<C>,
+}
+
+impl<C: ContactRepository, A: AccountRepository> ContactHandlers<C, A> {
+    pub fn new(
+        send_contact_request_use_case: SendContactRequestUseCase<C>,
+        respond_contact_request_use_case: RespondContactRequestUseCase<C>,
+        list_contact_requests_use_case: ListContactRequestsUseCase<C>,
+        list_contacts_use_case: ListContactsUseCase<C, A>,
+        remove_contact_use_case: RemoveContactUseCase<C>,
+        contact_settings_use_case: ContactSettingsUseCase<C>,
+    ) -> Self {
+        Self {
+            send_contact_request_use_case,
+            respond_contact_request_use_case,
+            list_contact_requests_use_case,
+            list_contacts_use_case,
+            remove_contact_use_case,
+            contact_settings_use_case,
+        }
+    }
+
+    pub async fn list_contacts(&self, claims: Claims) -> impl Responder {
+        match self.list_contacts_use_case.execute(claims.sub).await {
+            Ok(contacts) => HttpResponse::Ok().json(contacts),
+            Err(e) => error_response("Failed to get contacts", &*e),
+        }
+    }
+
+    pub async fn remove_contact(&self, claims: Claims, contact_id: web::Path<i32>) -> impl Responder {
+        match self.remove_contact_use_case.execute(claims.sub, contact_id.into_inner()).await {
+            Ok(_) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to remove contact", &*e),
+        }
+    }
+
+    pub async fn list_requests(&self, claims: Claims) -> impl Responder {
+        match self.list_contact_requests_use_case.execute(claims.sub).await {
+            Ok(requests) => HttpResponse::Ok().json(requests),
+            Err(e) => error_response("Failed to get contact requests", &*e),
+        }
+    }
+
+    pub async fn send_request(&self, claims: Claims, request_dto: web::Json<SendContactRequestDto>) -> impl Responder {
+        match self.send_contact_request_use_case.execute(claims.sub, request_dto.addressee_id).await {
+            Ok(request) => HttpResponse::Created().json(request),
+            Err(e) => error_response("Failed to send contact request", &*e),
+        }
+    }
+
+    pub async fn respond_to_request(&self, claims: Claims, request_id: web::Path<i32>, accept: bool) -> impl Responder {
+        match self.respond_contact_request_use_case.execute(claims.sub, request_id.into_inner(), accept).await {
+            Ok(request) => HttpResponse::Ok().json(request),
+            Err(e) => error_response("Failed to respond to contact request", &*e),
+        }
+    }
+
+    pub async fn get_settings(&self, claims: Claims) -> impl Responder {
+        match self.contact_settings_use_case.get(claims.sub).await {
+            Ok(settings) => HttpResponse::Ok().json(settings),
+            Err(e) => error_response("Failed to get contact settings", &*e),
+        }
+    }
+
+    pub async fn update_settings(&self, claims: Claims, settings_dto: web::Json<UpdateContactSettingsDto>) -> impl Responder {
+        match self.contact_settings_use_case.update(claims.sub, settings_dto.allow_messages_from_strangers).await {
+            Ok(settings) => HttpResponse::Ok().json(settings),
+            Err(e) => error_response("Failed to update contact settings", &*e),
+        }
+    }
+}
+
+pub fn configure<C: ContactRepository + 'static, A: AccountRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<ContactHandlers<C, A>>,
+) {
+    cfg.service(
+        web::scope("/contacts")
+            .route("", web::get().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims| async move {
+                handlers.list_contacts(claims).await
+            }))
+            .route("/requests", web::get().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims| async move {
+                handlers.list_requests(claims).await
+            }))
+            .route("/requests", web::post().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims, request_dto: web::Json<SendContactRequestDto>| async move {
+                handlers.send_request(claims, request_dto).await
+            }))
+            .route("/requests/{id}/accept", web::post().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.respond_to_request(claims, id, true).await
+            }))
+            .route("/requests/{id}/decline", web::post().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.respond_to_request(claims, id, false).await
+            }))
+            .route("/settings", web::get().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims| async move {
+                handlers.get_settings(claims).await
+            }))
+            .route("/settings", web::put().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims, settings_dto: web::Json<UpdateContactSettingsDto>| async move {
+                handlers.update_settings(claims, settings_dto).await
+            }))
+            .route("/{user_id}", web::delete().to(move |handlers: web::Data<ContactHandlers<C, A>>, claims: Claims, user_id: web::Path<i32>| async move {
+                handlers.remove_contact(claims, user_id).await
+            }))
+    );
+}
@@ -0,0 +1,38 @@
+use actix_web::HttpResponse;
+use std::io::ErrorKind;
+use tracing::error;
+use crate::domain::services::validation::ValidationErrors;
+
+/// Maps a use case error to an HTTP response, using the `std::io::ErrorKind`
+/// the use cases attach to domain errors to pick the status code. Validation failures
+/// are answered with 400 and the list of rejected fields. Anything else is a 500 whose
+/// cause is logged rather than sent to the client.
+pub fn error_response(error: &str, e: &(dyn std::error::Error + 'static)) -> HttpResponse {
+    if let Some(validation) = e.downcast_ref::<ValidationErrors>() {
+        return validation_response(error, validation);
+    }
+
+    let body = serde_json::json!({
+        "error": error,
+        "message": e.to_string()
+    });
+
+    match e.downcast_ref::<std::io::Error>().map(|io_error| io_error.kind()) {
+        Some(ErrorKind::NotFound) => HttpResponse::NotFound().json(body),
+        Some(ErrorKind::PermissionDenied) => HttpResponse::Forbidden().json(body),
+        Some(ErrorKind::AlreadyExists) => HttpResponse::Conflict().json(body),
+        Some(ErrorKind::InvalidInput) => HttpResponse::BadRequest().json(body),
+        _ => {
+            error!("{}: {}", error, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({ "error": error }))
+        }
+    }
+}
+
+fn validation_response(error: &str, validation: &ValidationErrors) -> HttpResponse {
+    HttpResponse::BadRequest().json(serde_json::json!({
+        "error": error,
+        "message": validation.to_string(),
+        "errors": validation.errors
+    }))
+}
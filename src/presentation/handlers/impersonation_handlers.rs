@@ -0,0 +1,45 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde_json::json;
+use crate::application::use_cases::impersonation_use_cases::ImpersonateUserUseCase;
+use crate::domain::entities::auth::Claims;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+
+/// Credentials, second factors, the account's email, its sessions, OAuth grants and the
+/// account itself belong to the user, so they cannot be changed with an impersonation token.
+pub(crate) fn forbid_impersonation(claims: &Claims) -> Option<HttpResponse> {
+    claims.act.as_ref().map(|_| HttpResponse::Forbidden().json(json!({
+        "error": "This action is not allowed while impersonating a user"
+    })))
+}
+
+pub struct ImpersonationHandlers<T: AuthRepository, R: RoleRepository> {
+    impersonate_user_use_case: ImpersonateUserUseCase<T, R>,
+}
+
+impl<T: AuthRepository, R: RoleRepository> ImpersonationHandlers<T, R> {
+    pub fn new(impersonate_user_use_case: ImpersonateUserUseCase<T, R>) -> Self {
+        Self { impersonate_user_use_case }
+    }
+
+    pub async fn impersonate(&self, req: HttpRequest, claims: Claims, user_id: web::Path<i32>) -> impl Responder {
+        match self.impersonate_user_use_case.execute(&claims, user_id.into_inner(), &client_info(&req)).await {
+            Ok(token) => HttpResponse::Ok().json(token),
+            Err(e) => error_response("Failed to impersonate user", &*e),
+        }
+    }
+}
+
+pub fn configure<T: AuthRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<ImpersonationHandlers<T, R>>,
+) {
+    cfg.service(
+        web::scope("/impersonation")
+            .route("/{user_id}", web::post().to(move |handlers: web::Data<ImpersonationHandlers<T, R>>, req: HttpRequest, claims: Claims, user_id: web::Path<i32>| async move {
+                handlers.impersonate(req, claims, user_id).await
+            }))
+    );
+}
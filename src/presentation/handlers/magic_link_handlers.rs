@@ -0,0 +1,69 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde_json::json;
+use tracing::debug;
+use crate::application::use_cases::magic_link_use_cases::{RequestMagicLinkUseCase, VerifyMagicLinkUseCase};
+use crate::domain::entities::auth::{RequestMagicLinkDto, VerifyMagicLinkDto};
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct MagicLinkHandlers<T: AuthRepository> {
+    request_magic_link_use_case: RequestMagicLinkUseCase<T>,
+    verify_magic_link_use_case: VerifyMagicLinkUseCase<T>,
+}
+
+impl<T: AuthRepository> MagicLinkHandlers<T> {
+    pub fn new(
+        request_magic_link_use_case: RequestMagicLinkUseCase<T>,
+        verify_magic_link_use_case: VerifyMagicLinkUseCase<T>,
+    ) -> Self {
+        Self {
+            request_magic_link_use_case,
+            verify_magic_link_use_case,
+        }
+    }
+
+    pub async fn request(&self, request_dto: web::Json<RequestMagicLinkDto>) -> impl Responder {
+        match self.request_magic_link_use_case.execute(&request_dto.email).await {
+            Ok(()) => HttpResponse::Accepted().json(json!({
+                "status": "success",
+                "message": "If the address belongs to an account, a sign-in link has been sent"
+            })),
+            Err(e) => error_response("Failed to send sign-in link", &*e),
+        }
+    }
+
+    pub async fn verify(&self, req: HttpRequest, verify_dto: web::Json<VerifyMagicLinkDto>) -> impl Responder {
+        match self.verify_magic_link_use_case.execute(&verify_dto.token, &client_info(&req)).await {
+            Ok(login_response) => HttpResponse::Ok().json(login_response),
+            Err(e) => {
+                debug!("Magic link login failed: {}", e);
+                HttpResponse::Unauthorized().json(json!({
+                    "error": "Authentication failed",
+                    "message": e.to_string()
+                }))
+            }
+        }
+    }
+}
+
+/// The emailed link opens the frontend, which posts the token here. A GET endpoint would
+/// let mail scanners that prefetch links use up the token.
+pub fn configure<T: AuthRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<MagicLinkHandlers<T>>,
+) {
+    cfg.service(
+        web::scope("/auth/magic-link")
+            .route("", web::post().to(
+                |handlers: web::Data<MagicLinkHandlers<T>>, request_dto: web::Json<RequestMagicLinkDto>| async move {
+                    handlers.request(request_dto).await
+                }
+            ))
+            .route("/verify", web::post().to(
+                |handlers: web::Data<MagicLinkHandlers<T>>, req: HttpRequest, verify_dto: web::Json<VerifyMagicLinkDto>| async move {
+                    handlers.verify(req, verify_dto).await
+                }
+            ))
+    );
+}
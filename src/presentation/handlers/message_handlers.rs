@@ -1,47 +1,74 @@
 use actix_web::{web, HttpResponse};
-use crate::application::use_cases::message_use_cases::{SendMessageUseCase, GetMessagesUseCase};
+use tracing::debug;
+use crate::application::use_cases::contact_use_cases::{SENDER_SUSPENDED, STRANGER_MESSAGES_DISABLED};
+use crate::application::use_cases::message_use_cases::{SendMessageUseCase, GetMessagesUseCase, GetScheduledMessagesUseCase};
 use crate::infrastructure::websocket::realtime_message_manager::RealtimeMessageManager;
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::message::SendMessageDto;
+use crate::domain::repositories::contact_repository::ContactRepository;
 use crate::domain::repositories::message_repository::MessageRepository;
+use crate::domain::repositories::moderation_repository::ModerationRepository;
 
-pub struct MessageHandlers<T: MessageRepository> {
-    send_message_use_case: SendMessageUseCase<T>,
+pub struct MessageHandlers<T: MessageRepository, C: ContactRepository, M: ModerationRepository> {
+    send_message_use_case: SendMessageUseCase<T, C, M>,
     get_messages_use_case: GetMessagesUseCase<T>,
+    get_scheduled_messages_use_case: GetScheduledMessagesUseCase<T>,
     realtime_message_manager: RealtimeMessageManager,
 }
 
-impl<T: MessageRepository> MessageHandlers<T> {
+impl<T: MessageRepository, C: ContactRepository, M: ModerationRepository> MessageHandlers<T, C, M> {
     pub fn new(
-        send_message_use_case: SendMessageUseCase<T>,
+        send_message_use_case: SendMessageUseCase<T, C, M>,
         get_messages_use_case: GetMessagesUseCase<T>,
+        get_scheduled_messages_use_case: GetScheduledMessagesUseCase<T>,
         realtime_message_manager: RealtimeMessageManager,
     ) -> Self {
         Self {
             send_message_use_case,
             get_messages_use_case,
+            get_scheduled_messages_use_case,
             realtime_message_manager,
         }
     }
 
-    // Example handler methods
     pub async fn send_message(
         &self,
         sender_id: i32,
-        receiver_id: i32,
-        content: String,
+        message_dto: SendMessageDto,
     ) -> Result<HttpResponse, actix_web::Error> {
         // Save to database
         let message = self.send_message_use_case
-            .execute(sender_id, receiver_id, content.clone())
+            .execute(sender_id, message_dto)
             .await
-            .map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+            .map_err(|e| {
+                if e == STRANGER_MESSAGES_DISABLED || e == SENDER_SUSPENDED {
+                    actix_web::error::ErrorForbidden(e)
+                } else {
+                    actix_web::error::ErrorInternalServerError(e)
+                }
+            })?;
+
+        // Scheduled messages are pushed by the message scheduler once they are due
+        if message.is_delivered {
+            // The message is already stored, so an offline recipient simply reads it later
+            if let Err(e) = self.realtime_message_manager
+                .send_message(sender_id, message.receiver_id, message.content.clone())
+                .await
+            {
+                debug!("Real-time delivery skipped for message {}: {}", message.id, e);
+            }
+        }
+
+        Ok(HttpResponse::Ok().json(message))
+    }
 
-        // Send real-time message
-        self.realtime_message_manager
-            .send_message(sender_id, receiver_id, content)
+    pub async fn get_scheduled_messages(&self, sender_id: i32) -> Result<HttpResponse, actix_web::Error> {
+        let messages = self.get_scheduled_messages_use_case
+            .execute(sender_id)
             .await
             .map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
 
-        Ok(HttpResponse::Ok().json(message))
+        Ok(HttpResponse::Ok().json(messages))
     }
 
     pub async fn get_messages(
@@ -59,30 +86,31 @@ impl<T: MessageRepository> MessageHandlers<T> {
 }
 
 // Add configuration function for routes
-pub fn configure<T: MessageRepository + 'static>(
+pub fn configure<T: MessageRepository + 'static, C: ContactRepository + 'static, M: ModerationRepository + 'static>(
     cfg: &mut web::ServiceConfig,
-    _handlers: web::Data<MessageHandlers<T>>,
+    _handlers: web::Data<MessageHandlers<T, C, M>>,
 ) {
     cfg.service(
         web::scope("/messages")
             .route("", web::post().to(move |
-                sender_id: web::Path<i32>,
-                receiver_id: web::Path<i32>,
-                content: web::Json<String>,
-                handlers: web::Data<MessageHandlers<T>>,
+                claims: Claims,
+                message_dto: web::Json<SendMessageDto>,
+                handlers: web::Data<MessageHandlers<T, C, M>>,
             | async move {
-                handlers.send_message(
-                    sender_id.into_inner(),
-                    receiver_id.into_inner(),
-                    content.into_inner(),
-                ).await
+                handlers.send_message(claims.sub, message_dto.into_inner()).await
+            }))
+            .route("/scheduled", web::get().to(move |
+                claims: Claims,
+                handlers: web::Data<MessageHandlers<T, C, M>>,
+            | async move {
+                handlers.get_scheduled_messages(claims.sub).await
             }))
             .route("/{user1_id}/{user2_id}", web::get().to(move |
                 path: web::Path<(i32, i32)>,
-                handlers: web::Data<MessageHandlers<T>>,
+                handlers: web::Data<MessageHandlers<T, C, M>>,
             | async move {
                 let (user1_id, user2_id) = path.into_inner();
                 handlers.get_messages(user1_id, user2_id).await
             }))
     );
-}
\ No newline at end of file
+}
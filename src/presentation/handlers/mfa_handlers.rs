@@ -0,0 +1,111 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use serde_json::json;
+use tracing::debug;
+use crate::application::use_cases::mfa_use_cases::{CompleteMfaLoginUseCase, TotpEnrollmentUseCase};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::mfa::{DisableTotpDto, MfaLoginDto, TotpCodeDto};
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+use crate::presentation::middleware::auth::validator;
+
+pub struct MfaHandlers<T: AuthRepository, L: LoginAttemptRepository> {
+    complete_mfa_login_use_case: CompleteMfaLoginUseCase<T, L>,
+    totp_enrollment_use_case: TotpEnrollmentUseCase<T>,
+}
+
+impl<T: AuthRepository, L: LoginAttemptRepository> MfaHandlers<T, L> {
+    pub fn new(
+        complete_mfa_login_use_case: CompleteMfaLoginUseCase<T, L>,
+        totp_enrollment_use_case: TotpEnrollmentUseCase<T>,
+    ) -> Self {
+        Self {
+            complete_mfa_login_use_case,
+            totp_enrollment_use_case,
+        }
+    }
+
+    pub async fn complete_mfa_login(&self, req: HttpRequest, mfa_dto: web::Json<MfaLoginDto>) -> impl Responder {
+        match self.complete_mfa_login_use_case.execute(mfa_dto.into_inner(), &client_info(&req)).await {
+            Ok(token) => HttpResponse::Ok().json(token),
+            Err(e) => {
+                debug!("MFA login failed: {}", e);
+                HttpResponse::Unauthorized().json(json!({
+                    "error": "Authentication failed",
+                    "message": e.to_string()
+                }))
+            }
+        }
+    }
+
+    pub async fn enroll_totp(&self, claims: Claims) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.totp_enrollment_use_case.enroll(claims.sub).await {
+            Ok(enrollment) => HttpResponse::Ok().json(enrollment),
+            Err(e) => error_response("Failed to start two-factor enrollment", &*e),
+        }
+    }
+
+    pub async fn confirm_totp(&self, req: HttpRequest, claims: Claims, code_dto: web::Json<TotpCodeDto>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.totp_enrollment_use_case.confirm(claims.sub, &code_dto.code, &client_info(&req)).await {
+            Ok(recovery_codes) => HttpResponse::Ok().json(recovery_codes),
+            Err(e) => error_response("Failed to confirm two-factor authentication", &*e),
+        }
+    }
+
+    pub async fn disable_totp(&self, req: HttpRequest, claims: Claims, disable_dto: web::Json<DisableTotpDto>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.totp_enrollment_use_case.disable(claims.sub, disable_dto.into_inner(), &client_info(&req)).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to disable two-factor authentication", &*e),
+        }
+    }
+}
+
+/// Registered before the `/auth` scope, which would otherwise claim these paths.
+/// The second login step is public; managing two-factor needs a signed-in user.
+pub fn configure<T: AuthRepository + 'static, L: LoginAttemptRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<MfaHandlers<T, L>>,
+) {
+    cfg.service(
+        web::resource("/auth/login/mfa")
+            .route(web::post().to(
+                |handlers: web::Data<MfaHandlers<T, L>>, req: HttpRequest, mfa_dto: web::Json<MfaLoginDto>| async move {
+                    handlers.complete_mfa_login(req, mfa_dto).await
+                }
+            ))
+    )
+    .service(
+        web::scope("/auth/2fa")
+            .wrap(HttpAuthentication::bearer(validator))
+            .route("/enroll", web::post().to(
+                |handlers: web::Data<MfaHandlers<T, L>>, claims: Claims| async move {
+                    handlers.enroll_totp(claims).await
+                }
+            ))
+            .route("/confirm", web::post().to(
+                |handlers: web::Data<MfaHandlers<T, L>>, req: HttpRequest, claims: Claims, code_dto: web::Json<TotpCodeDto>| async move {
+                    handlers.confirm_totp(req, claims, code_dto).await
+                }
+            ))
+            .route("/disable", web::post().to(
+                |handlers: web::Data<MfaHandlers<T, L>>, req: HttpRequest, claims: Claims, disable_dto: web::Json<DisableTotpDto>| async move {
+                    handlers.disable_totp(req, claims, disable_dto).await
+                }
+            ))
+    );
+}
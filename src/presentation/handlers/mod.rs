@@ -3,4 +3,20 @@ pub(crate) mod auth_handlers;
 pub mod account_handlers;
 pub mod ws_handlers;
 pub mod message_handlers;
-pub mod avatar_handlers;
\ No newline at end of file
+pub mod avatar_handlers;
+pub mod contact_handlers;
+pub mod moderation_handlers;
+pub mod announcement_handlers;
+pub mod notification_handlers;
+pub mod errors;
+pub mod client_info;
+pub mod security_handlers;
+pub mod oidc_handlers;
+pub mod oauth_handlers;
+pub mod api_key_handlers;
+pub mod session_handlers;
+pub mod passkey_handlers;
+pub mod magic_link_handlers;
+pub mod registration_handlers;
+pub mod impersonation_handlers;
+pub mod mfa_handlers;
@@ -0,0 +1,121 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::moderation_use_cases::{
+    ReportMessageUseCase, ListReportsUseCase, DismissReportUseCase, HideMessageUseCase,
+    SuspendUserUseCase, ListModerationActionsUseCase,
+};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::moderation::{CreateReportDto, ModerationNoteDto, ReportQuery, SuspendUserDto};
+use crate::domain::repositories::moderation_repository::ModerationRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct ModerationHandlers<M: ModerationRepository, R: RoleRepository> {
+    report_message_use_case: ReportMessageUseCase<M>,
+    list_reports_use_case: ListReportsUseCase<M, R>,
+    dismiss_report_use_case: DismissReportUseCase<M, R>,
+    hide_message_use_case: HideMessageUseCase<M, R>,
+    suspend_user_use_case: SuspendUserUseCase<M, R>,
+    list_moderation_actions_use_case: ListModerationActionsUseCase<M, R>,
+}
+
+impl<M: ModerationRepository, R: RoleRepository> ModerationHandlers<M, R> {
+    pub fn new(
+        report_message_use_case: ReportMessageUseCase<M>,
+        list_reports_use_case: ListReportsUseCase<M, R>,
+        dismiss_report_use_case: DismissReportUseCase<M, R>,
+        hide_message_use_case: HideMessageUseCase<M, R>,
+        suspend_user_use_case: SuspendUserUseCase<M, R>,
+        list_moderation_actions_use_case: ListModerationActionsUseCase<M, R>,
+    ) -> Self {
+        Self {
+            report_message_use_case,
+            list_reports_use_case,
+            dismiss_report_use_case,
+            hide_message_use_case,
+            suspend_user_use_case,
+            list_moderation_actions_use_case,
+        }
+    }
+
+    pub async fn report_message(&self, claims: Claims, report_dto: web::Json<CreateReportDto>) -> impl Responder {
+        match self.report_message_use_case.execute(claims.sub, report_dto.into_inner()).await {
+            Ok(report) => HttpResponse::Created().json(report),
+            Err(e) => error_response("Failed to report message", &*e),
+        }
+    }
+
+    pub async fn list_reports(&self, claims: Claims, query: web::Query<ReportQuery>) -> impl Responder {
+        match self.list_reports_use_case.execute(claims.sub, query.status).await {
+            Ok(reports) => HttpResponse::Ok().json(reports),
+            Err(e) => error_response("Failed to get reports", &*e),
+        }
+    }
+
+    pub async fn dismiss_report(&self, claims: Claims, report_id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>) -> impl Responder {
+        match self.dismiss_report_use_case.execute(claims.sub, report_id.into_inner(), note_dto.into_inner().note).await {
+            Ok(report) => HttpResponse::Ok().json(report),
+            Err(e) => error_response("Failed to dismiss report", &*e),
+        }
+    }
+
+    pub async fn set_message_hidden(&self, claims: Claims, message_id: web::Path<i32>, hidden: bool, note_dto: web::Json<ModerationNoteDto>) -> impl Responder {
+        match self.hide_message_use_case.execute(claims.sub, message_id.into_inner(), hidden, note_dto.into_inner().note).await {
+            Ok(action) => HttpResponse::Ok().json(action),
+            Err(e) => error_response("Failed to update message visibility", &*e),
+        }
+    }
+
+    pub async fn suspend_user(&self, claims: Claims, user_id: web::Path<i32>, suspension_dto: web::Json<SuspendUserDto>) -> impl Responder {
+        match self.suspend_user_use_case.execute(claims.sub, user_id.into_inner(), suspension_dto.into_inner()).await {
+            Ok(suspension) => HttpResponse::Created().json(suspension),
+            Err(e) => error_response("Failed to suspend user", &*e),
+        }
+    }
+
+    pub async fn lift_suspension(&self, claims: Claims, user_id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>) -> impl Responder {
+        match self.suspend_user_use_case.lift(claims.sub, user_id.into_inner(), note_dto.into_inner().note).await {
+            Ok(action) => HttpResponse::Ok().json(action),
+            Err(e) => error_response("Failed to lift suspension", &*e),
+        }
+    }
+
+    pub async fn list_actions(&self, claims: Claims) -> impl Responder {
+        match self.list_moderation_actions_use_case.execute(claims.sub).await {
+            Ok(actions) => HttpResponse::Ok().json(actions),
+            Err(e) => error_response("Failed to get moderation log", &*e),
+        }
+    }
+}
+
+pub fn configure<M: ModerationRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<ModerationHandlers<M, R>>,
+) {
+    cfg.service(
+        web::scope("/moderation")
+            .route("/reports", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, report_dto: web::Json<CreateReportDto>| async move {
+                handlers.report_message(claims, report_dto).await
+            }))
+            .route("/reports", web::get().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, query: web::Query<ReportQuery>| async move {
+                handlers.list_reports(claims, query).await
+            }))
+            .route("/reports/{id}/dismiss", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>| async move {
+                handlers.dismiss_report(claims, id, note_dto).await
+            }))
+            .route("/messages/{id}/hide", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>| async move {
+                handlers.set_message_hidden(claims, id, true, note_dto).await
+            }))
+            .route("/messages/{id}/unhide", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>| async move {
+                handlers.set_message_hidden(claims, id, false, note_dto).await
+            }))
+            .route("/users/{id}/suspend", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, id: web::Path<i32>, suspension_dto: web::Json<SuspendUserDto>| async move {
+                handlers.suspend_user(claims, id, suspension_dto).await
+            }))
+            .route("/users/{id}/unsuspend", web::post().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims, id: web::Path<i32>, note_dto: web::Json<ModerationNoteDto>| async move {
+                handlers.lift_suspension(claims, id, note_dto).await
+            }))
+            .route("/actions", web::get().to(move |handlers: web::Data<ModerationHandlers<M, R>>, claims: Claims| async move {
+                handlers.list_actions(claims).await
+            }))
+    );
+}
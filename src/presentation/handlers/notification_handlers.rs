@@ -0,0 +1,75 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::notification_use_cases::NotificationPreferencesUseCase;
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::notification::{NotificationMode, UpdateNotificationPreferenceDto};
+use crate::domain::repositories::notification_repository::NotificationRepository;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct NotificationHandlers<N: NotificationRepository> {
+    notification_preferences_use_case: NotificationPreferencesUseCase<N>,
+}
+
+impl<N: NotificationRepository> NotificationHandlers<N> {
+    pub fn new(notification_preferences_use_case: NotificationPreferencesUseCase<N>) -> Self {
+        Self {
+            notification_preferences_use_case,
+        }
+    }
+
+    pub async fn list_preferences(&self, claims: Claims) -> impl Responder {
+        match self.notification_preferences_use_case.list(claims.sub).await {
+            Ok(preferences) => HttpResponse::Ok().json(preferences),
+            Err(e) => error_response("Failed to get notification preferences", &*e),
+        }
+    }
+
+    pub async fn get_preference(&self, claims: Claims, counterpart_id: web::Path<i32>) -> impl Responder {
+        let counterpart_id = counterpart_id.into_inner();
+        match self.notification_preferences_use_case.get(claims.sub, counterpart_id).await {
+            Ok(Some(preference)) => HttpResponse::Ok().json(preference),
+            // No row means the defaults apply
+            Ok(None) => HttpResponse::Ok().json(serde_json::json!({
+                "user_id": claims.sub,
+                "counterpart_user_id": counterpart_id,
+                "mode": NotificationMode::All,
+                "muted_until": null
+            })),
+            Err(e) => error_response("Failed to get notification preference", &*e),
+        }
+    }
+
+    pub async fn update_preference(&self, claims: Claims, counterpart_id: web::Path<i32>, preference_dto: web::Json<UpdateNotificationPreferenceDto>) -> impl Responder {
+        match self.notification_preferences_use_case.update(claims.sub, counterpart_id.into_inner(), preference_dto.into_inner()).await {
+            Ok(preference) => HttpResponse::Ok().json(preference),
+            Err(e) => error_response("Failed to update notification preference", &*e),
+        }
+    }
+
+    pub async fn reset_preference(&self, claims: Claims, counterpart_id: web::Path<i32>) -> impl Responder {
+        match self.notification_preferences_use_case.reset(claims.sub, counterpart_id.into_inner()).await {
+            Ok(_) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to reset notification preference", &*e),
+        }
+    }
+}
+
+pub fn configure<N: NotificationRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<NotificationHandlers<N>>,
+) {
+    cfg.service(
+        web::scope("/notifications/preferences")
+            .route("", web::get().to(move |handlers: web::Data<NotificationHandlers<N>>, claims: Claims| async move {
+                handlers.list_preferences(claims).await
+            }))
+            .route("/users/{id}", web::get().to(move |handlers: web::Data<NotificationHandlers<N>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.get_preference(claims, id).await
+            }))
+            .route("/users/{id}", web::put().to(move |handlers: web::Data<NotificationHandlers<N>>, claims: Claims, id: web::Path<i32>, preference_dto: web::Json<UpdateNotificationPreferenceDto>| async move {
+                handlers.update_preference(claims, id, preference_dto).await
+            }))
+            .route("/users/{id}", web::delete().to(move |handlers: web::Data<NotificationHandlers<N>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.reset_preference(claims, id).await
+            }))
+    );
+}
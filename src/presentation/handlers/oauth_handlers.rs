@@ -0,0 +1,192 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::json;
+use crate::application::use_cases::oauth_use_cases::{
+    AuthorizeUseCase, IssueOAuthTokenUseCase, ManageOAuthClientsUseCase, OpenIdDiscoveryUseCase,
+};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::oauth::{AuthorizationRequest, CreateOAuthClientDto, OAuthError, TokenRequest};
+use crate::domain::repositories::oauth_repository::OAuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+use crate::presentation::middleware::auth::validator;
+
+/// OAuth errors use their own body and status codes (RFC 6749 section 5.2);
+/// anything else is an internal failure.
+fn oauth_error_response(e: &(dyn std::error::Error + 'static)) -> HttpResponse {
+    match e.downcast_ref::<OAuthError>() {
+        Some(oauth_error) if oauth_error.error == "invalid_client" => HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Basic"))
+            .json(oauth_error),
+        Some(oauth_error) => HttpResponse::BadRequest().json(oauth_error),
+        None => error_response("OAuth request failed", e),
+    }
+}
+
+/// Consent is given with a signed-in session, so a leaked API key cannot grant
+/// a client access to the account.
+fn requires_session(claims: &Claims) -> Option<HttpResponse> {
+    claims.scopes.as_ref().map(|_| HttpResponse::Forbidden().json(json!({
+        "error": "Authorization requests cannot be approved with an API key"
+    })))
+}
+
+/// Client id and secret from an `Authorization: Basic` header.
+fn basic_credentials(req: &HttpRequest) -> Option<(String, String)> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    let encoded = value.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (client_id, client_secret) = decoded.split_once(':')?;
+    Some((client_id.to_string(), client_secret.to_string()))
+}
+
+pub struct OAuthHandlers<O: OAuthRepository, R: RoleRepository> {
+    manage_clients_use_case: ManageOAuthClientsUseCase<O, R>,
+    authorize_use_case: AuthorizeUseCase<O>,
+    issue_token_use_case: IssueOAuthTokenUseCase<O>,
+    discovery_use_case: OpenIdDiscoveryUseCase,
+}
+
+impl<O: OAuthRepository, R: RoleRepository> OAuthHandlers<O, R> {
+    pub fn new(
+        manage_clients_use_case: ManageOAuthClientsUseCase<O, R>,
+        authorize_use_case: AuthorizeUseCase<O>,
+        issue_token_use_case: IssueOAuthTokenUseCase<O>,
+        discovery_use_case: OpenIdDiscoveryUseCase,
+    ) -> Self {
+        Self {
+            manage_clients_use_case,
+            authorize_use_case,
+            issue_token_use_case,
+            discovery_use_case,
+        }
+    }
+
+    pub async fn authorize(&self, req: HttpRequest, query: web::Query<AuthorizationRequest>) -> impl Responder {
+        match self.authorize_use_case.start(&query, req.query_string()).await {
+            Ok(consent_url) => HttpResponse::Found()
+                .insert_header((header::LOCATION, consent_url))
+                .finish(),
+            Err(e) => oauth_error_response(&*e),
+        }
+    }
+
+    pub async fn approve(&self, claims: Claims, request: web::Json<AuthorizationRequest>) -> impl Responder {
+        if let Some(response) = requires_session(&claims).or_else(|| forbid_impersonation(&claims)) {
+            return response;
+        }
+        match self.authorize_use_case.approve(claims.sub, request.into_inner()).await {
+            Ok(grant) => HttpResponse::Ok().json(grant),
+            Err(e) => oauth_error_response(&*e),
+        }
+    }
+
+    pub async fn token(&self, req: HttpRequest, form: web::Form<TokenRequest>) -> impl Responder {
+        match self.issue_token_use_case.execute(form.into_inner(), basic_credentials(&req)).await {
+            Ok(token_response) => HttpResponse::Ok()
+                .insert_header((header::CACHE_CONTROL, "no-store"))
+                .json(token_response),
+            Err(e) => oauth_error_response(&*e),
+        }
+    }
+
+    pub async fn create_client(&self, claims: Claims, client_dto: web::Json<CreateOAuthClientDto>) -> impl Responder {
+        match self.manage_clients_use_case.create(claims.sub, client_dto.into_inner()).await {
+            Ok(client) => HttpResponse::Created().json(client),
+            Err(e) => error_response("Failed to register client", &*e),
+        }
+    }
+
+    pub async fn list_clients(&self, claims: Claims) -> impl Responder {
+        match self.manage_clients_use_case.list(claims.sub).await {
+            Ok(clients) => HttpResponse::Ok().json(clients),
+            Err(e) => error_response("Failed to get clients", &*e),
+        }
+    }
+
+    pub async fn delete_client(&self, claims: Claims, client_id: web::Path<String>) -> impl Responder {
+        match self.manage_clients_use_case.delete(claims.sub, &client_id).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to delete client", &*e),
+        }
+    }
+
+    pub async fn openid_configuration(&self) -> impl Responder {
+        HttpResponse::Ok().json(self.discovery_use_case.configuration())
+    }
+
+    pub async fn jwks(&self) -> impl Responder {
+        HttpResponse::Ok().json(self.discovery_use_case.jwks())
+    }
+}
+
+pub fn configure<O: OAuthRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<OAuthHandlers<O, R>>,
+) {
+    cfg.service(
+        web::scope("/oauth")
+            .route("/authorize", web::get().to(
+                |handlers: web::Data<OAuthHandlers<O, R>>, req: HttpRequest, query: web::Query<AuthorizationRequest>| async move {
+                    handlers.authorize(req, query).await
+                }
+            ))
+            .route("/token", web::post().to(
+                |handlers: web::Data<OAuthHandlers<O, R>>, req: HttpRequest, form: web::Form<TokenRequest>| async move {
+                    handlers.token(req, form).await
+                }
+            ))
+            // Called by the consent page on behalf of the signed-in user
+            .service(
+                web::resource("/authorize/approve")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::post().to(
+                        |handlers: web::Data<OAuthHandlers<O, R>>, claims: Claims, request: web::Json<AuthorizationRequest>| async move {
+                            handlers.approve(claims, request).await
+                        }
+                    ))
+            )
+            .service(
+                web::scope("/clients")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route("", web::get().to(
+                        |handlers: web::Data<OAuthHandlers<O, R>>, claims: Claims| async move {
+                            handlers.list_clients(claims).await
+                        }
+                    ))
+                    .route("", web::post().to(
+                        |handlers: web::Data<OAuthHandlers<O, R>>, claims: Claims, client_dto: web::Json<CreateOAuthClientDto>| async move {
+                            handlers.create_client(claims, client_dto).await
+                        }
+                    ))
+                    .route("/{client_id}", web::delete().to(
+                        |handlers: web::Data<OAuthHandlers<O, R>>, claims: Claims, client_id: web::Path<String>| async move {
+                            handlers.delete_client(claims, client_id).await
+                        }
+                    ))
+            )
+    );
+}
+
+/// Discovery lives at the root, next to the issuer, as OpenID Connect Discovery requires.
+pub fn configure_well_known<O: OAuthRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<OAuthHandlers<O, R>>,
+) {
+    cfg.service(
+        web::scope("/.well-known")
+            .route("/openid-configuration", web::get().to(
+                |handlers: web::Data<OAuthHandlers<O, R>>| async move {
+                    handlers.openid_configuration().await
+                }
+            ))
+            .route("/jwks.json", web::get().to(
+                |handlers: web::Data<OAuthHandlers<O, R>>| async move {
+                    handlers.jwks().await
+                }
+            ))
+    );
+}
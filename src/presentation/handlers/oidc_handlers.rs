@@ -0,0 +1,72 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use serde_json::json;
+use crate::application::use_cases::oidc_use_cases::{CompleteOidcLoginUseCase, StartOidcLoginUseCase};
+use crate::domain::entities::identity::OidcCallbackQuery;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::identity_repository::IdentityRepository;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct OidcHandlers<T: AuthRepository, I: IdentityRepository> {
+    start_oidc_login_use_case: StartOidcLoginUseCase<I>,
+    complete_oidc_login_use_case: CompleteOidcLoginUseCase<T, I>,
+}
+
+impl<T: AuthRepository, I: IdentityRepository> OidcHandlers<T, I> {
+    pub fn new(
+        start_oidc_login_use_case: StartOidcLoginUseCase<I>,
+        complete_oidc_login_use_case: CompleteOidcLoginUseCase<T, I>,
+    ) -> Self {
+        Self {
+            start_oidc_login_use_case,
+            complete_oidc_login_use_case,
+        }
+    }
+
+    pub async fn authorize(&self, provider: web::Path<String>) -> impl Responder {
+        match self.start_oidc_login_use_case.execute(&provider).await {
+            Ok(authorization_url) => HttpResponse::Found()
+                .insert_header((header::LOCATION, authorization_url))
+                .finish(),
+            Err(e) => error_response("Failed to start sign-in", &*e),
+        }
+    }
+
+    pub async fn callback(&self, req: HttpRequest, provider: web::Path<String>, query: web::Query<OidcCallbackQuery>) -> impl Responder {
+        let query = query.into_inner();
+
+        if let Some(error) = query.error {
+            return HttpResponse::BadRequest().json(json!({
+                "error": "Sign-in was not completed",
+                "message": query.error_description.unwrap_or(error)
+            }));
+        }
+
+        let (Some(code), Some(state)) = (query.code, query.state) else {
+            return HttpResponse::BadRequest().json(json!({
+                "error": "Sign-in was not completed",
+                "message": "Missing code or state"
+            }));
+        };
+
+        match self.complete_oidc_login_use_case.execute(&provider, &code, &state, &client_info(&req)).await {
+            Ok(login_response) => HttpResponse::Ok().json(login_response),
+            Err(e) => error_response("Sign-in failed", &*e),
+        }
+    }
+}
+
+pub fn configure<T: AuthRepository + 'static, I: IdentityRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<OidcHandlers<T, I>>,
+) {
+    cfg.service(
+        web::scope("/auth/oidc")
+            .route("/{provider}/authorize", web::get().to(move |handlers: web::Data<OidcHandlers<T, I>>, provider: web::Path<String>| async move {
+                handlers.authorize(provider).await
+            }))
+            .route("/{provider}/callback", web::get().to(move |handlers: web::Data<OidcHandlers<T, I>>, req: HttpRequest, provider: web::Path<String>, query: web::Query<OidcCallbackQuery>| async move {
+                handlers.callback(req, provider, query).await
+            }))
+    );
+}
@@ -0,0 +1,146 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use serde_json::json;
+use tracing::debug;
+use crate::application::use_cases::passkey_use_cases::{ManagePasskeysUseCase, PasskeyLoginUseCase};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::passkey::{PasskeyLoginDto, RegisterPasskeyDto};
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::passkey_repository::PasskeyRepository;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+use crate::presentation::middleware::auth::validator;
+
+pub struct PasskeyHandlers<T: AuthRepository, P: PasskeyRepository> {
+    manage_passkeys_use_case: ManagePasskeysUseCase<T, P>,
+    passkey_login_use_case: PasskeyLoginUseCase<T, P>,
+}
+
+impl<T: AuthRepository, P: PasskeyRepository> PasskeyHandlers<T, P> {
+    pub fn new(
+        manage_passkeys_use_case: ManagePasskeysUseCase<T, P>,
+        passkey_login_use_case: PasskeyLoginUseCase<T, P>,
+    ) -> Self {
+        Self {
+            manage_passkeys_use_case,
+            passkey_login_use_case,
+        }
+    }
+
+    pub async fn login_options(&self) -> impl Responder {
+        match self.passkey_login_use_case.options().await {
+            Ok(options) => HttpResponse::Ok().json(options),
+            Err(e) => error_response("Failed to start passkey sign-in", &*e),
+        }
+    }
+
+    pub async fn login(&self, req: HttpRequest, login_dto: web::Json<PasskeyLoginDto>) -> impl Responder {
+        match self.passkey_login_use_case.execute(login_dto.into_inner(), &client_info(&req)).await {
+            Ok(token) => HttpResponse::Ok().json(token),
+            Err(e) => {
+                debug!("Passkey login failed: {}", e);
+                HttpResponse::Unauthorized().json(json!({
+                    "error": "Authentication failed",
+                    "message": e.to_string()
+                }))
+            }
+        }
+    }
+
+    pub async fn registration_options(&self, claims: Claims) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.manage_passkeys_use_case.registration_options(claims.sub).await {
+            Ok(options) => HttpResponse::Ok().json(options),
+            Err(e) => error_response("Failed to start passkey registration", &*e),
+        }
+    }
+
+    pub async fn register(&self, claims: Claims, register_dto: web::Json<RegisterPasskeyDto>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.manage_passkeys_use_case.register(claims.sub, register_dto.into_inner()).await {
+            Ok(passkey) => HttpResponse::Created().json(passkey),
+            Err(e) => error_response("Failed to register passkey", &*e),
+        }
+    }
+
+    pub async fn list(&self, claims: Claims) -> impl Responder {
+        match self.manage_passkeys_use_case.list(claims.sub).await {
+            Ok(passkeys) => HttpResponse::Ok().json(passkeys),
+            Err(e) => error_response("Failed to get passkeys", &*e),
+        }
+    }
+
+    pub async fn delete(&self, claims: Claims, passkey_id: web::Path<i32>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+
+        match self.manage_passkeys_use_case.delete(claims.sub, passkey_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to remove passkey", &*e),
+        }
+    }
+}
+
+pub fn configure<T: AuthRepository + 'static, P: PasskeyRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<PasskeyHandlers<T, P>>,
+) {
+    cfg.service(
+        web::scope("/auth/passkeys")
+            .route("/login/options", web::post().to(
+                |handlers: web::Data<PasskeyHandlers<T, P>>| async move {
+                    handlers.login_options().await
+                }
+            ))
+            .route("/login", web::post().to(
+                |handlers: web::Data<PasskeyHandlers<T, P>>, req: HttpRequest, login_dto: web::Json<PasskeyLoginDto>| async move {
+                    handlers.login(req, login_dto).await
+                }
+            ))
+            // Registering and managing passkeys needs a signed-in user
+            .service(
+                web::resource("/register/options")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::post().to(
+                        |handlers: web::Data<PasskeyHandlers<T, P>>, claims: Claims| async move {
+                            handlers.registration_options(claims).await
+                        }
+                    ))
+            )
+            .service(
+                web::resource("/register")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::post().to(
+                        |handlers: web::Data<PasskeyHandlers<T, P>>, claims: Claims, register_dto: web::Json<RegisterPasskeyDto>| async move {
+                            handlers.register(claims, register_dto).await
+                        }
+                    ))
+            )
+            .service(
+                web::resource("")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::get().to(
+                        |handlers: web::Data<PasskeyHandlers<T, P>>, claims: Claims| async move {
+                            handlers.list(claims).await
+                        }
+                    ))
+            )
+            .service(
+                web::resource("/{id}")
+                    .wrap(HttpAuthentication::bearer(validator))
+                    .route(web::delete().to(
+                        |handlers: web::Data<PasskeyHandlers<T, P>>, claims: Claims, id: web::Path<i32>| async move {
+                            handlers.delete(claims, id).await
+                        }
+                    ))
+            )
+    );
+}
@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::registration_use_cases::{ManageInvitesUseCase, ReviewRegistrationsUseCase};
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::registration::CreateInviteDto;
+use crate::domain::repositories::registration_repository::RegistrationRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct RegistrationHandlers<G: RegistrationRepository, R: RoleRepository> {
+    manage_invites_use_case: ManageInvitesUseCase<G, R>,
+    review_registrations_use_case: ReviewRegistrationsUseCase<G, R>,
+}
+
+impl<G: RegistrationRepository, R: RoleRepository> RegistrationHandlers<G, R> {
+    pub fn new(
+        manage_invites_use_case: ManageInvitesUseCase<G, R>,
+        review_registrations_use_case: ReviewRegistrationsUseCase<G, R>,
+    ) -> Self {
+        Self {
+            manage_invites_use_case,
+            review_registrations_use_case,
+        }
+    }
+
+    pub async fn create_invite(&self, claims: Claims, invite_dto: web::Json<CreateInviteDto>) -> impl Responder {
+        match self.manage_invites_use_case.create(claims.sub, invite_dto.into_inner()).await {
+            Ok(invite) => HttpResponse::Created().json(invite),
+            Err(e) => error_response("Failed to create invite", &*e),
+        }
+    }
+
+    pub async fn list_invites(&self, claims: Claims) -> impl Responder {
+        match self.manage_invites_use_case.list(claims.sub).await {
+            Ok(invites) => HttpResponse::Ok().json(invites),
+            Err(e) => error_response("Failed to get invites", &*e),
+        }
+    }
+
+    pub async fn revoke_invite(&self, claims: Claims, invite_id: web::Path<i32>) -> impl Responder {
+        match self.manage_invites_use_case.revoke(claims.sub, invite_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to revoke invite", &*e),
+        }
+    }
+
+    pub async fn list_pending(&self, claims: Claims) -> impl Responder {
+        match self.review_registrations_use_case.list_pending(claims.sub).await {
+            Ok(users) => HttpResponse::Ok().json(users),
+            Err(e) => error_response("Failed to get pending registrations", &*e),
+        }
+    }
+
+    pub async fn approve(&self, claims: Claims, user_id: web::Path<i32>) -> impl Responder {
+        match self.review_registrations_use_case.approve(claims.sub, user_id.into_inner()).await {
+            Ok(user) => HttpResponse::Ok().json(user),
+            Err(e) => error_response("Failed to approve registration", &*e),
+        }
+    }
+
+    pub async fn reject(&self, claims: Claims, user_id: web::Path<i32>) -> impl Responder {
+        match self.review_registrations_use_case.reject(claims.sub, user_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to reject registration", &*e),
+        }
+    }
+}
+
+pub fn configure<G: RegistrationRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<RegistrationHandlers<G, R>>,
+) {
+    cfg.service(
+        web::scope("/registrations")
+            .route("/invites", web::get().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims| async move {
+                handlers.list_invites(claims).await
+            }))
+            .route("/invites", web::post().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims, invite_dto: web::Json<CreateInviteDto>| async move {
+                handlers.create_invite(claims, invite_dto).await
+            }))
+            .route("/invites/{id}", web::delete().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.revoke_invite(claims, id).await
+            }))
+            .route("/pending", web::get().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims| async move {
+                handlers.list_pending(claims).await
+            }))
+            .route("/pending/{id}/approve", web::post().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.approve(claims, id).await
+            }))
+            .route("/pending/{id}/reject", web::post().to(move |handlers: web::Data<RegistrationHandlers<G, R>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.reject(claims, id).await
+            }))
+    );
+}
@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::audit_use_cases::ListAuditEventsUseCase;
+use crate::application::use_cases::login_attempt_use_cases::ListLockoutsUseCase;
+use crate::domain::entities::audit::AuditEventQuery;
+use crate::domain::entities::auth::Claims;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::presentation::handlers::errors::error_response;
+
+pub struct SecurityHandlers<L: LoginAttemptRepository, R: RoleRepository> {
+    list_lockouts_use_case: ListLockoutsUseCase<L, R>,
+    list_audit_events_use_case: ListAuditEventsUseCase<R>,
+}
+
+impl<L: LoginAttemptRepository, R: RoleRepository> SecurityHandlers<L, R> {
+    pub fn new(list_lockouts_use_case: ListLockoutsUseCase<L, R>, list_audit_events_use_case: ListAuditEventsUseCase<R>) -> Self {
+        Self {
+            list_lockouts_use_case,
+            list_audit_events_use_case,
+        }
+    }
+
+    pub async fn list_lockouts(&self, claims: Claims) -> impl Responder {
+        match self.list_lockouts_use_case.execute(claims.sub).await {
+            Ok(lockouts) => HttpResponse::Ok().json(lockouts),
+            Err(e) => error_response("Failed to get login lockouts", &*e),
+        }
+    }
+
+    pub async fn list_audit_events(&self, claims: Claims, query: web::Query<AuditEventQuery>) -> impl Responder {
+        match self.list_audit_events_use_case.execute(claims.sub, query.into_inner()).await {
+            Ok(events) => HttpResponse::Ok().json(events),
+            Err(e) => error_response("Failed to get audit events", &*e),
+        }
+    }
+}
+
+pub fn configure<L: LoginAttemptRepository + 'static, R: RoleRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<SecurityHandlers<L, R>>,
+) {
+    cfg.service(
+        web::scope("/security")
+            .route("/lockouts", web::get().to(move |handlers: web::Data<SecurityHandlers<L, R>>, claims: Claims| async move {
+                handlers.list_lockouts(claims).await
+            }))
+            .route("/audit-events", web::get().to(move |handlers: web::Data<SecurityHandlers<L, R>>, claims: Claims, query: web::Query<AuditEventQuery>| async move {
+                handlers.list_audit_events(claims, query).await
+            }))
+    );
+}
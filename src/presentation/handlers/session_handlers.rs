@@ -0,0 +1,48 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::application::use_cases::session_use_cases::ManageSessionsUseCase;
+use crate::domain::entities::auth::Claims;
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+
+pub struct SessionHandlers<T: AuthRepository> {
+    manage_sessions_use_case: ManageSessionsUseCase<T>,
+}
+
+impl<T: AuthRepository> SessionHandlers<T> {
+    pub fn new(manage_sessions_use_case: ManageSessionsUseCase<T>) -> Self {
+        Self { manage_sessions_use_case }
+    }
+
+    pub async fn list(&self, claims: Claims) -> impl Responder {
+        match self.manage_sessions_use_case.list(claims.sub, &claims.jti).await {
+            Ok(sessions) => HttpResponse::Ok().json(sessions),
+            Err(e) => error_response("Failed to get sessions", &*e),
+        }
+    }
+
+    pub async fn revoke(&self, claims: Claims, session_id: web::Path<i32>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+        match self.manage_sessions_use_case.revoke(claims.sub, session_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => error_response("Failed to revoke session", &*e),
+        }
+    }
+}
+
+pub fn configure<T: AuthRepository + 'static>(
+    cfg: &mut web::ServiceConfig,
+    _handlers: web::Data<SessionHandlers<T>>,
+) {
+    cfg.service(
+        web::scope("/sessions")
+            .route("", web::get().to(move |handlers: web::Data<SessionHandlers<T>>, claims: Claims| async move {
+                handlers.list(claims).await
+            }))
+            .route("/{id}", web::delete().to(move |handlers: web::Data<SessionHandlers<T>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.revoke(claims, id).await
+            }))
+    );
+}
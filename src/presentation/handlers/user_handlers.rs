@@ -1,12 +1,17 @@
-use actix_web::{web, HttpResponse, Responder, dev::Payload, FromRequest, HttpMessage};
-use serde_json::json;
+use actix_web::{web, HttpRequest, HttpResponse, Responder, dev::Payload, FromRequest, HttpMessage};
 use std::future::{ready, Ready};
-use futures::future::LocalBoxFuture;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use crate::domain::entities::auth::Claims;
+use crate::application::use_cases::role_use_cases::ADMIN_ROLES;
 use crate::application::use_cases::user_use_cases::{CreateUserUseCase, ListUsersUseCase, GetUserByIdUseCase, UpdateUserUseCase, DeleteUserUseCase};
+use crate::domain::repositories::auth_repository::AuthRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
 use crate::domain::repositories::user_repository::UserRepository;
-use crate::domain::entities::user::{CreateUserDto, UpdateUserDto};
+use crate::domain::entities::user::{AdminUserResponse, CreateUserDto, UpdateUserDto, User, UserResponse};
+use crate::domain::services::validation::ValidationErrors;
+use crate::presentation::handlers::client_info::client_info;
+use crate::presentation::handlers::errors::error_response;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
 
 impl FromRequest for Claims {
     type Error = actix_web::Error;
@@ -20,21 +25,39 @@ impl FromRequest for Claims {
     }
 }
 
-pub struct UserHandlers<T: UserRepository> {
+/// A user as returned by the API: the full view for admins and for the user
+/// themselves, the public one for everybody else. Neither carries the password hash.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum UserView {
+    Full(AdminUserResponse),
+    Public(UserResponse),
+}
+
+pub(crate) fn user_view(claims: &Claims, user: User) -> UserView {
+    let is_admin = claims.roles.iter().any(|role| ADMIN_ROLES.contains(&role.as_str()));
+    if is_admin || claims.sub == user.id {
+        UserView::Full(user.into())
+    } else {
+        UserView::Public(user.into())
+    }
+}
+
+pub struct UserHandlers<T: UserRepository, A: AuthRepository, R: RoleRepository> {
     get_user_use_case: GetUserByIdUseCase<T>,
-    create_user_use_case: CreateUserUseCase<T>,
+    create_user_use_case: CreateUserUseCase<A, R>,
     list_users_use_case: ListUsersUseCase<T>,
-    update_user_use_case: UpdateUserUseCase<T>,
-    delete_user_use_case: DeleteUserUseCase<T>,
+    update_user_use_case: UpdateUserUseCase<T, A, R>,
+    delete_user_use_case: DeleteUserUseCase<T, R>,
 }
 
-impl<T: UserRepository> UserHandlers<T> {
+impl<T: UserRepository, A: AuthRepository, R: RoleRepository> UserHandlers<T, A, R> {
     pub fn new(
         get_user_use_case: GetUserByIdUseCase<T>,
-        create_user_use_case: CreateUserUseCase<T>,
+        create_user_use_case: CreateUserUseCase<A, R>,
         list_users_use_case: ListUsersUseCase<T>,
-        update_user_use_case: UpdateUserUseCase<T>,
-        delete_user_use_case: DeleteUserUseCase<T>,
+        update_user_use_case: UpdateUserUseCase<T, A, R>,
+        delete_user_use_case: DeleteUserUseCase<T, R>,
     ) -> Self {
         Self {
             get_user_use_case,
@@ -45,78 +68,85 @@ impl<T: UserRepository> UserHandlers<T> {
         }
     }
 
-    pub async fn get_user(&self, user_id: web::Path<i32>) -> impl Responder {
+    pub async fn get_user(&self, claims: Claims, user_id: web::Path<i32>) -> impl Responder {
         match self.get_user_use_case.execute(user_id.into_inner()).await {
-            Ok(user) => HttpResponse::Ok().json(user),
+            Ok(user) => HttpResponse::Ok().json(user_view(&claims, user)),
             Err(_) => HttpResponse::NotFound().finish(),
         }
     }
 
-    pub async fn create_user(&self, user_dto: web::Json<CreateUserDto>) -> impl Responder {
-        match self.create_user_use_case.execute(user_dto.into_inner()).await {
-            Ok(user) => HttpResponse::Created().json(user),
+    pub async fn create_user(&self, req: HttpRequest, claims: Claims, user_dto: web::Json<CreateUserDto>) -> impl Responder {
+        match self.create_user_use_case.execute(claims.sub, user_dto.into_inner(), &client_info(&req)).await {
+            Ok(user) => HttpResponse::Created().json(user_view(&claims, user)),
+            Err(e) if e.is::<ValidationErrors>() || e.is::<std::io::Error>() => error_response("Failed to create user", &*e),
             Err(_) => HttpResponse::InternalServerError().finish(),
         }
     }
 
-    pub async fn list_users(&self) -> impl Responder {
+    pub async fn list_users(&self, claims: Claims) -> impl Responder {
         match self.list_users_use_case.execute().await {
-            Ok(users) => HttpResponse::Ok().json(users),
+            Ok(users) => HttpResponse::Ok().json(
+                users.into_iter().map(|user| user_view(&claims, user)).collect::<Vec<_>>(),
+            ),
             Err(_) => HttpResponse::InternalServerError().finish(),
         }
     }
 
-    pub async fn update_user(&self, user_id: web::Path<i32>, user_dto: web::Json<UpdateUserDto>) -> impl Responder {
-        match self.update_user_use_case.execute(user_id.into_inner(), user_dto.into_inner()).await {
-            Ok(user) => HttpResponse::Ok().json(user),
+    pub async fn update_user(&self, req: HttpRequest, claims: Claims, user_id: web::Path<i32>, user_dto: web::Json<UpdateUserDto>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+        match self.update_user_use_case.execute(claims.sub, user_id.into_inner(), user_dto.into_inner(), &client_info(&req)).await {
+            Ok(user) => HttpResponse::Ok().json(user_view(&claims, user)),
+            Err(e) if e.is::<ValidationErrors>() || e.is::<std::io::Error>() => error_response("Failed to update user", &*e),
             Err(_) => HttpResponse::NotFound().finish(),
         }
     }
 
-    pub async fn delete_user(&self, user_id: web::Path<i32>) -> impl Responder {
-        match self.delete_user_use_case.execute(user_id.into_inner()).await {
+    pub async fn delete_user(&self, req: HttpRequest, claims: Claims, user_id: web::Path<i32>) -> impl Responder {
+        if let Some(response) = forbid_impersonation(&claims) {
+            return response;
+        }
+        match self.delete_user_use_case.execute(claims.sub, user_id.into_inner(), &client_info(&req)).await {
             Ok(_) => HttpResponse::NoContent().finish(),
+            Err(e) if e.is::<std::io::Error>() => error_response("Failed to delete user", &*e),
             Err(_) => HttpResponse::NotFound().finish(),
         }
     }
 
     pub async fn get_profile(&self, claims: Claims) -> impl Responder {
         match self.get_user_use_case.execute(claims.sub).await {
-            Ok(user) => HttpResponse::Ok().json(json!({
-                "id": user.id,
-                "username": user.username,
-                "email": user.email,
-            })),
+            Ok(user) => HttpResponse::Ok().json(AdminUserResponse::from(user)),
             Err(_) => HttpResponse::NotFound().finish(),
         }
     }
 }
 
-pub fn configure<T: UserRepository + 'static>(
+pub fn configure<T: UserRepository + 'static, A: AuthRepository + 'static, R: RoleRepository + 'static>(
     cfg: &mut web::ServiceConfig,
-    _handlers: web::Data<UserHandlers<T>>,
+    _handlers: web::Data<UserHandlers<T, A, R>>,
 ) {
     cfg.service(
         web::scope("/user")
             // First, define the static routes
-            .route("/me", web::get().to(move |handlers: web::Data<UserHandlers<T>>, claims: Claims| async move {
+            .route("/me", web::get().to(move |handlers: web::Data<UserHandlers<T, A, R>>, claims: Claims| async move {
                 handlers.get_profile(claims).await
             }))
-            .route("", web::get().to(move |handlers: web::Data<UserHandlers<T>>| async move {
-                handlers.list_users().await
+            .route("", web::get().to(move |handlers: web::Data<UserHandlers<T, A, R>>, claims: Claims| async move {
+                handlers.list_users(claims).await
             }))
-            .route("", web::post().to(move |handlers: web::Data<UserHandlers<T>>, user_dto: web::Json<CreateUserDto>| async move {
-                handlers.create_user(user_dto).await
+            .route("", web::post().to(move |handlers: web::Data<UserHandlers<T, A, R>>, req: HttpRequest, claims: Claims, user_dto: web::Json<CreateUserDto>| async move {
+                handlers.create_user(req, claims, user_dto).await
             }))
             // Then, define the routes with parameters
-            .route("/{id}", web::get().to(move |handlers: web::Data<UserHandlers<T>>, id: web::Path<i32>| async move {
-                handlers.get_user(id).await
+            .route("/{id}", web::get().to(move |handlers: web::Data<UserHandlers<T, A, R>>, claims: Claims, id: web::Path<i32>| async move {
+                handlers.get_user(claims, id).await
             }))
-            .route("/{id}", web::put().to(move |handlers: web::Data<UserHandlers<T>>, id: web::Path<i32>, user_dto: web::Json<UpdateUserDto>| async move {
-                handlers.update_user(id, user_dto).await
+            .route("/{id}", web::put().to(move |handlers: web::Data<UserHandlers<T, A, R>>, req: HttpRequest, claims: Claims, id: web::Path<i32>, user_dto: web::Json<UpdateUserDto>| async move {
+                handlers.update_user(req, claims, id, user_dto).await
             }))
-            .route("/{id}", web::delete().to(move |handlers: web::Data<UserHandlers<T>>, id: web::Path<i32>| async move {
-                handlers.delete_user(id).await
+            .route("/{id}", web::delete().to(move |handlers: web::Data<UserHandlers<T, A, R>>, req: HttpRequest, claims: Claims, id: web::Path<i32>| async move {
+                handlers.delete_user(req, claims, id).await
             })),
     );
 }
\ No newline at end of file
@@ -6,12 +6,18 @@ use crate::infrastructure::websocket::{
     user_status_manager::UserStatusManager,
     realtime_message_manager::RealtimeMessageManager
 };
+use crate::application::use_cases::contact_use_cases::{CheckMessagePermissionUseCase, STRANGER_MESSAGES_DISABLED};
 use crate::domain::entities::message::WebSocketMessage;
+use crate::infrastructure::repositories::contact_repository::ContactRepositoryImpl;
+use crate::infrastructure::repositories::moderation_repository::ModerationRepositoryImpl;
+
+pub type MessagePermission = CheckMessagePermissionUseCase<ContactRepositoryImpl, ModerationRepositoryImpl>;
 
 pub struct WebSocketActor {
     user_id: i32,
     user_status_manager: Arc<UserStatusManager>,
     realtime_message_manager: Arc<RealtimeMessageManager>, // Changed to Arc
+    message_permission: Arc<MessagePermission>,
 }
 
 impl Clone for WebSocketActor {
@@ -20,6 +26,7 @@ impl Clone for WebSocketActor {
             user_id: self.user_id,
             user_status_manager: Arc::clone(&self.user_status_manager),
             realtime_message_manager: Arc::clone(&self.realtime_message_manager),
+            message_permission: Arc::clone(&self.message_permission),
         }
     }
 }
@@ -29,11 +36,13 @@ impl WebSocketActor {
         user_id: i32,
         user_status_manager: Arc<UserStatusManager>,
         realtime_message_manager: RealtimeMessageManager,
+        message_permission: Arc<MessagePermission>,
     ) -> Self {
         Self {
             user_id,
             user_status_manager,
             realtime_message_manager: Arc::new(realtime_message_manager),
+            message_permission,
         }
     }
 }
@@ -82,11 +91,23 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WebSocketActor {
                 match serde_json::from_str::<WebSocketMessage>(&text) {
                     Ok(websocket_msg) => {
                         match websocket_msg {
-                            WebSocketMessage::Chat { to_user_id, content } => {
+                            WebSocketMessage::Chat { to_user_id, content, .. } => {
                                 let realtime_manager = Arc::clone(&self.realtime_message_manager);
+                                let message_permission = Arc::clone(&self.message_permission);
                                 let from_user_id = self.user_id;
+                                let addr = ctx.address();
                                 actix::spawn(async move {
-                                    realtime_manager.send_message(from_user_id, to_user_id, content).await.ok();
+                                    match message_permission.execute(from_user_id, to_user_id).await {
+                                        Ok(true) => {
+                                            realtime_manager.send_message(from_user_id, to_user_id, content).await.ok();
+                                        },
+                                        Ok(false) => addr.do_send(WebSocketMessage::Error {
+                                            message: STRANGER_MESSAGES_DISABLED.to_string(),
+                                        }),
+                                        Err(e) => addr.do_send(WebSocketMessage::Error {
+                                            message: e.to_string(),
+                                        }),
+                                    }
                                 });
                             },
                             WebSocketMessage::CallOffer { to_user_id, sdp } => {
@@ -125,7 +146,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WebSocketActor {
                                     let _manager = realtime_manager;
                                 });
                             },
-                            WebSocketMessage::Status { .. } | WebSocketMessage::Error { .. } => {
+                            WebSocketMessage::Status { .. } | WebSocketMessage::Error { .. } | WebSocketMessage::Announcement { .. } => {
                                 ctx.text(serde_json::json!({
                                     "type": "error",
                                     "message": "Invalid message type for client"
@@ -159,12 +180,14 @@ pub async fn ws_route(
     path: web::Path<i32>,
     user_status_manager: web::Data<Arc<UserStatusManager>>,
     realtime_message_manager: web::Data<RealtimeMessageManager>,
+    message_permission: web::Data<MessagePermission>,
 ) -> Result<HttpResponse, Error> {
     let user_id = path.into_inner();
     let actor = WebSocketActor::new(
         user_id,
         user_status_manager.get_ref().clone(),
         realtime_message_manager.get_ref().clone(),
+        message_permission.into_inner(),
     );
     let resp = ws::start(actor, &req, stream)?;
     Ok(resp)
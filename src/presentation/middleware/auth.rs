@@ -1,25 +1,79 @@
-use actix_web::error::ErrorUnauthorized;
-use actix_web::{Error, dev::ServiceRequest, HttpMessage};
+use std::io::ErrorKind;
+use actix_web::error::{ErrorForbidden, ErrorInternalServerError, ErrorUnauthorized};
+use actix_web::http::Method;
+use actix_web::{web, Error, dev::ServiceRequest, HttpMessage};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use crate::domain::entities::auth::Claims;
+use crate::application::use_cases::api_key_use_cases::AuthenticateApiKeyUseCase;
+use crate::application::use_cases::auth_use_cases::ValidateSessionUseCase;
+use crate::domain::entities::api_key::{API_KEY_PREFIX, SCOPE_READ, SCOPE_WRITE};
+use crate::domain::services::access_token::AccessTokenService;
+use crate::infrastructure::repositories::api_key_repository::ApiKeyRepositoryImpl;
+use crate::infrastructure::repositories::auth_repository::AuthRepositoryImpl;
+
+pub type SessionValidation = ValidateSessionUseCase<AuthRepositoryImpl>;
+pub type ApiKeyAuthentication = AuthenticateApiKeyUseCase<ApiKeyRepositoryImpl, AuthRepositoryImpl>;
+
+/// `read` keys may only use safe methods; `write` keys may do anything.
+pub(crate) fn scope_allows(scopes: &[String], method: &Method) -> bool {
+    let required = match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => &[SCOPE_READ, SCOPE_WRITE][..],
+        _ => &[SCOPE_WRITE][..],
+    };
+    scopes.iter().any(|scope| required.contains(&scope.as_str()))
+}
+
+async fn api_key_validator(req: ServiceRequest, key: &str) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let Some(api_key_authentication) = req.app_data::<web::Data<ApiKeyAuthentication>>().cloned() else {
+        return Err((ErrorUnauthorized("API keys are not accepted"), req));
+    };
+
+    match api_key_authentication.execute(key).await {
+        Ok(Some(claims)) => {
+            if !scope_allows(claims.scopes.as_deref().unwrap_or_default(), req.method()) {
+                return Err((ErrorForbidden("API key scope does not allow this request"), req));
+            }
+
+            req.extensions_mut().insert(claims);
+            Ok(req)
+        },
+        Ok(None) => Err((ErrorUnauthorized("Invalid API key"), req)),
+        Err(e) => match e.downcast_ref::<std::io::Error>() {
+            Some(io_error) if io_error.kind() == ErrorKind::PermissionDenied => {
+                Err((ErrorForbidden(io_error.to_string()), req))
+            }
+            _ => Err((ErrorInternalServerError("Failed to validate API key"), req)),
+        },
+    }
+}
 
 #[allow(dead_code)]  // Added because the compiler can't detect usage through middleware configuration
 pub async fn validator(req: ServiceRequest, credentials: BearerAuth)
                        -> Result<ServiceRequest, (Error, ServiceRequest)> {
-    let secret_key = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set");
-    let token = credentials.token();
-
-    match decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret_key.as_bytes()),
-        &Validation::default(),
-    ) {
-        Ok(token_data) => {
+    // API keys are opaque and looked up in the database; everything else must be a JWT
+    if credentials.token().starts_with(API_KEY_PREFIX) {
+        return api_key_validator(req, credentials.token()).await;
+    }
+
+    let Some(access_tokens) = req.app_data::<web::Data<AccessTokenService>>().cloned() else {
+        return Err((ErrorInternalServerError("Token verification is not configured"), req));
+    };
+
+    match access_tokens.verify(credentials.token()) {
+        Ok(claims) => {
+            // Reject tokens whose session was revoked (e.g. by a password reset)
+            let Some(session_validation) = req.app_data::<web::Data<SessionValidation>>().cloned() else {
+                return Err((ErrorInternalServerError("Session validation is not configured"), req));
+            };
+            match session_validation.execute(&claims).await {
+                Ok(true) => {}
+                Ok(false) => return Err((ErrorUnauthorized("Session has been revoked"), req)),
+                Err(_) => return Err((ErrorInternalServerError("Failed to validate session"), req)),
+            }
+
             // Add claims to request extensions for use in handlers
-            req.extensions_mut().insert(token_data.claims);
+            req.extensions_mut().insert(claims);
             Ok(req)
         },
         Err(_) => Err((ErrorUnauthorized("Invalid token"), req)),
     }
-}
\ No newline at end of file
+}
@@ -1 +1,4 @@
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+// Wired up by the binary only
+#[allow(dead_code)]
+pub mod request_id;
\ No newline at end of file
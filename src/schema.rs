@@ -10,6 +10,61 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         default_avatar_id -> Nullable<Int4>,
+        allow_messages_from_strangers -> Bool,
+    }
+}
+
+diesel::table! {
+    announcement_receipts (announcement_id, user_id) {
+        announcement_id -> Int4,
+        user_id -> Int4,
+        delivered_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    announcements (id) {
+        id -> Int4,
+        author_id -> Nullable<Int4>,
+        content -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Int4,
+        #[max_length = 32]
+        action -> Varchar,
+        #[max_length = 16]
+        outcome -> Varchar,
+        actor_id -> Nullable<Int4>,
+        target_user_id -> Nullable<Int4>,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        #[max_length = 512]
+        user_agent -> Nullable<Varchar>,
+        #[max_length = 500]
+        details -> Nullable<Varchar>,
+        created_at -> Timestamp,
     }
 }
 
@@ -24,6 +79,75 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    contact_requests (id) {
+        id -> Int4,
+        requester_id -> Int4,
+        addressee_id -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invites (id) {
+        id -> Int4,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        created_by -> Nullable<Int4>,
+        #[max_length = 200]
+        note -> Nullable<Varchar>,
+        max_uses -> Int4,
+        use_count -> Int4,
+        expires_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_attempts (id) {
+        id -> Int4,
+        #[max_length = 255]
+        username -> Varchar,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        succeeded -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_lockouts (id) {
+        id -> Int4,
+        #[max_length = 255]
+        username -> Nullable<Varchar>,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        failed_attempts -> Int4,
+        locked_until -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    message_reports (id) {
+        id -> Int4,
+        message_id -> Int4,
+        reporter_id -> Int4,
+        #[max_length = 30]
+        reason -> Varchar,
+        details -> Nullable<Text>,
+        #[max_length = 20]
+        status -> Varchar,
+        resolved_by -> Nullable<Int4>,
+        resolved_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     messages (id) {
         id -> Int4,
@@ -32,6 +156,88 @@ diesel::table! {
         content -> Text,
         is_read -> Bool,
         created_at -> Timestamp,
+        is_hidden -> Bool,
+        deliver_at -> Nullable<Timestamp>,
+        expires_at -> Nullable<Timestamp>,
+        is_delivered -> Bool,
+    }
+}
+
+diesel::table! {
+    moderation_actions (id) {
+        id -> Int4,
+        moderator_id -> Nullable<Int4>,
+        #[max_length = 30]
+        action -> Varchar,
+        target_user_id -> Nullable<Int4>,
+        target_message_id -> Nullable<Int4>,
+        report_id -> Nullable<Int4>,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notification_preferences (id) {
+        id -> Int4,
+        user_id -> Int4,
+        counterpart_user_id -> Nullable<Int4>,
+        group_id -> Nullable<Int4>,
+        #[max_length = 20]
+        mode -> Varchar,
+        muted_until -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oauth_authorization_codes (id) {
+        id -> Int4,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        #[max_length = 64]
+        client_id -> Varchar,
+        user_id -> Int4,
+        redirect_uri -> Text,
+        scope -> Text,
+        #[max_length = 128]
+        code_challenge -> Varchar,
+        #[max_length = 255]
+        nonce -> Nullable<Varchar>,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oauth_clients (id) {
+        id -> Int4,
+        #[max_length = 64]
+        client_id -> Varchar,
+        #[max_length = 64]
+        client_secret_hash -> Nullable<Varchar>,
+        #[max_length = 100]
+        name -> Varchar,
+        redirect_uris -> Array<Text>,
+        grant_types -> Array<Text>,
+        scopes -> Array<Text>,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oidc_login_states (id) {
+        id -> Int4,
+        #[max_length = 50]
+        provider -> Varchar,
+        #[max_length = 64]
+        state_hash -> Varchar,
+        #[max_length = 128]
+        code_verifier -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
     }
 }
 
@@ -47,6 +253,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_identities (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 50]
+        provider -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_recovery_codes (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     user_roles (id) {
         id -> Int4,
@@ -56,24 +287,149 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_sessions (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        token_id -> Varchar,
+        #[max_length = 512]
+        user_agent -> Nullable<Varchar>,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        expires_at -> Timestamp,
+        last_active_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        impersonator_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    user_suspensions (id) {
+        id -> Int4,
+        user_id -> Int4,
+        suspended_by -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        suspended_until -> Nullable<Timestamp>,
+        lifted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 30]
+        purpose -> Varchar,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_totp (user_id) {
+        user_id -> Int4,
+        #[max_length = 64]
+        secret -> Varchar,
+        confirmed_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
         username -> Varchar,
         password -> Varchar,
         email -> Varchar,
+        email_verified_at -> Nullable<Timestamp>,
+        pending_approval -> Bool,
+    }
+}
+
+diesel::table! {
+    webauthn_challenges (id) {
+        id -> Int4,
+        user_id -> Nullable<Int4>,
+        #[max_length = 20]
+        ceremony -> Varchar,
+        #[max_length = 64]
+        challenge_hash -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    webauthn_credentials (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 1400]
+        credential_id -> Varchar,
+        public_key -> Bytea,
+        algorithm -> Int4,
+        sign_count -> Int8,
+        #[max_length = 100]
+        name -> Varchar,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::joinable!(accounts -> users (user_id));
+diesel::joinable!(announcement_receipts -> announcements (announcement_id));
+diesel::joinable!(announcement_receipts -> users (user_id));
+diesel::joinable!(announcements -> users (author_id));
+diesel::joinable!(api_keys -> users (user_id));
+diesel::joinable!(invites -> users (created_by));
+diesel::joinable!(message_reports -> messages (message_id));
+diesel::joinable!(moderation_actions -> message_reports (report_id));
+diesel::joinable!(moderation_actions -> messages (target_message_id));
+diesel::joinable!(oauth_authorization_codes -> users (user_id));
+diesel::joinable!(oauth_clients -> users (created_by));
+diesel::joinable!(user_identities -> users (user_id));
+diesel::joinable!(user_recovery_codes -> users (user_id));
 diesel::joinable!(user_roles -> roles (role_id));
 diesel::joinable!(user_roles -> users (user_id));
+diesel::joinable!(user_sessions -> users (user_id));
+diesel::joinable!(user_tokens -> users (user_id));
+diesel::joinable!(user_totp -> users (user_id));
+diesel::joinable!(webauthn_challenges -> users (user_id));
+diesel::joinable!(webauthn_credentials -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     accounts,
+    announcement_receipts,
+    announcements,
+    api_keys,
+    audit_events,
     avatars,
+    contact_requests,
+    invites,
+    login_attempts,
+    login_lockouts,
+    message_reports,
     messages,
+    moderation_actions,
+    notification_preferences,
+    oauth_authorization_codes,
+    oauth_clients,
+    oidc_login_states,
     roles,
+    user_identities,
+    user_recovery_codes,
     user_roles,
+    user_sessions,
+    user_suspensions,
+    user_tokens,
+    user_totp,
     users,
+    webauthn_challenges,
+    webauthn_credentials,
 );
@@ -0,0 +1,81 @@
+// File: src/tests/access_token_test.rs
+
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::domain::entities::auth::Claims;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::signing_key::{SigningKey, SigningKeys};
+use crate::tests::fixtures::{access_tokens, user};
+
+const SECRET: &str = "access-token-test-secret";
+
+fn claims(service: &AccessTokenService) -> Claims {
+    service.claims_for(&user(7, "alice"), vec!["user".to_string()])
+}
+
+/// Signs `claims` with the test key, whatever they say.
+fn token(service: &AccessTokenService, claims: &Claims) -> String {
+    service.sign(claims).unwrap().access_token
+}
+
+#[test]
+fn a_token_we_issued_verifies() {
+    let service = access_tokens(SECRET);
+    let issued = claims(&service);
+
+    let verified = service.verify(&token(&service, &issued)).unwrap();
+
+    assert_eq!(verified.sub, 7);
+    assert_eq!(verified.jti, issued.jti);
+}
+
+#[test]
+fn tokens_for_another_issuer_or_audience_are_rejected() {
+    let service = access_tokens(SECRET);
+
+    let other_issuer = Claims { iss: "https://other.example.com".to_string(), ..claims(&service) };
+    // ID tokens are signed with the same keys but name the OAuth client as audience
+    let other_audience = Claims { aud: "notes-app".to_string(), ..claims(&service) };
+
+    assert!(service.verify(&token(&service, &other_issuer)).is_err());
+    assert!(service.verify(&token(&service, &other_audience)).is_err());
+}
+
+#[test]
+fn tokens_are_rejected_before_nbf_and_after_exp() {
+    let service = access_tokens(SECRET);
+    let now = Utc::now().timestamp();
+
+    let not_yet_valid = Claims { nbf: now + 60, ..claims(&service) };
+    let expired = Claims { iat: now - 120, nbf: now - 120, exp: now - 60, ..claims(&service) };
+
+    assert!(service.verify(&token(&service, &not_yet_valid)).is_err());
+    assert!(service.verify(&token(&service, &expired)).is_err());
+}
+
+#[test]
+fn leeway_tolerates_small_clock_skew() {
+    let signing_keys = Arc::new(SigningKeys::new(SigningKey::derive_from_secret(SECRET).unwrap(), vec![]).unwrap());
+    let service = AccessTokenService::new(
+        signing_keys,
+        "http://localhost:8080".to_string(),
+        "rust-crate".to_string(),
+        30,
+    );
+    let now = Utc::now().timestamp();
+
+    let slightly_early = Claims { nbf: now + 10, ..claims(&service) };
+    let too_early = Claims { nbf: now + 60, ..claims(&service) };
+
+    assert!(service.verify(&token(&service, &slightly_early)).is_ok());
+    assert!(service.verify(&token(&service, &too_early)).is_err());
+}
+
+#[test]
+fn tokens_signed_with_another_key_are_rejected() {
+    let service = access_tokens(SECRET);
+    let forger = access_tokens("another-secret");
+
+    assert!(service.verify(&token(&forger, &claims(&forger))).is_err());
+}
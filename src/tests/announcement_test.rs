@@ -0,0 +1,117 @@
+// File: src/tests/announcement_test.rs
+
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::application::use_cases::announcement_use_cases::{GetPendingAnnouncementsUseCase, PublishAnnouncementUseCase};
+use crate::domain::entities::announcement::{Announcement, CreateAnnouncementDto};
+use crate::domain::repositories::announcement_repository::MockAnnouncementRepository;
+use crate::tests::fixtures::{error_kind, StubRoleRepository};
+
+const ADMIN_ID: i32 = 1;
+const MEMBER_ID: i32 = 2;
+
+fn announcement(id: i32, created_at: NaiveDateTime) -> Announcement {
+    Announcement {
+        id,
+        author_id: Some(ADMIN_ID),
+        content: format!("announcement {}", id),
+        created_at,
+    }
+}
+
+fn publish_use_case(repository: MockAnnouncementRepository) -> PublishAnnouncementUseCase<MockAnnouncementRepository, StubRoleRepository> {
+    let roles = StubRoleRepository::default().with_role(ADMIN_ID, "admin");
+    PublishAnnouncementUseCase::new(Arc::new(repository), Arc::new(roles))
+}
+
+fn content(text: &str) -> CreateAnnouncementDto {
+    CreateAnnouncementDto { content: text.to_string() }
+}
+
+#[tokio::test]
+async fn admins_publish_trimmed_announcements() {
+    let mut repository = MockAnnouncementRepository::new();
+    repository.expect_create()
+        .withf(|author_id, content| *author_id == ADMIN_ID && content == "Maintenance tonight")
+        .times(1)
+        .returning(|_, content| Ok(Announcement { content, ..announcement(5, Utc::now().naive_utc()) }));
+
+    let published = publish_use_case(repository).execute(ADMIN_ID, content("  Maintenance tonight \n")).await.unwrap();
+
+    assert_eq!(published.content, "Maintenance tonight");
+}
+
+#[tokio::test]
+async fn members_and_empty_announcements_are_refused() {
+    let denied = publish_use_case(MockAnnouncementRepository::new()).execute(MEMBER_ID, content("Hello")).await;
+    let empty = publish_use_case(MockAnnouncementRepository::new()).execute(ADMIN_ID, content("   ")).await;
+
+    assert_eq!(error_kind(&*denied.unwrap_err()), Some(ErrorKind::PermissionDenied));
+    assert_eq!(error_kind(&*empty.unwrap_err()), Some(ErrorKind::InvalidInput));
+}
+
+#[tokio::test]
+async fn live_deliveries_are_recorded_for_the_announcement() {
+    let mut repository = MockAnnouncementRepository::new();
+    repository.expect_mark_delivered()
+        .withf(|announcement_ids, user_ids| *announcement_ids == vec![5] && *user_ids == vec![2, 3])
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    publish_use_case(repository).mark_delivered(5, vec![2, 3]).await.unwrap();
+}
+
+/// Announcements and receipts kept in memory, filtered like the database query.
+fn replay_repository(announcements: Vec<Announcement>) -> MockAnnouncementRepository {
+    let receipts: Arc<Mutex<HashSet<(i32, i32)>>> = Arc::default();
+    let mut repository = MockAnnouncementRepository::new();
+
+    let delivered = receipts.clone();
+    repository.expect_find_undelivered().returning(move |user_id, since| {
+        let delivered = delivered.lock().unwrap();
+        Ok(announcements.iter()
+            .filter(|announcement| announcement.created_at >= since && !delivered.contains(&(announcement.id, user_id)))
+            .cloned()
+            .collect())
+    });
+    repository.expect_mark_delivered().returning(move |announcement_ids, user_ids| {
+        let mut receipts = receipts.lock().unwrap();
+        for announcement_id in &announcement_ids {
+            receipts.extend(user_ids.iter().map(|user_id| (*announcement_id, *user_id)));
+        }
+        Ok(())
+    });
+
+    repository
+}
+
+#[tokio::test]
+async fn missed_announcements_are_replayed_once() {
+    let now = Utc::now().naive_utc();
+    let repository = replay_repository(vec![
+        announcement(1, now - Duration::days(40)),
+        announcement(2, now - Duration::days(2)),
+        announcement(3, now - Duration::hours(1)),
+    ]);
+    let use_case = GetPendingAnnouncementsUseCase::new(Arc::new(repository));
+
+    let pending: Vec<i32> = use_case.execute(MEMBER_ID).await.unwrap().iter().map(|announcement| announcement.id).collect();
+    assert_eq!(pending, vec![2, 3]);
+
+    assert!(use_case.execute(MEMBER_ID).await.unwrap().is_empty());
+    assert_eq!(use_case.execute(ADMIN_ID).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn nothing_is_marked_when_nothing_is_pending() {
+    let mut repository = MockAnnouncementRepository::new();
+    repository.expect_find_undelivered().returning(|_, _| Ok(vec![]));
+    repository.expect_mark_delivered().never();
+
+    let pending = GetPendingAnnouncementsUseCase::new(Arc::new(repository)).execute(MEMBER_ID).await.unwrap();
+
+    assert!(pending.is_empty());
+}
@@ -0,0 +1,148 @@
+// File: src/tests/api_key_test.rs
+
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use actix_web::http::Method;
+use chrono::Utc;
+
+use crate::application::use_cases::api_key_use_cases::{AuthenticateApiKeyUseCase, ManageApiKeysUseCase};
+use crate::application::use_cases::auth_use_cases::{ACCOUNT_PENDING_APPROVAL, ACCOUNT_SUSPENDED};
+use crate::domain::entities::api_key::{ApiKey, CreateApiKeyDto, API_KEY_PREFIX, SCOPE_READ, SCOPE_WRITE};
+use crate::domain::repositories::api_key_repository::MockApiKeyRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::presentation::middleware::auth::scope_allows;
+use crate::tests::fixtures::{access_tokens, error_kind, user};
+
+const SECRET: &str = "api-key-test-secret";
+const KEY: &str = "rca_test-key";
+const KEY_ID: i32 = 3;
+const USER_ID: i32 = 7;
+
+fn scopes(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn api_key(scopes: Vec<String>) -> ApiKey {
+    ApiKey {
+        id: KEY_ID,
+        user_id: USER_ID,
+        name: "deploy script".to_string(),
+        prefix: "rca_test-key".to_string(),
+        scopes,
+        expires_at: None,
+        last_used_at: None,
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+#[test]
+fn read_keys_are_limited_to_safe_methods() {
+    let read = scopes(&[SCOPE_READ]);
+
+    for method in [Method::GET, Method::HEAD, Method::OPTIONS] {
+        assert!(scope_allows(&read, &method), "{}", method);
+    }
+    for method in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+        assert!(!scope_allows(&read, &method), "{}", method);
+    }
+}
+
+#[test]
+fn write_keys_may_use_any_method() {
+    let write = scopes(&[SCOPE_WRITE]);
+
+    for method in [Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+        assert!(scope_allows(&write, &method), "{}", method);
+    }
+    assert!(!scope_allows(&[], &Method::GET));
+}
+
+/// Keys belonging to an active, approved user.
+fn authenticate_use_case(api_keys: MockApiKeyRepository) -> AuthenticateApiKeyUseCase<MockApiKeyRepository, MockAuthRepository> {
+    owner_use_case(api_keys, false, false)
+}
+
+fn owner_use_case(api_keys: MockApiKeyRepository, suspended: bool, pending_approval: bool) -> AuthenticateApiKeyUseCase<MockApiKeyRepository, MockAuthRepository> {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(Some(user(id, "alice"))));
+    repository.expect_is_suspended().returning(move |_| Ok(suspended));
+    repository.expect_is_pending_approval().returning(move |_| Ok(pending_approval));
+    repository.expect_find_role_names().returning(|_| Ok(vec!["user".to_string()]));
+
+    AuthenticateApiKeyUseCase::new(Arc::new(api_keys), repository, Arc::new(TokenService::new(SECRET)), access_tokens(SECRET))
+}
+
+#[tokio::test]
+async fn a_key_authenticates_as_its_owner_with_its_scopes() {
+    let key_hash = TokenService::new(SECRET).hash(KEY);
+    let mut api_keys = MockApiKeyRepository::new();
+    api_keys.expect_find_active_by_hash()
+        .withf(move |hash| *hash == key_hash)
+        .returning(|_| Ok(Some(api_key(scopes(&[SCOPE_READ])))));
+    api_keys.expect_record_use().withf(|id| *id == KEY_ID).times(1).returning(|_| Ok(()));
+
+    let claims = authenticate_use_case(api_keys).execute(KEY).await.unwrap().unwrap();
+
+    assert_eq!(claims.sub, USER_ID);
+    assert_eq!(claims.scopes, Some(scopes(&[SCOPE_READ])));
+    assert_eq!(claims.jti, format!("api-key:{}", KEY_ID));
+}
+
+#[tokio::test]
+async fn unknown_revoked_or_expired_keys_do_not_authenticate() {
+    let mut api_keys = MockApiKeyRepository::new();
+    api_keys.expect_find_active_by_hash().returning(|_| Ok(None));
+
+    assert!(authenticate_use_case(api_keys).execute(KEY).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn keys_of_suspended_or_unapproved_users_are_refused() {
+    for (suspended, pending_approval, message) in [(true, false, ACCOUNT_SUSPENDED), (false, true, ACCOUNT_PENDING_APPROVAL)] {
+        let mut api_keys = MockApiKeyRepository::new();
+        api_keys.expect_find_active_by_hash().returning(|_| Ok(Some(api_key(scopes(&[SCOPE_WRITE])))));
+        api_keys.expect_record_use().never();
+
+        let error = owner_use_case(api_keys, suspended, pending_approval).execute(KEY).await.unwrap_err();
+
+        assert_eq!(error.to_string(), message);
+        assert_eq!(error_kind(&*error), Some(ErrorKind::PermissionDenied));
+    }
+}
+
+#[tokio::test]
+async fn a_created_key_is_only_stored_hashed() {
+    let stored_hash = Arc::new(Mutex::new(None));
+    let mut api_keys = MockApiKeyRepository::new();
+    api_keys.expect_count_active().returning(|_| Ok(0));
+    let stored = stored_hash.clone();
+    api_keys.expect_create().times(1).returning(move |new_key| {
+        *stored.lock().unwrap() = Some(new_key.key_hash);
+        Ok(ApiKey { prefix: new_key.prefix, scopes: new_key.scopes, ..api_key(vec![]) })
+    });
+    let token_service = Arc::new(TokenService::new(SECRET));
+
+    let created = ManageApiKeysUseCase::new(Arc::new(api_keys), token_service.clone())
+        .create(USER_ID, CreateApiKeyDto { name: " deploy script ".to_string(), scopes: None, expires_in_days: Some(30) })
+        .await
+        .unwrap();
+
+    assert!(created.key.starts_with(API_KEY_PREFIX));
+    assert!(created.key.starts_with(&created.api_key.prefix));
+    assert_eq!(*stored_hash.lock().unwrap(), Some(token_service.hash(&created.key)));
+    assert_eq!(created.api_key.scopes, scopes(&[SCOPE_READ]));
+}
+
+#[tokio::test]
+async fn unknown_scopes_and_lifetimes_are_rejected() {
+    let use_case = ManageApiKeysUseCase::new(Arc::new(MockApiKeyRepository::new()), Arc::new(TokenService::new(SECRET)));
+
+    let admin_scope = CreateApiKeyDto { name: "key".to_string(), scopes: Some(scopes(&["admin"])), expires_in_days: None };
+    let too_long = CreateApiKeyDto { name: "key".to_string(), scopes: None, expires_in_days: Some(366) };
+
+    for dto in [admin_scope, too_long] {
+        let result = use_case.create(USER_ID, dto).await;
+        assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::InvalidInput));
+    }
+}
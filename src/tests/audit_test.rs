@@ -0,0 +1,129 @@
+// File: src/tests/audit_test.rs
+
+use std::sync::Arc;
+
+use crate::application::use_cases::audit_use_cases::ListAuditEventsUseCase;
+use crate::application::use_cases::auth_use_cases::ChangePasswordUseCase;
+use crate::domain::entities::audit::{AuditAction, AuditEventQuery, AuditOutcome};
+use crate::domain::entities::auth::ChangePasswordDto;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::audit_repository::MockAuditRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::validation::PasswordPolicy;
+use crate::tests::fixtures::StubRoleRepository;
+
+const USER_ID: i32 = 7;
+const ADMIN_ID: i32 = 1;
+
+fn client() -> ClientInfo {
+    ClientInfo {
+        user_agent: Some("audit-test".to_string()),
+        ip_address: Some("203.0.113.9".to_string()),
+    }
+}
+
+fn change_password_dto() -> ChangePasswordDto {
+    ChangePasswordDto {
+        current_password: "old password".to_string(),
+        new_password: "correct horse battery".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn password_change_is_audited() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_verify_password().returning(|_, _| Ok(true));
+    repository.expect_update_password().returning(|_, _| Ok(()));
+    repository.expect_revoke_tokens().returning(|_, _| Ok(()));
+
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event()
+        .withf(|event| {
+            event.action == AuditAction::PasswordChange
+                && event.outcome == AuditOutcome::Success
+                && event.actor_id == Some(USER_ID)
+                && event.target_user_id == Some(USER_ID)
+                && event.ip_address.as_deref() == Some("203.0.113.9")
+                && event.user_agent.as_deref() == Some("audit-test")
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    ChangePasswordUseCase::new(repository, Arc::new(PasswordPolicy::default()), Arc::new(audit))
+        .execute(USER_ID, change_password_dto(), &client())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn failed_password_change_is_audited_with_the_reason() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_verify_password().returning(|_, _| Ok(false));
+    repository.expect_update_password().never();
+
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event()
+        .withf(|event| {
+            event.action == AuditAction::PasswordChange
+                && event.outcome == AuditOutcome::Failure
+                && event.details.as_deref() == Some("Current password is incorrect")
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let result = ChangePasswordUseCase::new(repository, Arc::new(PasswordPolicy::default()), Arc::new(audit))
+        .execute(USER_ID, change_password_dto(), &client())
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn audit_write_failures_do_not_fail_the_action() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_verify_password().returning(|_, _| Ok(true));
+    repository.expect_update_password().returning(|_, _| Ok(()));
+    repository.expect_revoke_tokens().returning(|_, _| Ok(()));
+
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event().returning(|_| Err("database is down".into()));
+
+    let result = ChangePasswordUseCase::new(repository, Arc::new(PasswordPolicy::default()), Arc::new(audit))
+        .execute(USER_ID, change_password_dto(), &client())
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn only_admins_can_read_the_audit_log() {
+    let mut audit = MockAuditRepository::new();
+    audit.expect_find_events().never();
+
+    let result = ListAuditEventsUseCase::new(Arc::new(audit), Arc::new(StubRoleRepository::default().with_role(ADMIN_ID, "admin")))
+        .execute(USER_ID, AuditEventQuery::default())
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn audit_log_queries_are_capped() {
+    let mut audit = MockAuditRepository::new();
+    audit.expect_find_events()
+        .withf(|query, limit| query.action == Some(AuditAction::Login) && *limit == 500)
+        .times(1)
+        .returning(|_, _| Ok(vec![]));
+
+    let query = AuditEventQuery {
+        action: Some(AuditAction::Login),
+        limit: Some(100_000),
+        ..AuditEventQuery::default()
+    };
+    let events = ListAuditEventsUseCase::new(Arc::new(audit), Arc::new(StubRoleRepository::default().with_role(ADMIN_ID, "admin")))
+        .execute(ADMIN_ID, query)
+        .await
+        .unwrap();
+
+    assert!(events.is_empty());
+}
@@ -0,0 +1,51 @@
+// File: src/tests/client_info_test.rs
+
+use std::net::IpAddr;
+use actix_web::{test::TestRequest, web};
+
+use crate::presentation::handlers::client_info::{client_info, TrustedProxies};
+
+const PROXY: &str = "10.0.0.2";
+const CLIENT: &str = "203.0.113.9";
+
+fn request(peer: &str, forwarded_for: Option<&str>, trusted: &[&str]) -> TestRequest {
+    let mut request = TestRequest::default()
+        .peer_addr(format!("{}:40000", peer).parse().unwrap())
+        .app_data(web::Data::new(TrustedProxies::new(
+            trusted.iter().map(|address| address.parse::<IpAddr>().unwrap()).collect(),
+        )));
+    if let Some(forwarded_for) = forwarded_for {
+        request = request.insert_header(("X-Forwarded-For", forwarded_for));
+    }
+    request
+}
+
+fn ip_address(request: TestRequest) -> Option<String> {
+    client_info(&request.to_http_request()).ip_address
+}
+
+#[actix_web::test]
+async fn clients_cannot_spoof_their_address() {
+    assert_eq!(ip_address(request(CLIENT, Some("198.51.100.1"), &[])), Some(CLIENT.to_string()));
+    assert_eq!(ip_address(request(CLIENT, Some("198.51.100.1"), &[PROXY])), Some(CLIENT.to_string()));
+}
+
+#[actix_web::test]
+async fn trusted_proxies_pass_the_client_address_on() {
+    assert_eq!(ip_address(request(PROXY, Some(CLIENT), &[PROXY])), Some(CLIENT.to_string()));
+}
+
+#[actix_web::test]
+async fn a_trusted_proxy_without_forwarding_headers_is_the_client() {
+    assert_eq!(ip_address(request(PROXY, None, &[PROXY])), Some(PROXY.to_string()));
+    assert_eq!(ip_address(request(PROXY, Some("not an address"), &[PROXY])), Some(PROXY.to_string()));
+}
+
+#[actix_web::test]
+async fn without_configuration_the_socket_address_is_used() {
+    let request = TestRequest::default()
+        .peer_addr(format!("{}:40000", PROXY).parse().unwrap())
+        .insert_header(("X-Forwarded-For", CLIENT));
+
+    assert_eq!(ip_address(request), Some(PROXY.to_string()));
+}
@@ -0,0 +1,203 @@
+// File: src/tests/contact_test.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+
+use crate::application::use_cases::contact_use_cases::{
+    CheckMessagePermissionUseCase, RemoveContactUseCase, RespondContactRequestUseCase,
+    SendContactRequestUseCase, SENDER_SUSPENDED,
+};
+use crate::domain::entities::contact::{ContactRequest, ContactRequestStatus, ContactSettings};
+use crate::domain::entities::moderation::UserSuspension;
+use crate::domain::repositories::contact_repository::MockContactRepository;
+use crate::domain::repositories::moderation_repository::MockModerationRepository;
+use crate::tests::fixtures::error_kind;
+
+const ALICE: i32 = 1;
+const BOB: i32 = 2;
+const REQUEST_ID: i32 = 10;
+
+/// A request from `requester_id` to `addressee_id`, last updated `days_ago`.
+fn request(requester_id: i32, addressee_id: i32, status: ContactRequestStatus, days_ago: i64) -> ContactRequest {
+    let updated_at = (Utc::now() - Duration::days(days_ago)).naive_utc();
+    ContactRequest {
+        id: REQUEST_ID,
+        requester_id,
+        addressee_id,
+        status,
+        created_at: updated_at,
+        updated_at,
+    }
+}
+
+fn repository_with(existing: Option<ContactRequest>) -> MockContactRepository {
+    let mut repository = MockContactRepository::new();
+    repository.expect_find_request_between().returning(move |_, _| Ok(existing.clone()));
+    repository
+}
+
+async fn send(repository: MockContactRepository) -> Result<ContactRequest, Option<ErrorKind>> {
+    SendContactRequestUseCase::new(Arc::new(repository))
+        .execute(ALICE, BOB)
+        .await
+        .map_err(|e| error_kind(&*e))
+}
+
+#[tokio::test]
+async fn a_first_request_is_created() {
+    let mut repository = repository_with(None);
+    repository.expect_create_request()
+        .times(1)
+        .returning(|requester_id, addressee_id| Ok(request(requester_id, addressee_id, ContactRequestStatus::Pending, 0)));
+
+    let created = send(repository).await.unwrap();
+
+    assert_eq!((created.requester_id, created.addressee_id), (ALICE, BOB));
+    assert_eq!(created.status, ContactRequestStatus::Pending);
+}
+
+#[tokio::test]
+async fn requests_to_yourself_are_rejected() {
+    let result = SendContactRequestUseCase::new(Arc::new(MockContactRepository::new()))
+        .execute(ALICE, ALICE)
+        .await;
+
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::InvalidInput));
+}
+
+#[tokio::test]
+async fn asking_someone_who_already_asked_accepts_their_request() {
+    let mut repository = repository_with(Some(request(BOB, ALICE, ContactRequestStatus::Pending, 0)));
+    repository.expect_update_status()
+        .withf(|id, status| *id == REQUEST_ID && *status == ContactRequestStatus::Accepted)
+        .times(1)
+        .returning(|_, status| Ok(request(BOB, ALICE, status, 0)));
+
+    assert_eq!(send(repository).await.unwrap().status, ContactRequestStatus::Accepted);
+}
+
+#[tokio::test]
+async fn pending_and_accepted_requests_are_not_repeated() {
+    for status in [ContactRequestStatus::Pending, ContactRequestStatus::Accepted] {
+        let repository = repository_with(Some(request(ALICE, BOB, status, 0)));
+
+        assert_eq!(send(repository).await.unwrap_err(), Some(ErrorKind::AlreadyExists), "{:?}", status);
+    }
+}
+
+#[tokio::test]
+async fn a_declined_requester_has_to_wait_before_asking_again() {
+    let repository = repository_with(Some(request(ALICE, BOB, ContactRequestStatus::Declined, 29)));
+
+    assert_eq!(send(repository).await.unwrap_err(), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn a_declined_request_is_replaced_once_the_cooldown_is_over() {
+    let mut repository = repository_with(Some(request(ALICE, BOB, ContactRequestStatus::Declined, 31)));
+    repository.expect_delete_request().withf(|id| *id == REQUEST_ID).times(1).returning(|_| Ok(()));
+    repository.expect_create_request()
+        .times(1)
+        .returning(|requester_id, addressee_id| Ok(request(requester_id, addressee_id, ContactRequestStatus::Pending, 0)));
+
+    assert_eq!(send(repository).await.unwrap().status, ContactRequestStatus::Pending);
+}
+
+#[tokio::test]
+async fn the_user_who_declined_can_ask_at_any_time() {
+    let mut repository = repository_with(Some(request(BOB, ALICE, ContactRequestStatus::Declined, 0)));
+    repository.expect_delete_request().times(1).returning(|_| Ok(()));
+    repository.expect_create_request()
+        .times(1)
+        .returning(|requester_id, addressee_id| Ok(request(requester_id, addressee_id, ContactRequestStatus::Pending, 0)));
+
+    let created = send(repository).await.unwrap();
+
+    assert_eq!((created.requester_id, created.addressee_id), (ALICE, BOB));
+}
+
+fn respond_repository(existing: ContactRequest) -> MockContactRepository {
+    let mut repository = MockContactRepository::new();
+    repository.expect_find_request().returning(move |_| Ok(Some(existing.clone())));
+    repository
+}
+
+#[tokio::test]
+async fn only_the_recipient_answers_a_pending_request() {
+    let repository = respond_repository(request(ALICE, BOB, ContactRequestStatus::Pending, 0));
+    let result = RespondContactRequestUseCase::new(Arc::new(repository)).execute(ALICE, REQUEST_ID, true).await;
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::PermissionDenied));
+
+    let repository = respond_repository(request(ALICE, BOB, ContactRequestStatus::Declined, 0));
+    let result = RespondContactRequestUseCase::new(Arc::new(repository)).execute(BOB, REQUEST_ID, true).await;
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::InvalidInput));
+
+    let mut repository = respond_repository(request(ALICE, BOB, ContactRequestStatus::Pending, 0));
+    repository.expect_update_status()
+        .withf(|_, status| *status == ContactRequestStatus::Declined)
+        .times(1)
+        .returning(|_, status| Ok(request(ALICE, BOB, status, 0)));
+    let declined = RespondContactRequestUseCase::new(Arc::new(repository)).execute(BOB, REQUEST_ID, false).await.unwrap();
+    assert_eq!(declined.status, ContactRequestStatus::Declined);
+}
+
+#[tokio::test]
+async fn only_accepted_contacts_can_be_removed() {
+    let mut repository = repository_with(Some(request(BOB, ALICE, ContactRequestStatus::Accepted, 0)));
+    repository.expect_delete_request().withf(|id| *id == REQUEST_ID).times(1).returning(|_| Ok(()));
+    RemoveContactUseCase::new(Arc::new(repository)).execute(ALICE, BOB).await.unwrap();
+
+    // Without an expectation for delete_request, any deletion would panic
+    for status in [ContactRequestStatus::Pending, ContactRequestStatus::Declined] {
+        let repository = repository_with(Some(request(BOB, ALICE, status, 0)));
+        let result = RemoveContactUseCase::new(Arc::new(repository)).execute(ALICE, BOB).await;
+        assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::NotFound), "{:?}", status);
+    }
+}
+
+fn suspension(user_id: i32) -> UserSuspension {
+    UserSuspension {
+        id: 1,
+        user_id,
+        suspended_by: None,
+        reason: None,
+        suspended_until: None,
+        lifted_at: None,
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+fn moderation(suspended: bool) -> MockModerationRepository {
+    let mut moderation = MockModerationRepository::new();
+    moderation.expect_find_active_suspension()
+        .returning(move |user_id| Ok(suspended.then(|| suspension(user_id))));
+    moderation
+}
+
+fn settings_repository(allow_messages_from_strangers: bool, are_contacts: bool) -> MockContactRepository {
+    let mut repository = MockContactRepository::new();
+    repository.expect_get_settings()
+        .returning(move |user_id| Ok(ContactSettings { user_id, allow_messages_from_strangers }));
+    repository.expect_are_contacts().returning(move |_, _| Ok(are_contacts));
+    repository
+}
+
+async fn may_message(repository: MockContactRepository, moderation: MockModerationRepository) -> Result<bool, String> {
+    CheckMessagePermissionUseCase::new(Arc::new(repository), Arc::new(moderation))
+        .execute(ALICE, BOB)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tokio::test]
+async fn strangers_can_only_message_users_who_allow_it() {
+    assert_eq!(may_message(settings_repository(true, false), moderation(false)).await, Ok(true));
+    assert_eq!(may_message(settings_repository(false, false), moderation(false)).await, Ok(false));
+    assert_eq!(may_message(settings_repository(false, true), moderation(false)).await, Ok(true));
+}
+
+#[tokio::test]
+async fn suspended_users_cannot_message_anyone() {
+    assert_eq!(may_message(settings_repository(true, true), moderation(true)).await, Err(SENDER_SUSPENDED.to_string()));
+}
@@ -0,0 +1,223 @@
+// File: src/tests/email_verification_test.rs
+
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::application::use_cases::auth_use_cases::{
+    LoginUseCase, SendVerificationEmailUseCase, VerifyEmailUseCase, EMAIL_NOT_VERIFIED,
+};
+use crate::domain::entities::auth::{AuthUser, TokenPurpose};
+use crate::domain::entities::mfa::LoginResponse;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::entities::user::User;
+use crate::domain::repositories::audit_repository::MockAuditRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::infrastructure::mail::memory_mailer::MemoryMailer;
+use crate::tests::fixtures::{access_tokens, error_kind, user, MemoryLoginAttempts};
+
+const SECRET: &str = "email-verification-test-secret";
+const BASE_URL: &str = "http://localhost:8080/";
+const ALICE: i32 = 7;
+
+/// A stored token, like a row of user_tokens.
+struct StoredToken {
+    purpose: TokenPurpose,
+    token_hash: String,
+    expires_at: NaiveDateTime,
+    used: bool,
+}
+
+#[derive(Default)]
+struct Store {
+    tokens: Vec<StoredToken>,
+    verified: bool,
+}
+
+/// Tokens kept in memory; used and expired ones are not consumed, like the database.
+fn repository(store: Arc<Mutex<Store>>) -> MockAuthRepository {
+    let mut repository = MockAuthRepository::new();
+
+    let found = store.clone();
+    repository.expect_find_by_email().returning(move |email| {
+        let verified = found.lock().unwrap().verified;
+        Ok((email == "alice@example.com").then(|| alice(verified)))
+    });
+
+    let revoked = store.clone();
+    repository.expect_revoke_tokens().returning(move |_, purpose| {
+        for token in revoked.lock().unwrap().tokens.iter_mut().filter(|token| token.purpose == purpose) {
+            token.used = true;
+        }
+        Ok(())
+    });
+
+    let created = store.clone();
+    repository.expect_create_token().returning(move |_, purpose, token_hash, expires_at| {
+        created.lock().unwrap().tokens.push(StoredToken { purpose, token_hash, expires_at, used: false });
+        Ok(())
+    });
+
+    let consumed = store.clone();
+    repository.expect_consume_token().returning(move |purpose, token_hash| {
+        let now = Utc::now().naive_utc();
+        let mut store = consumed.lock().unwrap();
+        let token = store.tokens.iter_mut().find(|token| {
+            token.purpose == purpose && token.token_hash == token_hash && !token.used && token.expires_at > now
+        });
+        Ok(token.map(|token| token.used = true).map(|_| ALICE))
+    });
+
+    repository.expect_mark_email_verified().returning(move |_| {
+        store.lock().unwrap().verified = true;
+        Ok(())
+    });
+
+    repository
+}
+
+fn alice(verified: bool) -> User {
+    User {
+        email_verified_at: verified.then(|| Utc::now().naive_utc()),
+        ..user(ALICE, "alice")
+    }
+}
+
+fn token_from_link(body: &str) -> String {
+    let start = body.find("?token=").expect("link missing from email") + "?token=".len();
+    body[start..].split_whitespace().next().unwrap().to_string()
+}
+
+fn sender(store: &Arc<Mutex<Store>>, token_service: &Arc<TokenService>, mailer: &Arc<MemoryMailer>) -> SendVerificationEmailUseCase<MockAuthRepository> {
+    SendVerificationEmailUseCase::new(repository(store.clone()), token_service.clone(), mailer.clone(), BASE_URL.to_string())
+}
+
+async fn verify(store: &Arc<Mutex<Store>>, token_service: &Arc<TokenService>, token: &str) -> Result<(), Option<ErrorKind>> {
+    VerifyEmailUseCase::new(repository(store.clone()), token_service.clone())
+        .execute(token)
+        .await
+        .map_err(|e| error_kind(&*e))
+}
+
+#[tokio::test]
+async fn a_verification_link_is_mailed_and_expires_in_a_day() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let before = Utc::now().naive_utc();
+    sender(&store, &token_service, &mailer).execute(&alice(false)).await.unwrap();
+
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "alice@example.com");
+    assert!(sent[0].body.contains("http://localhost:8080/api/v1/auth/verify-email?token="));
+    assert!(token_service.verify(TokenPurpose::EmailVerification, &token_from_link(&sent[0].body)));
+
+    let store = store.lock().unwrap();
+    assert_eq!(store.tokens.len(), 1);
+    assert_eq!(store.tokens[0].purpose, TokenPurpose::EmailVerification);
+    assert!(store.tokens[0].expires_at >= before + Duration::hours(24));
+    assert!(store.tokens[0].expires_at <= Utc::now().naive_utc() + Duration::hours(24));
+}
+
+#[tokio::test]
+async fn the_link_verifies_the_address_once() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    sender(&store, &token_service, &mailer).execute(&alice(false)).await.unwrap();
+    let token = token_from_link(&mailer.sent()[0].body);
+
+    assert_eq!(verify(&store, &token_service, &token).await, Ok(()));
+    assert!(store.lock().unwrap().verified);
+
+    assert_eq!(verify(&store, &token_service, &token).await, Err(Some(ErrorKind::InvalidInput)));
+}
+
+#[tokio::test]
+async fn expired_links_are_refused() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    sender(&store, &token_service, &mailer).execute(&alice(false)).await.unwrap();
+    store.lock().unwrap().tokens[0].expires_at = Utc::now().naive_utc() - Duration::seconds(1);
+
+    let token = token_from_link(&mailer.sent()[0].body);
+    assert_eq!(verify(&store, &token_service, &token).await, Err(Some(ErrorKind::InvalidInput)));
+    assert!(!store.lock().unwrap().verified);
+}
+
+#[tokio::test]
+async fn tokens_for_other_purposes_are_refused() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let password_reset = token_service.generate(TokenPurpose::PasswordReset).unwrap();
+
+    assert_eq!(verify(&store, &token_service, &password_reset).await, Err(Some(ErrorKind::InvalidInput)));
+}
+
+#[tokio::test]
+async fn a_resent_link_replaces_the_old_one() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    sender(&store, &token_service, &mailer).execute(&alice(false)).await.unwrap();
+    sender(&store, &token_service, &mailer).resend("alice@example.com").await.unwrap();
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 2);
+
+    assert_eq!(verify(&store, &token_service, &token_from_link(&sent[0].body)).await, Err(Some(ErrorKind::InvalidInput)));
+    assert_eq!(verify(&store, &token_service, &token_from_link(&sent[1].body)).await, Ok(()));
+}
+
+#[tokio::test]
+async fn nothing_is_resent_to_verified_or_unknown_addresses() {
+    let store = Arc::new(Mutex::new(Store { verified: true, ..Store::default() }));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    sender(&store, &token_service, &mailer).resend("alice@example.com").await.unwrap();
+    sender(&store, &token_service, &mailer).resend("nobody@example.com").await.unwrap();
+
+    assert!(mailer.sent().is_empty());
+}
+
+async fn log_in(verified: bool, require_email_verification: bool) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_login().returning(move |_| Ok(Some(alice(verified))));
+    repository.expect_authenticate().returning(move |_| Ok(alice(verified)));
+    repository.expect_is_suspended().returning(|_| Ok(false));
+    repository.expect_is_pending_approval().returning(|_| Ok(false));
+    repository.expect_find_totp().returning(|_| Ok(None));
+    repository.expect_find_role_names().returning(|_| Ok(vec![]));
+    repository.expect_create_session().returning(|_| Ok(()));
+
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event().returning(|_| Ok(()));
+
+    LoginUseCase::new(
+        repository,
+        Arc::new(MemoryLoginAttempts::default()),
+        Arc::new(TokenService::new(SECRET)),
+        access_tokens(SECRET),
+        Arc::new(audit),
+        require_email_verification,
+    )
+        .execute(AuthUser { username: "alice".to_string(), password: "password".to_string() }, &ClientInfo::default())
+        .await
+}
+
+#[tokio::test]
+async fn unverified_users_are_refused_at_login_when_verification_is_required() {
+    let error = log_in(false, true).await.unwrap_err();
+    assert_eq!(error_kind(&*error), Some(ErrorKind::PermissionDenied));
+    assert_eq!(error.to_string(), EMAIL_NOT_VERIFIED);
+
+    assert!(matches!(log_in(true, true).await.unwrap(), LoginResponse::Token(_)));
+    assert!(matches!(log_in(false, false).await.unwrap(), LoginResponse::Token(_)));
+}
@@ -0,0 +1,30 @@
+// File: src/tests/error_response_test.rs
+
+use std::io::{Error as IoError, ErrorKind};
+use actix_web::body::to_bytes;
+use actix_web::http::StatusCode;
+
+use crate::presentation::handlers::errors::error_response;
+
+async fn body(error: &IoError) -> (StatusCode, serde_json::Value) {
+    let response = error_response("Authentication failed", error);
+    let status = response.status();
+    let bytes = to_bytes(response.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[actix_web::test]
+async fn domain_errors_keep_their_message() {
+    let (status, body) = body(&IoError::new(ErrorKind::PermissionDenied, "Account is suspended")).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["message"], "Account is suspended");
+}
+
+#[actix_web::test]
+async fn internal_errors_are_not_sent_to_the_client() {
+    let (status, body) = body(&IoError::other("could not connect to server: Connection refused")).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body, serde_json::json!({ "error": "Authentication failed" }));
+}
@@ -0,0 +1,153 @@
+// File: src/tests/fixtures.rs
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::login_attempt::{FailureStats, LoginLockout, NewLoginLockout};
+use crate::domain::entities::user::User;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::domain::services::signing_key::{SigningKey, SigningKeys};
+
+pub const PASSWORD_HASH: &str = "$2b$12$abcdefghijklmnopqrstuv";
+
+/// A stored user with an unverified `<username>@example.com` address.
+pub fn user(id: i32, username: &str) -> User {
+    User {
+        id,
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        password: PASSWORD_HASH.to_string(),
+        email_verified_at: None,
+    }
+}
+
+/// Unsigned claims for `sub` holding `roles`, as the auth middleware would hand them over.
+pub fn claims(sub: i32, roles: &[&str]) -> Claims {
+    Claims {
+        sub,
+        exp: 0,
+        iat: 0,
+        nbf: 0,
+        iss: "http://localhost:8080".to_string(),
+        aud: "rust-crate".to_string(),
+        jti: "jti".to_string(),
+        username: "caller".to_string(),
+        roles: roles.iter().map(|role| role.to_string()).collect(),
+        scopes: None,
+        act: None,
+    }
+}
+
+pub fn access_tokens(secret: &str) -> Arc<AccessTokenService> {
+    let signing_keys = SigningKeys::new(SigningKey::derive_from_secret(secret).unwrap(), vec![]).unwrap();
+    Arc::new(AccessTokenService::new(
+        Arc::new(signing_keys),
+        "http://localhost:8080".to_string(),
+        "rust-crate".to_string(),
+        0,
+    ))
+}
+
+/// The `ErrorKind` a use case attached to its error, if any.
+pub fn error_kind(e: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    e.downcast_ref::<std::io::Error>().map(|io_error| io_error.kind())
+}
+
+/// Roles held in memory; users without an entry hold none.
+#[derive(Default)]
+pub struct StubRoleRepository {
+    roles: HashMap<i32, Vec<String>>,
+}
+
+impl StubRoleRepository {
+    pub fn with_role(mut self, user_id: i32, role: &str) -> Self {
+        self.roles.entry(user_id).or_default().push(role.to_string());
+        self
+    }
+}
+
+#[async_trait]
+impl RoleRepository for StubRoleRepository {
+    async fn has_any_role(&self, user_id: i32, role_names: &[&str]) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(self.roles.get(&user_id).is_some_and(|roles| roles.iter().any(|role| role_names.contains(&role.as_str()))))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Attempt {
+    username: String,
+    ip_address: Option<String>,
+    succeeded: bool,
+    created_at: NaiveDateTime,
+}
+
+/// Keeps attempts and lockouts in memory, counting failures like the database does.
+#[derive(Default)]
+pub struct MemoryLoginAttempts {
+    attempts: Mutex<Vec<Attempt>>,
+    lockouts: Mutex<Vec<NewLoginLockout>>,
+}
+
+impl MemoryLoginAttempts {
+    pub fn attempts(&self) -> Vec<(String, bool)> {
+        self.attempts.lock().unwrap().iter().map(|attempt| (attempt.username.clone(), attempt.succeeded)).collect()
+    }
+
+    fn stats(&self, matches: impl Fn(&Attempt) -> bool, since: NaiveDateTime) -> FailureStats {
+        let attempts = self.attempts.lock().unwrap();
+        let mut stats = FailureStats::default();
+        for attempt in attempts.iter().filter(|attempt| matches(attempt) && attempt.created_at >= since) {
+            if attempt.succeeded {
+                stats = FailureStats::default();
+            } else {
+                stats.failed_attempts += 1;
+                stats.last_failed_at = Some(attempt.created_at);
+            }
+        }
+        stats
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for MemoryLoginAttempts {
+    async fn record_attempt(&self, username: &str, ip_address: Option<&str>, succeeded: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.attempts.lock().unwrap().push(Attempt {
+            username: username.to_string(),
+            ip_address: ip_address.map(str::to_string),
+            succeeded,
+            created_at: Utc::now().naive_utc(),
+        });
+        Ok(())
+    }
+
+    async fn failures_for_username(&self, username: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.stats(|attempt| attempt.username == username, since))
+    }
+
+    async fn failures_for_ip(&self, ip_address: &str, since: NaiveDateTime) -> Result<FailureStats, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.stats(|attempt| attempt.ip_address.as_deref() == Some(ip_address), since))
+    }
+
+    async fn record_lockout(&self, lockout: NewLoginLockout) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.lockouts.lock().unwrap().push(lockout);
+        Ok(())
+    }
+
+    async fn find_lockouts(&self, limit: i64) -> Result<Vec<LoginLockout>, Box<dyn std::error::Error + Send + Sync>> {
+        let lockouts = self.lockouts.lock().unwrap();
+        Ok(lockouts.iter().enumerate().rev().take(limit as usize).map(|(index, lockout)| LoginLockout {
+            id: index as i32 + 1,
+            username: lockout.username.clone(),
+            ip_address: lockout.ip_address.clone(),
+            failed_attempts: lockout.failed_attempts,
+            locked_until: lockout.locked_until,
+            created_at: Utc::now().naive_utc(),
+        }).collect())
+    }
+}
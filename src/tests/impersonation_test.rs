@@ -0,0 +1,115 @@
+// File: src/tests/impersonation_test.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::application::use_cases::impersonation_use_cases::ImpersonateUserUseCase;
+use crate::domain::entities::auth::{Actor, Claims};
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::access_token::AccessTokenService;
+use crate::presentation::handlers::impersonation_handlers::forbid_impersonation;
+use crate::tests::fixtures::{access_tokens, claims, error_kind, user, StubRoleRepository};
+
+const SECRET: &str = "impersonation-test-secret";
+const SUPERUSER_ID: i32 = 1;
+const TARGET_ID: i32 = 7;
+
+fn caller_claims(access_tokens: &AccessTokenService, id: i32) -> Claims {
+    access_tokens.claims_for(&user(id, "root"), vec!["superuser".to_string()])
+}
+
+fn use_case(repository: MockAuthRepository, access_tokens: Arc<AccessTokenService>) -> ImpersonateUserUseCase<MockAuthRepository, StubRoleRepository> {
+    let roles = StubRoleRepository::default().with_role(SUPERUSER_ID, "superuser");
+    ImpersonateUserUseCase::new(repository, access_tokens, Arc::new(roles))
+}
+
+/// The claims a signed token carries, read without checking the signature.
+fn token_claims(token: &str) -> Claims {
+    let payload = token.split('.').nth(1).unwrap();
+    serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn superuser_gets_a_short_lived_token_naming_them() {
+    let access_tokens = access_tokens(SECRET);
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(Some(user(id, "alice"))));
+    repository.expect_find_role_names().returning(|_| Ok(vec!["user".to_string()]));
+    repository.expect_create_session()
+        .withf(|session| session.user_id == TARGET_ID && session.impersonator_id == Some(SUPERUSER_ID))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let token = use_case(repository, access_tokens.clone())
+        .execute(&caller_claims(&access_tokens, SUPERUSER_ID), TARGET_ID, &ClientInfo::default())
+        .await
+        .unwrap();
+
+    assert!(token.expires_in <= 15 * 60);
+    let claims = token_claims(&token.access_token);
+    assert_eq!(claims.sub, TARGET_ID);
+    assert_eq!(claims.username, "alice");
+    let actor = claims.act.expect("impersonation tokens name the impersonator");
+    assert_eq!(actor.sub, SUPERUSER_ID);
+    assert_eq!(actor.username, "root");
+}
+
+#[tokio::test]
+async fn only_superusers_can_impersonate() {
+    let access_tokens = access_tokens(SECRET);
+    let mut repository = MockAuthRepository::new();
+    repository.expect_create_session().never();
+
+    let e = use_case(repository, access_tokens.clone())
+        .execute(&caller_claims(&access_tokens, 2), TARGET_ID, &ClientInfo::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn impersonation_tokens_cannot_impersonate_again() {
+    let access_tokens = access_tokens(SECRET);
+    let mut repository = MockAuthRepository::new();
+    repository.expect_create_session().never();
+
+    let mut claims = caller_claims(&access_tokens, SUPERUSER_ID);
+    claims.act = Some(Actor { sub: 3, username: "other".to_string() });
+
+    let e = use_case(repository, access_tokens.clone())
+        .execute(&claims, TARGET_ID, &ClientInfo::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn superusers_cannot_be_impersonated() {
+    let access_tokens = access_tokens(SECRET);
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(Some(user(id, "admin"))));
+    repository.expect_find_role_names().returning(|_| Ok(vec!["superuser".to_string()]));
+    repository.expect_create_session().never();
+
+    let e = use_case(repository, access_tokens.clone())
+        .execute(&caller_claims(&access_tokens, SUPERUSER_ID), TARGET_ID, &ClientInfo::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[test]
+fn account_changes_are_refused_while_impersonating() {
+    let mut impersonated = claims(TARGET_ID, &["user"]);
+    assert!(forbid_impersonation(&impersonated).is_none());
+
+    impersonated.act = Some(Actor { sub: SUPERUSER_ID, username: "root".to_string() });
+    let response = forbid_impersonation(&impersonated).expect("impersonation tokens are refused");
+    assert_eq!(response.status(), actix_web::http::StatusCode::FORBIDDEN);
+}
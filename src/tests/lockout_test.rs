@@ -0,0 +1,112 @@
+// File: src/tests/lockout_test.rs
+
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::application::use_cases::login_attempt_use_cases::{LoginThrottleUseCase, LOGIN_LOCKED};
+use crate::domain::entities::login_attempt::LoginLockout;
+use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+use crate::tests::fixtures::MemoryLoginAttempts;
+
+const USERNAME: &str = "alice";
+const IP_ADDRESS: &str = "203.0.113.9";
+
+struct Fixture {
+    attempts: Arc<MemoryLoginAttempts>,
+    throttle: LoginThrottleUseCase<MemoryLoginAttempts>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let attempts = Arc::new(MemoryLoginAttempts::default());
+        let throttle = LoginThrottleUseCase::new(attempts.clone());
+        Self { attempts, throttle }
+    }
+
+    async fn fail(&self, username: &str, times: usize) {
+        for _ in 0..times {
+            self.throttle.record_failure(username, Some(IP_ADDRESS)).await.unwrap();
+        }
+    }
+
+    async fn check(&self, username: &str) -> Result<(), String> {
+        self.throttle.check(username, Some(IP_ADDRESS)).await.map_err(|e| e.to_string())
+    }
+
+    /// Lockouts recorded so far, oldest first.
+    async fn lockouts(&self) -> Vec<LoginLockout> {
+        let mut lockouts = self.attempts.find_lockouts(100).await.unwrap();
+        lockouts.reverse();
+        lockouts
+    }
+}
+
+/// Seconds left on a lockout recorded a moment ago.
+fn seconds_locked(lockout: &LoginLockout) -> i64 {
+    (lockout.locked_until - Utc::now().naive_utc()).num_seconds()
+}
+
+#[tokio::test]
+async fn the_fifth_failure_locks_the_username() {
+    let fixture = Fixture::new();
+
+    fixture.fail(USERNAME, 4).await;
+    assert_eq!(fixture.check(USERNAME).await, Ok(()));
+    assert!(fixture.lockouts().await.is_empty());
+
+    fixture.fail(USERNAME, 1).await;
+    assert_eq!(fixture.check(USERNAME).await, Err(LOGIN_LOCKED.to_string()));
+    // Another user behind the same address is not affected yet
+    assert_eq!(fixture.check("bob").await, Ok(()));
+
+    let lockouts = fixture.lockouts().await;
+    assert_eq!(lockouts.len(), 1);
+    assert_eq!(lockouts[0].username.as_deref(), Some(USERNAME));
+    assert_eq!(lockouts[0].failed_attempts, 5);
+}
+
+#[tokio::test]
+async fn lockouts_double_with_every_further_failure_up_to_an_hour() {
+    let fixture = Fixture::new();
+
+    fixture.fail(USERNAME, 13).await;
+
+    let expected = [30, 60, 120, 240, 480, 960, 1920, 3600, 3600];
+    let lockouts = fixture.lockouts().await;
+    assert_eq!(lockouts.len(), expected.len());
+    for (lockout, seconds) in lockouts.iter().zip(expected) {
+        // Allow for the time the test itself takes
+        assert!((seconds - 5..=seconds).contains(&seconds_locked(lockout)), "{} failures", lockout.failed_attempts);
+    }
+}
+
+#[tokio::test]
+async fn a_successful_login_resets_the_counter() {
+    let fixture = Fixture::new();
+
+    fixture.fail(USERNAME, 4).await;
+    fixture.throttle.record_success(USERNAME, Some(IP_ADDRESS)).await.unwrap();
+    fixture.fail(USERNAME, 4).await;
+
+    assert_eq!(fixture.check(USERNAME).await, Ok(()));
+    assert!(fixture.lockouts().await.is_empty());
+}
+
+#[tokio::test]
+async fn an_address_is_locked_after_twenty_failures_across_usernames() {
+    let fixture = Fixture::new();
+
+    for index in 0..19 {
+        fixture.fail(&format!("user{}", index), 1).await;
+    }
+    assert_eq!(fixture.check("someone-else").await, Ok(()));
+
+    fixture.fail("user19", 1).await;
+    assert_eq!(fixture.check("someone-else").await, Err(LOGIN_LOCKED.to_string()));
+
+    let lockouts = fixture.lockouts().await;
+    assert_eq!(lockouts.len(), 1);
+    assert_eq!(lockouts[0].username, None);
+    assert_eq!(lockouts[0].ip_address.as_deref(), Some(IP_ADDRESS));
+    assert_eq!(lockouts[0].failed_attempts, 20);
+}
@@ -0,0 +1,97 @@
+// File: src/tests/logging_test.rs
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use serde_json::Value;
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::infrastructure::logging::json::{JsonFields, JsonFormat};
+use crate::infrastructure::logging::redact::{is_sensitive, RedactingFields, REDACTED};
+
+const PASSWORD_HASH: &str = "$2b$12$abcdefghijklmnopqrstuv";
+
+/// Collects everything the subscriber writes.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    fn output(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl io::Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn log_login(user_id: i32) {
+    let span = tracing::info_span!("request", request_id = "req-42");
+    let _entered = span.enter();
+    tracing::info!(user_id, password_hash = PASSWORD_HASH, "Login succeeded");
+}
+
+#[test]
+fn sensitive_field_names_are_recognised() {
+    for name in ["password", "password_hash", "access_token", "client_secret", "Authorization", "log.salt"] {
+        assert!(is_sensitive(name), "{} should be sensitive", name);
+    }
+    for name in ["user_id", "username", "request_id", "status_code", "hashtag"] {
+        assert!(!is_sensitive(name), "{} should not be sensitive", name);
+    }
+}
+
+#[test]
+fn text_output_redacts_sensitive_fields() {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_ansi(false)
+        .fmt_fields(RedactingFields)
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || log_login(7));
+
+    let output = captured.output();
+    assert!(output.contains("Login succeeded"));
+    assert!(output.contains("user_id=7"));
+    assert!(output.contains(&format!("password_hash={}", REDACTED)));
+    assert!(!output.contains(PASSWORD_HASH));
+}
+
+#[test]
+fn json_output_carries_the_request_id_and_redacts_sensitive_fields() {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .fmt_fields(JsonFields)
+        .event_format(JsonFormat)
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || log_login(7));
+
+    let output = captured.output();
+    assert!(!output.contains(PASSWORD_HASH));
+
+    let line: Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(line["message"], "Login succeeded");
+    assert_eq!(line["request_id"], "req-42");
+    assert_eq!(line["user_id"], 7);
+    assert_eq!(line["password_hash"], REDACTED);
+    assert!(line["timestamp"].is_string());
+}
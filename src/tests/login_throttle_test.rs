@@ -0,0 +1,125 @@
+// File: src/tests/login_throttle_test.rs
+
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::application::use_cases::auth_use_cases::LoginUseCase;
+use crate::domain::entities::auth::AuthUser;
+use crate::domain::entities::mfa::{LoginResponse, UserTotp};
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::audit_repository::MockAuditRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::tests::fixtures::{access_tokens, user, MemoryLoginAttempts};
+
+const SECRET: &str = "login-throttle-test-secret";
+const IP_ADDRESS: &str = "203.0.113.9";
+
+fn client() -> ClientInfo {
+    ClientInfo {
+        user_agent: None,
+        ip_address: Some(IP_ADDRESS.to_string()),
+    }
+}
+
+fn credentials(login: &str) -> AuthUser {
+    AuthUser {
+        username: login.to_string(),
+        password: "wrong password".to_string(),
+    }
+}
+
+fn bad_credentials() -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid username or password"))
+}
+
+fn login_use_case(repository: MockAuthRepository, attempts: Arc<MemoryLoginAttempts>) -> LoginUseCase<MockAuthRepository, MemoryLoginAttempts> {
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event().returning(|_| Ok(()));
+
+    LoginUseCase::new(
+        repository,
+        attempts,
+        Arc::new(TokenService::new(SECRET)),
+        access_tokens(SECRET),
+        Arc::new(audit),
+        false,
+    )
+}
+
+#[tokio::test]
+async fn email_and_username_share_the_account_counter() {
+    let attempts = Arc::new(MemoryLoginAttempts::default());
+
+    for login in ["alice", "Alice@Example.com", "alice@EXAMPLE.com"] {
+        let mut repository = MockAuthRepository::new();
+        repository.expect_find_by_login().returning(|_| Ok(Some(user(7, "alice"))));
+        repository.expect_authenticate().returning(|_| Err(bad_credentials()));
+
+        let result = login_use_case(repository, attempts.clone())
+            .execute(credentials(login), &client())
+            .await;
+        assert!(result.is_err());
+    }
+
+    assert_eq!(attempts.attempts(), vec![("alice".to_string(), false); 3]);
+}
+
+#[tokio::test]
+async fn unknown_identifiers_are_counted_normalised() {
+    let attempts = Arc::new(MemoryLoginAttempts::default());
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_login().returning(|_| Ok(None));
+    repository.expect_authenticate().returning(|_| Err(bad_credentials()));
+
+    let result = login_use_case(repository, attempts.clone())
+        .execute(credentials("  NoBody@Example.com "), &client())
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.attempts(), vec![("nobody@example.com".to_string(), false)]);
+}
+
+#[tokio::test]
+async fn a_correct_password_alone_does_not_reset_the_counter_when_2fa_is_on() {
+    let attempts = Arc::new(MemoryLoginAttempts::default());
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_login().returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_authenticate().returning(|_| Ok(user(7, "alice")));
+    repository.expect_is_suspended().returning(|_| Ok(false));
+    repository.expect_is_pending_approval().returning(|_| Ok(false));
+    repository.expect_find_totp().returning(|_| Ok(Some(UserTotp {
+        secret: "JBSWY3DPEHPK3PXP".to_string(),
+        confirmed_at: Some(Utc::now().naive_utc()),
+    })));
+    repository.expect_create_token().returning(|_, _, _, _| Ok(()));
+
+    let response = login_use_case(repository, attempts.clone())
+        .execute(credentials("alice"), &client())
+        .await
+        .unwrap();
+
+    assert!(matches!(response, LoginResponse::MfaRequired(_)));
+    assert!(attempts.attempts().is_empty());
+}
+
+#[tokio::test]
+async fn a_completed_login_resets_the_counter() {
+    let attempts = Arc::new(MemoryLoginAttempts::default());
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_login().returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_authenticate().returning(|_| Ok(user(7, "alice")));
+    repository.expect_is_suspended().returning(|_| Ok(false));
+    repository.expect_is_pending_approval().returning(|_| Ok(false));
+    repository.expect_find_totp().returning(|_| Ok(None));
+    repository.expect_find_role_names().returning(|_| Ok(vec![]));
+    repository.expect_create_session().returning(|_| Ok(()));
+
+    let response = login_use_case(repository, attempts.clone())
+        .execute(credentials("alice@example.com"), &client())
+        .await
+        .unwrap();
+
+    assert!(matches!(response, LoginResponse::Token(_)));
+    assert_eq!(attempts.attempts(), vec![("alice".to_string(), true)]);
+}
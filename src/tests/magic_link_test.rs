@@ -0,0 +1,162 @@
+// File: src/tests/magic_link_test.rs
+
+use std::sync::Arc;
+use mockall::predicate::eq;
+
+use crate::application::use_cases::auth_use_cases::ACCOUNT_SUSPENDED;
+use crate::application::use_cases::magic_link_use_cases::{RequestMagicLinkUseCase, VerifyMagicLinkUseCase};
+use crate::domain::entities::auth::TokenPurpose;
+use crate::domain::entities::mfa::LoginResponse;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::infrastructure::mail::memory_mailer::MemoryMailer;
+use crate::tests::fixtures::{access_tokens, user};
+
+const SECRET: &str = "magic-link-test-secret";
+const LINK_URL: &str = "http://localhost:3000/magic-link";
+
+fn token_from_link(body: &str) -> String {
+    let start = body.find("?token=").expect("link missing from email") + "?token=".len();
+    body[start..].split_whitespace().next().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn request_mails_a_link_with_a_signed_token() {
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_email()
+        .with(eq("alice@example.com"))
+        .returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_count_tokens_since()
+        .returning(|_, _, _| Ok(0));
+    repository.expect_create_token()
+        .withf(|user_id, purpose, _, _| *user_id == 7 && *purpose == TokenPurpose::MagicLink)
+        .times(1)
+        .returning(|_, _, _, _| Ok(()));
+
+    let use_case = RequestMagicLinkUseCase::new(repository, token_service.clone(), mailer.clone(), LINK_URL.to_string());
+    use_case.execute("alice@example.com").await.unwrap();
+
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "alice@example.com");
+    assert!(sent[0].body.contains(LINK_URL));
+    assert!(token_service.verify(TokenPurpose::MagicLink, &token_from_link(&sent[0].body)));
+}
+
+#[tokio::test]
+async fn request_for_unknown_email_sends_nothing() {
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_email().returning(|_| Ok(None));
+    repository.expect_create_token().never();
+
+    let use_case = RequestMagicLinkUseCase::new(
+        repository,
+        Arc::new(TokenService::new(SECRET)),
+        mailer.clone(),
+        LINK_URL.to_string(),
+    );
+
+    assert!(use_case.execute("nobody@example.com").await.is_ok());
+    assert!(mailer.sent().is_empty());
+}
+
+#[tokio::test]
+async fn request_over_the_rate_limit_sends_nothing() {
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_find_by_email().returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_count_tokens_since().returning(|_, _, _| Ok(5));
+    repository.expect_create_token().never();
+
+    let use_case = RequestMagicLinkUseCase::new(
+        repository,
+        Arc::new(TokenService::new(SECRET)),
+        mailer.clone(),
+        LINK_URL.to_string(),
+    );
+
+    assert!(use_case.execute("alice@example.com").await.is_ok());
+    assert!(mailer.sent().is_empty());
+}
+
+#[tokio::test]
+async fn verify_exchanges_the_token_for_an_access_token() {
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let token = token_service.generate(TokenPurpose::MagicLink).unwrap();
+    let token_hash = token_service.hash(&token);
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_consume_token()
+        .with(eq(TokenPurpose::MagicLink), eq(token_hash))
+        .times(1)
+        .returning(|_, _| Ok(Some(7)));
+    repository.expect_find_by_id().with(eq(7)).returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_is_suspended().returning(|_| Ok(false));
+    repository.expect_is_pending_approval().returning(|_| Ok(false));
+    repository.expect_mark_email_verified().with(eq(7)).times(1).returning(|_| Ok(()));
+    repository.expect_find_totp().returning(|_| Ok(None));
+    repository.expect_find_role_names().returning(|_| Ok(vec!["user".to_string()]));
+    repository.expect_create_session()
+        .withf(|session| session.user_id == 7)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let use_case = VerifyMagicLinkUseCase::new(repository, token_service, access_tokens(SECRET));
+
+    match use_case.execute(&token, &ClientInfo::default()).await.unwrap() {
+        LoginResponse::Token(response) => assert!(!response.access_token.is_empty()),
+        LoginResponse::MfaRequired(_) => panic!("expected an access token"),
+    }
+}
+
+#[tokio::test]
+async fn verify_rejects_a_token_for_another_purpose() {
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let token = token_service.generate(TokenPurpose::PasswordReset).unwrap();
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_consume_token().never();
+
+    let use_case = VerifyMagicLinkUseCase::new(repository, token_service, access_tokens(SECRET));
+
+    assert!(use_case.execute(&token, &ClientInfo::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn verify_rejects_a_used_token() {
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let token = token_service.generate(TokenPurpose::MagicLink).unwrap();
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_consume_token().returning(|_, _| Ok(None));
+    repository.expect_create_session().never();
+
+    let use_case = VerifyMagicLinkUseCase::new(repository, token_service, access_tokens(SECRET));
+
+    assert!(use_case.execute(&token, &ClientInfo::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn verify_refuses_a_suspended_user() {
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let token = token_service.generate(TokenPurpose::MagicLink).unwrap();
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_consume_token().returning(|_, _| Ok(Some(7)));
+    repository.expect_find_by_id().returning(|_| Ok(Some(user(7, "alice"))));
+    repository.expect_mark_email_verified().returning(|_| Ok(()));
+    repository.expect_is_suspended().with(eq(7)).returning(|_| Ok(true));
+    repository.expect_create_session().never();
+
+    let use_case = VerifyMagicLinkUseCase::new(repository, token_service, access_tokens(SECRET));
+
+    let error = use_case.execute(&token, &ClientInfo::default()).await.unwrap_err();
+    assert_eq!(error.to_string(), ACCOUNT_SUSPENDED);
+}
@@ -0,0 +1,210 @@
+// File: src/tests/message_test.rs
+
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::application::use_cases::contact_use_cases::STRANGER_MESSAGES_DISABLED;
+use crate::application::use_cases::message_use_cases::{
+    DeliverDueMessagesUseCase, GetMessagesUseCase, SendMessageUseCase,
+};
+use crate::domain::entities::contact::ContactSettings;
+use crate::domain::entities::message::{DatabaseMessage, SendMessageDto};
+use crate::domain::entities::moderation::UserSuspension;
+use crate::domain::repositories::contact_repository::MockContactRepository;
+use crate::domain::repositories::message_repository::MockMessageRepository;
+use crate::domain::repositories::moderation_repository::MockModerationRepository;
+
+const ALICE: i32 = 1;
+const BOB: i32 = 2;
+const MESSAGE_ID: i32 = 7;
+
+fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+fn message(deliver_at: Option<NaiveDateTime>, expires_at: Option<NaiveDateTime>) -> DatabaseMessage {
+    DatabaseMessage {
+        id: MESSAGE_ID,
+        sender_id: ALICE,
+        receiver_id: BOB,
+        content: "hello".to_string(),
+        is_read: false,
+        created_at: now(),
+        is_hidden: false,
+        deliver_at,
+        expires_at,
+        is_delivered: deliver_at.is_none(),
+    }
+}
+
+fn moderation(suspended: bool) -> MockModerationRepository {
+    let mut moderation = MockModerationRepository::new();
+    moderation.expect_find_active_suspension()
+        .returning(move |user_id| Ok(suspended.then(|| UserSuspension {
+            id: 1,
+            user_id,
+            suspended_by: None,
+            reason: None,
+            suspended_until: None,
+            lifted_at: None,
+            created_at: now(),
+        })));
+    moderation
+}
+
+fn contacts(allow_messages_from_strangers: bool) -> MockContactRepository {
+    let mut contacts = MockContactRepository::new();
+    contacts.expect_get_settings()
+        .returning(move |user_id| Ok(ContactSettings { user_id, allow_messages_from_strangers }));
+    contacts.expect_are_contacts().returning(|_, _| Ok(false));
+    contacts
+}
+
+fn saving_repository() -> MockMessageRepository {
+    let mut repository = MockMessageRepository::new();
+    repository.expect_save_message()
+        .returning(|message| Ok(DatabaseMessage { id: MESSAGE_ID, ..message }));
+    repository
+}
+
+async fn send(
+    repository: MockMessageRepository,
+    deliver_at: Option<NaiveDateTime>,
+    ttl_seconds: Option<i64>,
+) -> Result<DatabaseMessage, String> {
+    SendMessageUseCase::new(repository, Arc::new(contacts(true)), Arc::new(moderation(false)))
+        .execute(ALICE, SendMessageDto { receiver_id: BOB, content: "hello".to_string(), deliver_at, ttl_seconds })
+        .await
+}
+
+#[tokio::test]
+async fn a_delivery_time_in_the_past_sends_right_away() {
+    let sent = send(saving_repository(), Some(now() - Duration::hours(1)), None).await.unwrap();
+
+    assert_eq!(sent.deliver_at, None);
+    assert!(sent.is_delivered);
+}
+
+#[tokio::test]
+async fn a_delivery_time_in_the_future_holds_the_message_back() {
+    let deliver_at = now() + Duration::hours(1);
+
+    let sent = send(saving_repository(), Some(deliver_at), None).await.unwrap();
+
+    assert_eq!(sent.deliver_at, Some(deliver_at));
+    assert!(!sent.is_delivered);
+}
+
+#[tokio::test]
+async fn the_ttl_counts_from_delivery() {
+    let deliver_at = now() + Duration::hours(1);
+    let scheduled = send(saving_repository(), Some(deliver_at), Some(60)).await.unwrap();
+    assert_eq!(scheduled.expires_at, Some(deliver_at + Duration::seconds(60)));
+
+    let before = now();
+    let immediate = send(saving_repository(), None, Some(60)).await.unwrap();
+    let expires_at = immediate.expires_at.unwrap();
+    assert!(expires_at >= before + Duration::seconds(60) && expires_at <= now() + Duration::seconds(60));
+
+    assert_eq!(send(saving_repository(), None, None).await.unwrap().expires_at, None);
+}
+
+#[tokio::test]
+async fn non_positive_ttls_are_rejected() {
+    for ttl_seconds in [0, -5] {
+        let mut repository = MockMessageRepository::new();
+        repository.expect_save_message().never();
+
+        assert!(send(repository, None, Some(ttl_seconds)).await.is_err(), "{}", ttl_seconds);
+    }
+}
+
+#[tokio::test]
+async fn expired_messages_are_hidden_from_listings() {
+    let mut repository = MockMessageRepository::new();
+    repository.expect_get_messages().returning(|_, _| Ok(vec![
+        DatabaseMessage { id: 1, ..message(None, None) },
+        DatabaseMessage { id: 2, ..message(None, Some(now() - Duration::seconds(1))) },
+        DatabaseMessage { id: 3, ..message(None, Some(now() + Duration::hours(1))) },
+    ]));
+
+    let listed = GetMessagesUseCase::new(repository).execute(ALICE, BOB).await.unwrap();
+
+    assert_eq!(listed.iter().map(|message| message.id).collect::<Vec<_>>(), vec![1, 3]);
+}
+
+fn due_repository() -> MockMessageRepository {
+    let mut repository = MockMessageRepository::new();
+    repository.expect_find_due_messages()
+        .returning(|now| Ok(vec![message(Some(now - Duration::seconds(1)), None)]));
+    repository
+}
+
+async fn deliver(
+    repository: MockMessageRepository,
+    contacts: MockContactRepository,
+    moderation: MockModerationRepository,
+) -> Vec<DatabaseMessage> {
+    DeliverDueMessagesUseCase::new(repository, Arc::new(contacts), Arc::new(moderation))
+        .execute(now())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn due_messages_are_delivered_once() {
+    let mut repository = due_repository();
+    repository.expect_mark_as_delivered()
+        .withf(|id| *id == MESSAGE_ID)
+        .times(1)
+        .returning(|_| Ok(true));
+    repository.expect_delete_message().never();
+
+    let delivered = deliver(repository, contacts(true), moderation(false)).await;
+    assert_eq!(delivered.len(), 1);
+    assert!(delivered[0].is_delivered);
+
+    // Another tick got there first
+    let mut repository = due_repository();
+    repository.expect_mark_as_delivered().returning(|_| Ok(false));
+    assert!(deliver(repository, contacts(true), moderation(false)).await.is_empty());
+}
+
+#[tokio::test]
+async fn due_messages_from_suspended_senders_are_dropped() {
+    let mut repository = due_repository();
+    repository.expect_mark_as_delivered().never();
+    repository.expect_delete_message()
+        .withf(|id| *id == MESSAGE_ID)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    assert!(deliver(repository, contacts(true), moderation(true)).await.is_empty());
+}
+
+#[tokio::test]
+async fn due_messages_the_recipient_no_longer_accepts_are_dropped() {
+    let mut repository = due_repository();
+    repository.expect_mark_as_delivered().never();
+    repository.expect_delete_message().times(1).returning(|_| Ok(()));
+
+    assert!(deliver(repository, contacts(false), moderation(false)).await.is_empty());
+
+    // The same rule the sender hit when scheduling it would have hit now
+    let refused = SendMessageUseCase::new(saving_repository(), Arc::new(contacts(false)), Arc::new(moderation(false)))
+        .execute(ALICE, SendMessageDto { receiver_id: BOB, content: "hello".to_string(), deliver_at: None, ttl_seconds: None })
+        .await;
+    assert_eq!(refused.unwrap_err(), STRANGER_MESSAGES_DISABLED);
+}
+
+#[tokio::test]
+async fn due_messages_stay_scheduled_when_the_check_fails() {
+    let mut moderation = MockModerationRepository::new();
+    moderation.expect_find_active_suspension()
+        .returning(|_| Err("connection refused".into()));
+    let mut repository = due_repository();
+    repository.expect_mark_as_delivered().never();
+    repository.expect_delete_message().never();
+
+    assert!(deliver(repository, contacts(true), moderation).await.is_empty());
+}
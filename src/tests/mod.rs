@@ -1 +1,29 @@
-pub mod upload_avatar_test;
\ No newline at end of file
+pub mod fixtures;
+pub mod upload_avatar_test;
+pub mod oidc_test;
+pub mod webauthn_test;
+pub mod magic_link_test;
+pub mod validation_test;
+pub mod registration_test;
+pub mod impersonation_test;
+pub mod audit_test;
+pub mod logging_test;
+pub mod user_response_test;
+pub mod user_test;
+pub mod login_throttle_test;
+pub mod totp_test;
+pub mod signing_key_test;
+pub mod contact_test;
+pub mod moderation_test;
+pub mod lockout_test;
+pub mod oauth_test;
+pub mod access_token_test;
+pub mod api_key_test;
+pub mod session_test;
+pub mod announcement_test;
+pub mod message_test;
+pub mod password_reset_test;
+pub mod email_verification_test;
+pub mod client_info_test;
+pub mod error_response_test;
+pub mod notification_test;
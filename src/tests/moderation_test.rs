@@ -0,0 +1,220 @@
+// File: src/tests/moderation_test.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::application::use_cases::moderation_use_cases::{
+    DismissReportUseCase, HideMessageUseCase, ReportMessageUseCase, SuspendUserUseCase,
+};
+use crate::domain::entities::message::DatabaseMessage;
+use crate::domain::entities::moderation::{
+    CreateReportDto, MessageReport, ModerationAction, ModerationActionType, NewModerationAction,
+    ReportReason, ReportStatus, SuspendUserDto, UserSuspension,
+};
+use crate::domain::repositories::moderation_repository::MockModerationRepository;
+use crate::tests::fixtures::{error_kind, StubRoleRepository};
+
+const ADMIN_ID: i32 = 1;
+const MEMBER_ID: i32 = 2;
+const SENDER_ID: i32 = 3;
+const MESSAGE_ID: i32 = 20;
+const REPORT_ID: i32 = 30;
+
+fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+fn roles() -> Arc<StubRoleRepository> {
+    Arc::new(StubRoleRepository::default().with_role(ADMIN_ID, "admin"))
+}
+
+fn message() -> DatabaseMessage {
+    DatabaseMessage {
+        id: MESSAGE_ID,
+        sender_id: SENDER_ID,
+        receiver_id: MEMBER_ID,
+        content: "hello".to_string(),
+        is_read: false,
+        created_at: now(),
+        is_hidden: false,
+        deliver_at: None,
+        expires_at: None,
+        is_delivered: true,
+    }
+}
+
+fn report(status: ReportStatus) -> MessageReport {
+    MessageReport {
+        id: REPORT_ID,
+        message_id: MESSAGE_ID,
+        reporter_id: MEMBER_ID,
+        reason: ReportReason::Spam,
+        details: None,
+        status,
+        resolved_by: None,
+        resolved_at: None,
+        created_at: now(),
+    }
+}
+
+/// The log entry a repository would store for `action`.
+fn logged(action: NewModerationAction) -> ModerationAction {
+    ModerationAction {
+        id: 1,
+        moderator_id: Some(action.moderator_id),
+        action: action.action,
+        target_user_id: action.target_user_id,
+        target_message_id: action.target_message_id,
+        report_id: action.report_id,
+        note: action.note,
+        created_at: now(),
+    }
+}
+
+fn suspension_dto() -> SuspendUserDto {
+    SuspendUserDto { reason: Some("spam".to_string()), suspended_until: None }
+}
+
+#[tokio::test]
+async fn only_admins_can_moderate() {
+    // No expectations: the repository must not be touched
+    let repository = Arc::new(MockModerationRepository::new());
+
+    let suspend = SuspendUserUseCase::new(repository.clone(), roles())
+        .execute(MEMBER_ID, SENDER_ID, suspension_dto())
+        .await;
+    let hide = HideMessageUseCase::new(repository.clone(), roles())
+        .execute(MEMBER_ID, MESSAGE_ID, true, None)
+        .await;
+    let dismiss = DismissReportUseCase::new(repository, roles())
+        .execute(MEMBER_ID, REPORT_ID, None)
+        .await;
+
+    assert_eq!(error_kind(&*suspend.unwrap_err()), Some(ErrorKind::PermissionDenied));
+    assert_eq!(error_kind(&*hide.unwrap_err()), Some(ErrorKind::PermissionDenied));
+    assert_eq!(error_kind(&*dismiss.unwrap_err()), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn suspending_a_user_logs_the_action_with_the_suspension() {
+    let mut repository = MockModerationRepository::new();
+    repository.expect_suspend_user()
+        .withf(|user_id, reason, until, action| {
+            *user_id == SENDER_ID
+                && reason.as_deref() == Some("spam")
+                && until.is_none()
+                && action.moderator_id == ADMIN_ID
+                && action.action == ModerationActionType::SuspendUser
+                && action.target_user_id == Some(SENDER_ID)
+                && action.note.as_deref() == Some("spam")
+        })
+        .times(1)
+        .returning(|user_id, reason, suspended_until, action| Ok(UserSuspension {
+            id: 1,
+            user_id,
+            suspended_by: Some(action.moderator_id),
+            reason,
+            suspended_until,
+            lifted_at: None,
+            created_at: now(),
+        }));
+
+    let suspension = SuspendUserUseCase::new(Arc::new(repository), roles())
+        .execute(ADMIN_ID, SENDER_ID, suspension_dto())
+        .await
+        .unwrap();
+
+    assert_eq!(suspension.user_id, SENDER_ID);
+    assert_eq!(suspension.suspended_by, Some(ADMIN_ID));
+}
+
+#[tokio::test]
+async fn admins_cannot_suspend_themselves() {
+    let result = SuspendUserUseCase::new(Arc::new(MockModerationRepository::new()), roles())
+        .execute(ADMIN_ID, ADMIN_ID, suspension_dto())
+        .await;
+
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::InvalidInput));
+}
+
+#[tokio::test]
+async fn lifting_without_a_suspension_is_not_found() {
+    let mut repository = MockModerationRepository::new();
+    repository.expect_lift_suspensions().times(1).returning(|_, _| Ok(None));
+
+    let result = SuspendUserUseCase::new(Arc::new(repository), roles())
+        .lift(ADMIN_ID, SENDER_ID, None)
+        .await;
+
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::NotFound));
+}
+
+#[tokio::test]
+async fn hiding_a_message_logs_the_action_against_its_sender() {
+    let mut repository = MockModerationRepository::new();
+    repository.expect_find_message().returning(|_| Ok(Some(message())));
+    repository.expect_set_message_hidden()
+        .withf(|message_id, hidden, action| {
+            *message_id == MESSAGE_ID
+                && *hidden
+                && action.action == ModerationActionType::HideMessage
+                && action.target_user_id == Some(SENDER_ID)
+                && action.target_message_id == Some(MESSAGE_ID)
+        })
+        .times(1)
+        .returning(|_, _, action| Ok(logged(action)));
+
+    let action = HideMessageUseCase::new(Arc::new(repository), roles())
+        .execute(ADMIN_ID, MESSAGE_ID, true, None)
+        .await
+        .unwrap();
+
+    assert_eq!(action.moderator_id, Some(ADMIN_ID));
+}
+
+#[tokio::test]
+async fn only_open_reports_can_be_dismissed() {
+    let mut repository = MockModerationRepository::new();
+    repository.expect_find_report().returning(|_| Ok(Some(report(ReportStatus::Actioned))));
+    let result = DismissReportUseCase::new(Arc::new(repository), roles())
+        .execute(ADMIN_ID, REPORT_ID, None)
+        .await;
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::InvalidInput));
+
+    let mut repository = MockModerationRepository::new();
+    repository.expect_find_report().returning(|_| Ok(Some(report(ReportStatus::Open))));
+    repository.expect_resolve_report()
+        .withf(|report_id, status, action| {
+            *report_id == REPORT_ID
+                && *status == ReportStatus::Dismissed
+                && action.action == ModerationActionType::DismissReport
+                && action.report_id == Some(REPORT_ID)
+        })
+        .times(1)
+        .returning(|_, status, action| Ok(MessageReport {
+            resolved_by: Some(action.moderator_id),
+            ..report(status)
+        }));
+    let dismissed = DismissReportUseCase::new(Arc::new(repository), roles())
+        .execute(ADMIN_ID, REPORT_ID, None)
+        .await
+        .unwrap();
+    assert_eq!(dismissed.status, ReportStatus::Dismissed);
+}
+
+#[tokio::test]
+async fn only_the_recipient_can_report_a_message() {
+    let dto = || CreateReportDto { message_id: MESSAGE_ID, reason: ReportReason::Spam, details: None };
+    let use_case = || {
+        let mut repository = MockModerationRepository::new();
+        repository.expect_find_message().returning(|_| Ok(Some(message())));
+        ReportMessageUseCase::new(Arc::new(repository))
+    };
+
+    let own = use_case().execute(SENDER_ID, dto()).await;
+    let outsider = use_case().execute(ADMIN_ID, dto()).await;
+
+    assert_eq!(error_kind(&*own.unwrap_err()), Some(ErrorKind::InvalidInput));
+    assert_eq!(error_kind(&*outsider.unwrap_err()), Some(ErrorKind::NotFound));
+}
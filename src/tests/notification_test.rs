@@ -0,0 +1,93 @@
+// File: src/tests/notification_test.rs
+
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use mockall::predicate::eq;
+
+use crate::domain::entities::notification::{NotificationMode, NotificationPreference};
+use crate::domain::repositories::notification_repository::MockNotificationRepository;
+use crate::infrastructure::websocket::realtime_message_manager::RealtimeMessageManager;
+use crate::infrastructure::websocket::user_status_manager::UserStatusManager;
+
+const ALICE: i32 = 1;
+const BOB: i32 = 2;
+
+fn preference(mode: NotificationMode, muted_until: Option<NaiveDateTime>) -> NotificationPreference {
+    NotificationPreference {
+        id: 1,
+        user_id: BOB,
+        counterpart_user_id: Some(ALICE),
+        group_id: None,
+        mode,
+        muted_until,
+        updated_at: Utc::now().naive_utc(),
+    }
+}
+
+fn manager(repository: MockNotificationRepository) -> RealtimeMessageManager {
+    RealtimeMessageManager::new(Arc::new(UserStatusManager::new()), Arc::new(repository))
+}
+
+/// The recipient's stored preference for messages from Alice.
+fn repository_with(stored: Option<NotificationPreference>) -> MockNotificationRepository {
+    let mut repository = MockNotificationRepository::new();
+    repository.expect_find_for_counterpart()
+        .with(eq(BOB), eq(ALICE))
+        .times(1)
+        .returning(move |_, _| Ok(stored.clone()));
+    repository
+}
+
+#[tokio::test]
+async fn the_recipients_preference_sets_the_hint() {
+    let hint = manager(repository_with(Some(preference(NotificationMode::MentionsOnly, None))))
+        .notification_hint(BOB, ALICE)
+        .await;
+
+    assert_eq!(hint.mode, NotificationMode::MentionsOnly);
+    assert_eq!(hint.muted_until, None);
+}
+
+#[tokio::test]
+async fn a_mute_applies_until_it_runs_out() {
+    let until = Utc::now().naive_utc() + Duration::hours(1);
+    let hint = manager(repository_with(Some(preference(NotificationMode::Muted, Some(until)))))
+        .notification_hint(BOB, ALICE)
+        .await;
+    assert_eq!(hint.mode, NotificationMode::Muted);
+    assert_eq!(hint.muted_until, Some(until));
+
+    let expired = Utc::now().naive_utc() - Duration::minutes(1);
+    let hint = manager(repository_with(Some(preference(NotificationMode::Muted, Some(expired)))))
+        .notification_hint(BOB, ALICE)
+        .await;
+    assert_eq!(hint.mode, NotificationMode::All);
+}
+
+#[tokio::test]
+async fn without_a_preference_every_message_notifies() {
+    let hint = manager(repository_with(None)).notification_hint(BOB, ALICE).await;
+
+    assert_eq!(hint.mode, NotificationMode::All);
+}
+
+#[tokio::test]
+async fn a_failed_lookup_still_notifies() {
+    let mut repository = MockNotificationRepository::new();
+    repository.expect_find_for_counterpart()
+        .returning(|_, _| Err("connection refused".into()));
+
+    let hint = manager(repository).notification_hint(BOB, ALICE).await;
+
+    assert_eq!(hint.mode, NotificationMode::All);
+}
+
+#[tokio::test]
+async fn offline_recipients_are_not_pushed_to() {
+    let mut repository = MockNotificationRepository::new();
+    repository.expect_find_for_counterpart().never();
+
+    let result = manager(repository).send_message(ALICE, BOB, "hello".to_string()).await;
+
+    assert_eq!(result, Err(format!("User {} is not connected", BOB)));
+}
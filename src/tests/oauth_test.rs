@@ -0,0 +1,197 @@
+// File: src/tests/oauth_test.rs
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use reqwest::Url;
+
+use crate::application::use_cases::oauth_use_cases::{AuthorizeUseCase, IssueOAuthTokenUseCase};
+use crate::domain::entities::oauth::{
+    AuthorizationCode, AuthorizationRequest, OAuthClient, OAuthError, OAuthTokenResponse, TokenRequest,
+    UserProfile, GRANT_AUTHORIZATION_CODE, SCOPE_OPENID,
+};
+use crate::domain::repositories::oauth_repository::MockOAuthRepository;
+use crate::domain::services::oidc::pkce_challenge;
+use crate::domain::services::signing_key::{SigningKey, SigningKeys};
+use crate::domain::services::token_service::TokenService;
+
+const SECRET: &str = "oauth-test-secret";
+const ISSUER: &str = "http://localhost:8080";
+const CLIENT_ID: &str = "notes-app";
+const REDIRECT_URI: &str = "https://notes.example.com/callback";
+const CODE_VERIFIER: &str = "a-code-verifier-long-enough-to-satisfy-rfc-7636-rules";
+const USER_ID: i32 = 7;
+
+/// A public client allowed the authorization code grant and the `openid` scope.
+fn client() -> OAuthClient {
+    OAuthClient {
+        id: 1,
+        client_id: CLIENT_ID.to_string(),
+        client_secret_hash: None,
+        name: "Notes".to_string(),
+        redirect_uris: vec![REDIRECT_URI.to_string()],
+        grant_types: vec![GRANT_AUTHORIZATION_CODE.to_string()],
+        scopes: vec![SCOPE_OPENID.to_string()],
+        created_by: None,
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+fn profile(user_id: i32) -> UserProfile {
+    UserProfile {
+        user_id,
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        email_verified: true,
+        first_name: None,
+        middle_name: None,
+        last_name: None,
+        updated_at: None,
+    }
+}
+
+/// Authorization codes kept in memory; consuming one removes it, like the database.
+fn repository() -> MockOAuthRepository {
+    let codes: Arc<Mutex<HashMap<String, AuthorizationCode>>> = Arc::default();
+    let mut repository = MockOAuthRepository::new();
+
+    repository.expect_find_client()
+        .returning(|client_id| Ok((client_id == CLIENT_ID).then(client)));
+    let saved = codes.clone();
+    repository.expect_save_authorization_code().returning(move |code| {
+        saved.lock().unwrap().insert(code.code_hash, AuthorizationCode {
+            client_id: code.client_id,
+            user_id: code.user_id,
+            redirect_uri: code.redirect_uri,
+            scope: code.scope,
+            code_challenge: code.code_challenge,
+            nonce: code.nonce,
+        });
+        Ok(())
+    });
+    repository.expect_consume_authorization_code()
+        .returning(move |code_hash| Ok(codes.lock().unwrap().remove(&code_hash)));
+    repository.expect_find_user_profile().returning(|user_id| Ok(Some(profile(user_id))));
+
+    repository
+}
+
+struct Fixture {
+    authorize: AuthorizeUseCase<MockOAuthRepository>,
+    issue_token: IssueOAuthTokenUseCase<MockOAuthRepository>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let repository = Arc::new(repository());
+        let token_service = Arc::new(TokenService::new(SECRET));
+        let signing_keys = Arc::new(SigningKeys::new(SigningKey::derive_from_secret(SECRET).unwrap(), vec![]).unwrap());
+
+        Self {
+            authorize: AuthorizeUseCase::new(repository.clone(), token_service.clone(), format!("{}/consent", ISSUER)),
+            issue_token: IssueOAuthTokenUseCase::new(repository, token_service, signing_keys, ISSUER.to_string()),
+        }
+    }
+
+    /// Approves a request for `openid` and returns the code handed to the client.
+    async fn code(&self) -> String {
+        let grant = self.authorize.approve(USER_ID, authorization_request()).await.unwrap();
+        let redirect_to = Url::parse(&grant.redirect_to).unwrap();
+        redirect_to.query_pairs().find(|(name, _)| name == "code").unwrap().1.into_owned()
+    }
+
+    async fn exchange(&self, code: &str, redirect_uri: &str, code_verifier: &str) -> Result<OAuthTokenResponse, &'static str> {
+        let request = TokenRequest {
+            grant_type: GRANT_AUTHORIZATION_CODE.to_string(),
+            code: Some(code.to_string()),
+            redirect_uri: Some(redirect_uri.to_string()),
+            code_verifier: Some(code_verifier.to_string()),
+            client_id: Some(CLIENT_ID.to_string()),
+            client_secret: None,
+            scope: None,
+        };
+        self.issue_token.execute(request, None).await.map_err(|e| oauth_error(&*e))
+    }
+}
+
+fn authorization_request() -> AuthorizationRequest {
+    AuthorizationRequest {
+        response_type: "code".to_string(),
+        client_id: CLIENT_ID.to_string(),
+        redirect_uri: REDIRECT_URI.to_string(),
+        scope: Some(SCOPE_OPENID.to_string()),
+        state: Some("xyz".to_string()),
+        code_challenge: Some(pkce_challenge(CODE_VERIFIER)),
+        code_challenge_method: Some("S256".to_string()),
+        nonce: None,
+    }
+}
+
+/// The RFC 6749 error code a use case failed with.
+fn oauth_error(e: &(dyn std::error::Error + Send + Sync + 'static)) -> &'static str {
+    e.downcast_ref::<OAuthError>().expect("an OAuth error").error
+}
+
+#[tokio::test]
+async fn an_approved_code_is_exchanged_for_tokens() {
+    let fixture = Fixture::new();
+
+    let response = fixture.exchange(&fixture.code().await, REDIRECT_URI, CODE_VERIFIER).await.unwrap();
+
+    assert_eq!(response.scope, SCOPE_OPENID);
+    assert!(response.id_token.is_some());
+}
+
+#[tokio::test]
+async fn a_code_can_only_be_exchanged_once() {
+    let fixture = Fixture::new();
+    let code = fixture.code().await;
+
+    fixture.exchange(&code, REDIRECT_URI, CODE_VERIFIER).await.unwrap();
+
+    assert_eq!(fixture.exchange(&code, REDIRECT_URI, CODE_VERIFIER).await.unwrap_err(), "invalid_grant");
+}
+
+#[tokio::test]
+async fn a_wrong_code_verifier_spends_the_code() {
+    let fixture = Fixture::new();
+    let code = fixture.code().await;
+
+    assert_eq!(fixture.exchange(&code, REDIRECT_URI, "someone-elses-verifier").await.unwrap_err(), "invalid_grant");
+    assert_eq!(fixture.exchange(&code, REDIRECT_URI, CODE_VERIFIER).await.unwrap_err(), "invalid_grant");
+}
+
+#[tokio::test]
+async fn the_redirect_uri_must_match_the_authorization() {
+    let fixture = Fixture::new();
+    let code = fixture.code().await;
+
+    let result = fixture.exchange(&code, "https://notes.example.com/other", CODE_VERIFIER).await;
+
+    assert_eq!(result.unwrap_err(), "invalid_grant");
+}
+
+#[tokio::test]
+async fn requests_need_a_registered_redirect_uri_and_pkce() {
+    let fixture = Fixture::new();
+
+    let unregistered = AuthorizationRequest {
+        redirect_uri: "https://evil.example.com/callback".to_string(),
+        ..authorization_request()
+    };
+    let plain_pkce = AuthorizationRequest {
+        code_challenge: Some(CODE_VERIFIER.to_string()),
+        code_challenge_method: Some("plain".to_string()),
+        ..authorization_request()
+    };
+    let without_pkce = AuthorizationRequest {
+        code_challenge: None,
+        code_challenge_method: None,
+        ..authorization_request()
+    };
+
+    for request in [unregistered, plain_pkce, without_pkce] {
+        let result = fixture.authorize.approve(USER_ID, request).await;
+        assert_eq!(oauth_error(&*result.unwrap_err()), "invalid_request");
+    }
+}
@@ -0,0 +1,69 @@
+// File: src/tests/oidc_test.rs
+
+use crate::domain::entities::identity::ExternalIdentity;
+use crate::domain::services::oidc::{pkce_challenge, OidcProvider};
+use crate::infrastructure::oidc::mock_provider::MockOidcProvider;
+
+const REDIRECT_URI: &str = "http://127.0.0.1:8080/api/v1/auth/oidc/mock/callback";
+const CODE_VERIFIER: &str = "test-code-verifier-with-enough-entropy-0123456789";
+
+fn mock_provider() -> MockOidcProvider {
+    MockOidcProvider::new(ExternalIdentity {
+        provider: "mock".to_string(),
+        subject: "subject-42".to_string(),
+        email: Some("jane@example.com".to_string()),
+        email_verified: true,
+        preferred_username: Some("jane".to_string()),
+        given_name: Some("Jane".to_string()),
+        family_name: Some("Doe".to_string()),
+    })
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    let query = url.split_once('?')?.1;
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+#[tokio::test]
+async fn test_authorization_url_redirects_back_with_code_and_state() {
+    let provider = mock_provider();
+    let url = provider.authorization_url("state-123", &pkce_challenge(CODE_VERIFIER), REDIRECT_URI);
+
+    assert!(url.starts_with(REDIRECT_URI), "Unexpected redirect: {}", url);
+    assert_eq!(query_param(&url, "state").as_deref(), Some("state-123"));
+    assert!(query_param(&url, "code").is_some(), "Redirect should carry a code");
+}
+
+#[tokio::test]
+async fn test_exchange_code_with_matching_verifier() {
+    let provider = mock_provider();
+    let code = provider.issue_code(&pkce_challenge(CODE_VERIFIER));
+
+    let identity = provider.exchange_code(&code, CODE_VERIFIER, REDIRECT_URI).await
+        .expect("Exchange with the matching verifier should succeed");
+
+    assert_eq!(identity.provider, "mock");
+    assert_eq!(identity.subject, "subject-42");
+    assert_eq!(identity.email.as_deref(), Some("jane@example.com"));
+}
+
+#[tokio::test]
+async fn test_exchange_code_rejects_wrong_verifier() {
+    let provider = mock_provider();
+    let code = provider.issue_code(&pkce_challenge(CODE_VERIFIER));
+
+    let result = provider.exchange_code(&code, "some-other-verifier", REDIRECT_URI).await;
+    assert!(result.is_err(), "Exchange with a different verifier should fail");
+}
+
+#[tokio::test]
+async fn test_exchange_code_is_single_use() {
+    let provider = mock_provider();
+    let code = provider.issue_code(&pkce_challenge(CODE_VERIFIER));
+
+    assert!(provider.exchange_code(&code, CODE_VERIFIER, REDIRECT_URI).await.is_ok());
+    assert!(provider.exchange_code(&code, CODE_VERIFIER, REDIRECT_URI).await.is_err(), "A code must not be exchangeable twice");
+}
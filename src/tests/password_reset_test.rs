@@ -0,0 +1,258 @@
+// File: src/tests/password_reset_test.rs
+
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::application::use_cases::auth_use_cases::{
+    ConfirmPasswordResetUseCase, RequestPasswordResetUseCase, ValidateSessionUseCase,
+};
+use crate::domain::entities::auth::{ConfirmPasswordResetDto, TokenPurpose};
+use crate::domain::entities::session::{ClientInfo, Session};
+use crate::domain::repositories::audit_repository::MockAuditRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::validation::PasswordPolicy;
+use crate::infrastructure::mail::memory_mailer::MemoryMailer;
+use crate::tests::fixtures::{claims, error_kind, user};
+
+const SECRET: &str = "password-reset-test-secret";
+const RESET_URL: &str = "http://localhost:3000/reset-password";
+const ALICE: i32 = 7;
+const NEW_PASSWORD: &str = "Brand-new-passw0rd!";
+
+/// A stored token, like a row of user_tokens.
+struct StoredToken {
+    purpose: TokenPurpose,
+    token_hash: String,
+    expires_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+    used: bool,
+}
+
+#[derive(Default)]
+struct Store {
+    tokens: Vec<StoredToken>,
+    password_updates: usize,
+    sessions_revoked: bool,
+    api_keys_revoked: bool,
+}
+
+/// Tokens kept in memory; used and expired ones are not consumed, like the database.
+fn repository(store: Arc<Mutex<Store>>) -> MockAuthRepository {
+    let mut repository = MockAuthRepository::new();
+
+    repository.expect_find_by_email()
+        .returning(|email| Ok((email == "alice@example.com").then(|| user(ALICE, "alice"))));
+
+    let counted = store.clone();
+    repository.expect_count_tokens_since().returning(move |_, purpose, since| {
+        Ok(counted.lock().unwrap().tokens.iter()
+            .filter(|token| token.purpose == purpose && token.created_at >= since)
+            .count() as i64)
+    });
+
+    let created = store.clone();
+    repository.expect_create_token().returning(move |_, purpose, token_hash, expires_at| {
+        created.lock().unwrap().tokens.push(StoredToken {
+            purpose,
+            token_hash,
+            expires_at,
+            created_at: Utc::now().naive_utc(),
+            used: false,
+        });
+        Ok(())
+    });
+
+    let consumed = store.clone();
+    repository.expect_consume_token().returning(move |purpose, token_hash| {
+        let now = Utc::now().naive_utc();
+        let mut store = consumed.lock().unwrap();
+        let token = store.tokens.iter_mut().find(|token| {
+            token.purpose == purpose && token.token_hash == token_hash && !token.used && token.expires_at > now
+        });
+        Ok(token.map(|token| token.used = true).map(|_| ALICE))
+    });
+
+    let updated = store.clone();
+    repository.expect_update_password().returning(move |_, _| {
+        updated.lock().unwrap().password_updates += 1;
+        Ok(())
+    });
+
+    let revoked = store.clone();
+    repository.expect_revoke_tokens().returning(move |_, purpose| {
+        for token in revoked.lock().unwrap().tokens.iter_mut().filter(|token| token.purpose == purpose) {
+            token.used = true;
+        }
+        Ok(())
+    });
+
+    let sessions = store.clone();
+    repository.expect_revoke_sessions().returning(move |_| {
+        sessions.lock().unwrap().sessions_revoked = true;
+        Ok(())
+    });
+
+    let api_keys = store.clone();
+    repository.expect_revoke_api_keys().returning(move |_| {
+        api_keys.lock().unwrap().api_keys_revoked = true;
+        Ok(())
+    });
+
+    repository.expect_find_active_session().returning(move |_, token_id| {
+        let now = Utc::now().naive_utc();
+        Ok((!store.lock().unwrap().sessions_revoked).then(|| Session {
+            id: 1,
+            token_id: token_id.to_string(),
+            user_agent: None,
+            ip_address: None,
+            expires_at: now + Duration::hours(1),
+            last_active_at: now,
+            created_at: now,
+            impersonated: false,
+            current: false,
+        }))
+    });
+    repository.expect_touch_session().returning(|_| Ok(()));
+
+    repository
+}
+
+fn token_from_link(body: &str) -> String {
+    let start = body.find("?token=").expect("link missing from email") + "?token=".len();
+    body[start..].split_whitespace().next().unwrap().to_string()
+}
+
+/// Asks for a reset link for Alice and returns every token mailed so far.
+async fn request_reset(store: &Arc<Mutex<Store>>, token_service: &Arc<TokenService>, mailer: &Arc<MemoryMailer>) -> Vec<String> {
+    RequestPasswordResetUseCase::new(repository(store.clone()), token_service.clone(), mailer.clone(), RESET_URL.to_string())
+        .execute("alice@example.com")
+        .await
+        .unwrap();
+
+    mailer.sent().iter().map(|email| token_from_link(&email.body)).collect()
+}
+
+async fn confirm_reset(store: &Arc<Mutex<Store>>, token_service: &Arc<TokenService>, token: &str) -> Result<(), Option<ErrorKind>> {
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event().returning(|_| Ok(()));
+
+    ConfirmPasswordResetUseCase::new(
+        repository(store.clone()),
+        token_service.clone(),
+        Arc::new(PasswordPolicy::default()),
+        Arc::new(audit),
+    )
+        .execute(
+            ConfirmPasswordResetDto { token: token.to_string(), new_password: NEW_PASSWORD.to_string() },
+            &ClientInfo::default(),
+        )
+        .await
+        .map_err(|e| error_kind(&*e))
+}
+
+#[tokio::test]
+async fn a_reset_link_works_only_once() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let tokens = request_reset(&store, &token_service, &mailer).await;
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(mailer.sent()[0].to, "alice@example.com");
+
+    assert_eq!(confirm_reset(&store, &token_service, &tokens[0]).await, Ok(()));
+    assert_eq!(confirm_reset(&store, &token_service, &tokens[0]).await, Err(Some(ErrorKind::InvalidInput)));
+    assert_eq!(store.lock().unwrap().password_updates, 1);
+}
+
+#[tokio::test]
+async fn a_reset_revokes_the_other_outstanding_links() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    request_reset(&store, &token_service, &mailer).await;
+    let tokens = request_reset(&store, &token_service, &mailer).await;
+
+    assert_eq!(confirm_reset(&store, &token_service, &tokens[1]).await, Ok(()));
+    assert_eq!(confirm_reset(&store, &token_service, &tokens[0]).await, Err(Some(ErrorKind::InvalidInput)));
+}
+
+#[tokio::test]
+async fn reset_links_expire_after_an_hour() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let before = Utc::now().naive_utc();
+    let tokens = request_reset(&store, &token_service, &mailer).await;
+    let expires_at = store.lock().unwrap().tokens[0].expires_at;
+    assert!(expires_at >= before + Duration::minutes(60) && expires_at <= Utc::now().naive_utc() + Duration::minutes(60));
+
+    store.lock().unwrap().tokens[0].expires_at = Utc::now().naive_utc() - Duration::seconds(1);
+
+    assert_eq!(confirm_reset(&store, &token_service, &tokens[0]).await, Err(Some(ErrorKind::InvalidInput)));
+    assert_eq!(store.lock().unwrap().password_updates, 0);
+}
+
+#[tokio::test]
+async fn forged_tokens_are_refused() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let magic_link = token_service.generate(TokenPurpose::MagicLink).unwrap();
+
+    assert_eq!(confirm_reset(&store, &token_service, &magic_link).await, Err(Some(ErrorKind::InvalidInput)));
+    assert_eq!(confirm_reset(&store, &token_service, "not-a-token").await, Err(Some(ErrorKind::InvalidInput)));
+}
+
+#[tokio::test]
+async fn only_three_links_are_mailed_per_hour() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    for _ in 0..5 {
+        request_reset(&store, &token_service, &mailer).await;
+    }
+    assert_eq!(mailer.sent().len(), 3);
+
+    // Requests older than an hour no longer count
+    for token in store.lock().unwrap().tokens.iter_mut() {
+        token.created_at -= Duration::hours(2);
+    }
+    request_reset(&store, &token_service, &mailer).await;
+    assert_eq!(mailer.sent().len(), 4);
+}
+
+#[tokio::test]
+async fn unknown_addresses_get_no_mail() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    RequestPasswordResetUseCase::new(repository(store.clone()), Arc::new(TokenService::new(SECRET)), mailer.clone(), RESET_URL.to_string())
+        .execute("nobody@example.com")
+        .await
+        .unwrap();
+
+    assert!(mailer.sent().is_empty());
+    assert!(store.lock().unwrap().tokens.is_empty());
+}
+
+#[tokio::test]
+async fn a_reset_signs_out_every_session() {
+    let store = Arc::new(Mutex::new(Store::default()));
+    let token_service = Arc::new(TokenService::new(SECRET));
+    let mailer = Arc::new(MemoryMailer::new());
+    let signed_in = claims(ALICE, &["user"]);
+    let validate = || ValidateSessionUseCase::new(repository(store.clone()));
+
+    assert!(validate().execute(&signed_in).await.unwrap());
+
+    let tokens = request_reset(&store, &token_service, &mailer).await;
+    confirm_reset(&store, &token_service, &tokens[0]).await.unwrap();
+
+    assert!(!validate().execute(&signed_in).await.unwrap());
+    assert!(store.lock().unwrap().api_keys_revoked);
+}
@@ -0,0 +1,157 @@
+// File: src/tests/registration_test.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use crate::application::use_cases::auth_use_cases::{RegisterUseCase, SendVerificationEmailUseCase};
+use crate::domain::entities::auth::RegisterUserDto;
+use crate::domain::entities::registration::RegistrationMode;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::validation::PasswordPolicy;
+use crate::infrastructure::mail::memory_mailer::MemoryMailer;
+use crate::tests::fixtures::{error_kind, user};
+
+const SECRET: &str = "registration-test-secret";
+const INVITE_CODE: &str = "invite-code-0123456789";
+
+fn register_dto(invite_code: Option<&str>) -> RegisterUserDto {
+    RegisterUserDto {
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        password: "correct horse battery".to_string(),
+        first_name: None,
+        middle_name: None,
+        last_name: None,
+        invite_code: invite_code.map(str::to_string),
+    }
+}
+
+/// The verification email is best effort, so its repository accepts anything.
+fn send_verification_email() -> Arc<SendVerificationEmailUseCase<MockAuthRepository>> {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_revoke_tokens().returning(|_, _| Ok(()));
+    repository.expect_create_token().returning(|_, _, _, _| Ok(()));
+
+    Arc::new(SendVerificationEmailUseCase::new(
+        repository,
+        Arc::new(TokenService::new(SECRET)),
+        Arc::new(MemoryMailer::new()),
+        "http://localhost:8080".to_string(),
+    ))
+}
+
+fn register_use_case(repository: MockAuthRepository, mode: RegistrationMode) -> RegisterUseCase<MockAuthRepository> {
+    RegisterUseCase::new(
+        repository,
+        send_verification_email(),
+        Arc::new(PasswordPolicy::default()),
+        Arc::new(TokenService::new(SECRET)),
+        mode,
+    )
+}
+
+#[tokio::test]
+async fn open_registration_ignores_invite_codes() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register()
+        .withf(|_, admission| admission.invite_code_hash.is_none() && !admission.pending_approval)
+        .times(1)
+        .returning(|_, _| Ok(user(7, "alice")));
+
+    let registered = register_use_case(repository, RegistrationMode::Open)
+        .execute(register_dto(Some(INVITE_CODE)))
+        .await
+        .unwrap();
+
+    assert!(!registered.pending_approval);
+}
+
+#[tokio::test]
+async fn closed_registration_refuses_everyone() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register().never();
+
+    let e = register_use_case(repository, RegistrationMode::Closed)
+        .execute(register_dto(Some(INVITE_CODE)))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn invite_only_registration_requires_a_code() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register().never();
+
+    let e = register_use_case(repository, RegistrationMode::InviteOnly)
+        .execute(register_dto(Some("  ")))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn invite_only_registration_uses_up_the_hashed_code() {
+    let code_hash = TokenService::new(SECRET).hash(INVITE_CODE);
+
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register()
+        .withf(move |_, admission| admission.invite_code_hash.as_deref() == Some(code_hash.as_str()) && !admission.pending_approval)
+        .times(1)
+        .returning(|_, _| Ok(user(7, "alice")));
+
+    let registered = register_use_case(repository, RegistrationMode::InviteOnly)
+        .execute(register_dto(Some(INVITE_CODE)))
+        .await
+        .unwrap();
+
+    assert!(!registered.pending_approval);
+}
+
+#[tokio::test]
+async fn approval_required_registration_waits_without_an_invite() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register()
+        .withf(|_, admission| admission.invite_code_hash.is_none() && admission.pending_approval)
+        .times(1)
+        .returning(|_, _| Ok(user(7, "alice")));
+
+    let registered = register_use_case(repository, RegistrationMode::ApprovalRequired)
+        .execute(register_dto(None))
+        .await
+        .unwrap();
+
+    assert!(registered.pending_approval);
+}
+
+#[tokio::test]
+async fn approval_required_registration_with_an_invite_skips_approval() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register()
+        .withf(|_, admission| admission.invite_code_hash.is_some() && !admission.pending_approval)
+        .times(1)
+        .returning(|_, _| Ok(user(7, "alice")));
+
+    let registered = register_use_case(repository, RegistrationMode::ApprovalRequired)
+        .execute(register_dto(Some(INVITE_CODE)))
+        .await
+        .unwrap();
+
+    assert!(!registered.pending_approval);
+}
+
+#[test]
+fn parses_registration_modes() {
+    for mode in [
+        RegistrationMode::Open,
+        RegistrationMode::InviteOnly,
+        RegistrationMode::ApprovalRequired,
+        RegistrationMode::Closed,
+    ] {
+        assert_eq!(mode.as_str().parse::<RegistrationMode>(), Ok(mode));
+    }
+    assert!("invite".parse::<RegistrationMode>().is_err());
+}
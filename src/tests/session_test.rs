@@ -0,0 +1,150 @@
+// File: src/tests/session_test.rs
+
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use chrono::{Duration, Utc};
+
+use crate::application::use_cases::auth_use_cases::ValidateSessionUseCase;
+use crate::application::use_cases::session_use_cases::ManageSessionsUseCase;
+use crate::domain::entities::auth::Claims;
+use crate::domain::entities::session::Session;
+use crate::domain::repositories::auth_repository::{AuthRepository, MockAuthRepository};
+use crate::tests::fixtures::{claims, error_kind};
+
+const ALICE: i32 = 7;
+const BOB: i32 = 8;
+
+/// A stored session and whether it was revoked.
+struct StoredSession {
+    user_id: i32,
+    session: Session,
+    revoked: bool,
+}
+
+fn session(id: i32, token_id: &str) -> Session {
+    let now = Utc::now().naive_utc();
+    Session {
+        id,
+        token_id: token_id.to_string(),
+        user_agent: None,
+        ip_address: None,
+        expires_at: now + Duration::hours(1),
+        last_active_at: now,
+        created_at: now,
+        impersonated: false,
+        current: false,
+    }
+}
+
+/// Sessions kept in memory; revoked ones stop being found, like the database.
+fn repository(sessions: Arc<Mutex<Vec<StoredSession>>>) -> MockAuthRepository {
+    let mut repository = MockAuthRepository::new();
+
+    let active = sessions.clone();
+    repository.expect_find_active_session().returning(move |user_id, token_id| {
+        Ok(active.lock().unwrap().iter()
+            .find(|stored| stored.user_id == user_id && stored.session.token_id == token_id && !stored.revoked)
+            .map(|stored| session(stored.session.id, &stored.session.token_id)))
+    });
+    repository.expect_touch_session().returning(|_| Ok(()));
+    let listed = sessions.clone();
+    repository.expect_find_sessions().returning(move |user_id| {
+        Ok(listed.lock().unwrap().iter()
+            .filter(|stored| stored.user_id == user_id && !stored.revoked)
+            .map(|stored| session(stored.session.id, &stored.session.token_id))
+            .collect())
+    });
+    let revocable = sessions.clone();
+    repository.expect_revoke_session().returning(move |user_id, session_id| {
+        let mut sessions = revocable.lock().unwrap();
+        let stored = sessions.iter_mut()
+            .find(|stored| stored.user_id == user_id && stored.session.id == session_id && !stored.revoked);
+        Ok(stored.map(|stored| stored.revoked = true).is_some())
+    });
+    repository.expect_revoke_sessions().returning(move |user_id| {
+        for stored in sessions.lock().unwrap().iter_mut().filter(|stored| stored.user_id == user_id) {
+            stored.revoked = true;
+        }
+        Ok(())
+    });
+
+    repository
+}
+
+struct Fixture {
+    sessions: Arc<Mutex<Vec<StoredSession>>>,
+    validate: ValidateSessionUseCase<MockAuthRepository>,
+    manage: ManageSessionsUseCase<MockAuthRepository>,
+}
+
+impl Fixture {
+    /// Alice signed in on a laptop (session 1) and a phone (session 2); Bob on session 3.
+    fn new() -> Self {
+        let sessions = Arc::new(Mutex::new(vec![
+            StoredSession { user_id: ALICE, session: session(1, "laptop"), revoked: false },
+            StoredSession { user_id: ALICE, session: session(2, "phone"), revoked: false },
+            StoredSession { user_id: BOB, session: session(3, "bob"), revoked: false },
+        ]));
+
+        Self {
+            validate: ValidateSessionUseCase::new(repository(sessions.clone())),
+            manage: ManageSessionsUseCase::new(repository(sessions.clone())),
+            sessions,
+        }
+    }
+
+    async fn is_valid(&self, user_id: i32, token_id: &str) -> bool {
+        let claims = Claims { jti: token_id.to_string(), ..claims(user_id, &[]) };
+        self.validate.execute(&claims).await.unwrap()
+    }
+}
+
+#[tokio::test]
+async fn a_revoked_session_is_rejected_while_the_others_stay_valid() {
+    let fixture = Fixture::new();
+    assert!(fixture.is_valid(ALICE, "phone").await);
+
+    fixture.manage.revoke(ALICE, 2).await.unwrap();
+
+    assert!(!fixture.is_valid(ALICE, "phone").await);
+    assert!(fixture.is_valid(ALICE, "laptop").await);
+}
+
+#[tokio::test]
+async fn a_token_is_only_valid_for_the_user_it_was_issued_to() {
+    let fixture = Fixture::new();
+
+    assert!(!fixture.is_valid(BOB, "laptop").await);
+    assert!(!fixture.is_valid(ALICE, "unknown").await);
+}
+
+#[tokio::test]
+async fn other_users_sessions_cannot_be_revoked() {
+    let fixture = Fixture::new();
+
+    let result = fixture.manage.revoke(ALICE, 3).await;
+
+    assert_eq!(error_kind(&*result.unwrap_err()), Some(ErrorKind::NotFound));
+    assert!(fixture.is_valid(BOB, "bob").await);
+}
+
+#[tokio::test]
+async fn revoking_all_sessions_signs_every_device_out() {
+    let fixture = Fixture::new();
+
+    repository(fixture.sessions.clone()).revoke_sessions(ALICE).await.unwrap();
+
+    assert!(!fixture.is_valid(ALICE, "laptop").await);
+    assert!(!fixture.is_valid(ALICE, "phone").await);
+    assert!(fixture.is_valid(BOB, "bob").await);
+}
+
+#[tokio::test]
+async fn the_listing_marks_the_current_session() {
+    let fixture = Fixture::new();
+
+    let sessions = fixture.manage.list(ALICE, "laptop").await.unwrap();
+
+    let current: Vec<(i32, bool)> = sessions.iter().map(|session| (session.id, session.current)).collect();
+    assert_eq!(current, vec![(1, true), (2, false)]);
+}
@@ -0,0 +1,217 @@
+// File: src/tests/totp_test.rs
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+
+use crate::application::use_cases::login_attempt_use_cases::LOGIN_LOCKED;
+use crate::application::use_cases::mfa_use_cases::CompleteMfaLoginUseCase;
+use crate::domain::entities::auth::TokenPurpose;
+use crate::domain::entities::mfa::{MfaLoginDto, UserTotp};
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::totp;
+use crate::tests::fixtures::{access_tokens, user, MemoryLoginAttempts};
+
+const SECRET: &str = "totp-test-secret";
+/// The RFC 6238 SHA-1 seed, "12345678901234567890", in base32
+const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+const USER_ID: i32 = 7;
+const RECOVERY_CODE: &str = "abcde-fghij";
+
+#[test]
+fn codes_match_the_rfc_6238_sha1_vectors() {
+    // The RFC lists 8 digit codes; a 6 digit code is their last six digits
+    let vectors = [
+        (59, "287082"),
+        (1111111109, "081804"),
+        (1111111111, "050471"),
+        (1234567890, "005924"),
+        (2000000000, "279037"),
+        (20000000000, "353130"),
+    ];
+
+    for (unix_time, code) in vectors {
+        assert_eq!(totp::code_at(RFC_SECRET, unix_time).as_deref(), Some(code), "at {}", unix_time);
+        assert_eq!(totp::verify(RFC_SECRET, code, unix_time), Some(unix_time / 30), "at {}", unix_time);
+    }
+}
+
+#[test]
+fn base32_round_trips() {
+    assert_eq!(totp::base32_encode(b"foobar"), "MZXW6YTBOI");
+    assert_eq!(totp::base32_decode("MZXW6YTBOI"), Some(b"foobar".to_vec()));
+    assert_eq!(totp::base32_decode("mzxw6ytboi======"), Some(b"foobar".to_vec()));
+    assert_eq!(totp::base32_decode("MZXW1YTBOI"), None);
+
+    let data: Vec<u8> = (0..=255).collect();
+    for length in 0..=20 {
+        let encoded = totp::base32_encode(&data[..length]);
+        assert_eq!(totp::base32_decode(&encoded), Some(data[..length].to_vec()), "length {}", length);
+    }
+
+    let secret = totp::generate_secret().unwrap();
+    assert_eq!(totp::base32_decode(&secret).map(|key| key.len()), Some(20));
+}
+
+#[test]
+fn codes_from_the_neighbouring_steps_are_accepted() {
+    let unix_time = 1234567890;
+    let step = unix_time / 30;
+
+    assert_eq!(totp::verify(RFC_SECRET, "005924", unix_time - 30), Some(step));
+    assert_eq!(totp::verify(RFC_SECRET, "005924", unix_time + 30), Some(step));
+    assert_eq!(totp::verify(RFC_SECRET, "005924", unix_time - 60), None);
+    assert_eq!(totp::verify(RFC_SECRET, "005924", unix_time + 60), None);
+}
+
+#[test]
+fn malformed_codes_are_rejected() {
+    for code in ["", "05924", "0059245", "00592a", "-05924"] {
+        assert_eq!(totp::verify(RFC_SECRET, code, 1234567890), None, "{:?}", code);
+    }
+    assert_eq!(totp::verify(RFC_SECRET, " 005924 ", 1234567890), Some(1234567890 / 30));
+}
+
+/// The parts of the auth tables a second sign-in step touches, kept in memory.
+#[derive(Default)]
+struct MfaState {
+    challenges: Mutex<HashSet<String>>,
+    last_step: Mutex<Option<i64>>,
+    recovery_codes: Mutex<HashSet<String>>,
+}
+
+fn repository(state: Arc<MfaState>) -> MockAuthRepository {
+    let mut repository = MockAuthRepository::new();
+
+    let challenges = state.clone();
+    repository.expect_consume_token()
+        .withf(|purpose, _| *purpose == TokenPurpose::MfaChallenge)
+        .returning(move |_, hash| Ok(challenges.challenges.lock().unwrap().remove(&hash).then_some(USER_ID)));
+    repository.expect_find_totp().returning(|_| Ok(Some(UserTotp {
+        secret: RFC_SECRET.to_string(),
+        confirmed_at: Some(Utc::now().naive_utc()),
+    })));
+    // Like the database: a step is only accepted when it is newer than the last one used
+    let steps = state.clone();
+    repository.expect_record_totp_step().returning(move |_, step| {
+        let mut last_step = steps.last_step.lock().unwrap();
+        if last_step.is_some_and(|last| step <= last) {
+            return Ok(false);
+        }
+        *last_step = Some(step);
+        Ok(true)
+    });
+    let recovery_codes = state;
+    repository.expect_consume_recovery_code()
+        .returning(move |_, hash| Ok(recovery_codes.recovery_codes.lock().unwrap().remove(&hash)));
+    repository.expect_find_by_id().returning(|id| Ok(Some(user(id, "alice"))));
+    repository.expect_find_role_names().returning(|_| Ok(vec![]));
+    repository.expect_create_session().returning(|_| Ok(()));
+
+    repository
+}
+
+struct Fixture {
+    token_service: Arc<TokenService>,
+    state: Arc<MfaState>,
+    attempts: Arc<MemoryLoginAttempts>,
+    use_case: CompleteMfaLoginUseCase<MockAuthRepository, MemoryLoginAttempts>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let token_service = Arc::new(TokenService::new(SECRET));
+        let state = Arc::new(MfaState::default());
+        state.recovery_codes.lock().unwrap().insert(token_service.hash(RECOVERY_CODE));
+        let attempts = Arc::new(MemoryLoginAttempts::default());
+        let use_case = CompleteMfaLoginUseCase::new(
+            repository(state.clone()),
+            attempts.clone(),
+            token_service.clone(),
+            access_tokens(SECRET),
+        );
+
+        Self { token_service, state, attempts, use_case }
+    }
+
+    /// A challenge as issued after a correct password.
+    fn challenge(&self) -> String {
+        let token = self.token_service.generate(TokenPurpose::MfaChallenge).unwrap();
+        self.state.challenges.lock().unwrap().insert(self.token_service.hash(&token));
+        token
+    }
+
+    async fn login(&self, mfa_token: &str, code: &str) -> Result<(), String> {
+        self.use_case
+            .execute(MfaLoginDto { mfa_token: mfa_token.to_string(), code: code.to_string() }, &ClientInfo::default())
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn current_code() -> String {
+    totp::code_at(RFC_SECRET, Utc::now().timestamp()).unwrap()
+}
+
+#[tokio::test]
+async fn a_valid_code_completes_the_login() {
+    let fixture = Fixture::new();
+
+    fixture.login(&fixture.challenge(), &current_code()).await.unwrap();
+
+    assert_eq!(fixture.attempts.attempts(), vec![("alice".to_string(), true)]);
+}
+
+#[tokio::test]
+async fn a_challenge_can_only_be_used_once() {
+    let fixture = Fixture::new();
+    let challenge = fixture.challenge();
+
+    fixture.login(&challenge, &current_code()).await.unwrap();
+
+    assert_eq!(fixture.login(&challenge, RECOVERY_CODE).await.unwrap_err(), "Invalid or expired MFA token");
+}
+
+#[tokio::test]
+async fn a_wrong_code_spends_the_challenge() {
+    let fixture = Fixture::new();
+    let challenge = fixture.challenge();
+
+    assert_eq!(fixture.login(&challenge, "12345").await.unwrap_err(), "Invalid two-factor code");
+    assert_eq!(fixture.login(&challenge, &current_code()).await.unwrap_err(), "Invalid or expired MFA token");
+    assert_eq!(fixture.attempts.attempts(), vec![("alice".to_string(), false)]);
+}
+
+#[tokio::test]
+async fn repeated_wrong_codes_lock_the_account() {
+    let fixture = Fixture::new();
+
+    for _ in 0..5 {
+        assert_eq!(fixture.login(&fixture.challenge(), "12345").await.unwrap_err(), "Invalid two-factor code");
+    }
+
+    // Even the right code is refused until the lockout runs out
+    assert_eq!(fixture.login(&fixture.challenge(), &current_code()).await.unwrap_err(), LOGIN_LOCKED);
+}
+
+#[tokio::test]
+async fn a_code_cannot_be_replayed() {
+    let fixture = Fixture::new();
+    let code = current_code();
+
+    fixture.login(&fixture.challenge(), &code).await.unwrap();
+
+    assert_eq!(fixture.login(&fixture.challenge(), &code).await.unwrap_err(), "Invalid two-factor code");
+}
+
+#[tokio::test]
+async fn a_recovery_code_works_once() {
+    let fixture = Fixture::new();
+
+    fixture.login(&fixture.challenge(), " ABCDE-FGHIJ ").await.unwrap();
+
+    assert_eq!(fixture.login(&fixture.challenge(), RECOVERY_CODE).await.unwrap_err(), "Invalid two-factor code");
+}
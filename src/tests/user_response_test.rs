@@ -0,0 +1,63 @@
+// File: src/tests/user_response_test.rs
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::domain::entities::user::{AdminUserResponse, User, UserResponse};
+use crate::presentation::handlers::user_handlers::user_view;
+use crate::tests::fixtures::{self, claims, PASSWORD_HASH};
+
+fn user(id: i32) -> User {
+    User {
+        email_verified_at: Some(Utc::now().naive_utc()),
+        ..fixtures::user(id, "alice")
+    }
+}
+
+fn assert_no_hash(value: &Value) {
+    assert!(value.get("password").is_none());
+    assert!(!value.to_string().contains(PASSWORD_HASH));
+}
+
+#[test]
+fn public_response_has_only_id_and_username() {
+    let value = serde_json::to_value(UserResponse::from(user(1))).unwrap();
+
+    assert_no_hash(&value);
+    assert_eq!(value, serde_json::json!({ "id": 1, "username": "alice" }));
+}
+
+#[test]
+fn admin_response_omits_the_password_hash() {
+    let value = serde_json::to_value(AdminUserResponse::from(user(1))).unwrap();
+
+    assert_no_hash(&value);
+    assert_eq!(value["email"], "alice@example.com");
+    assert_eq!(value["email_verified"], true);
+}
+
+#[test]
+fn other_users_get_the_public_view() {
+    let value = serde_json::to_value(user_view(&claims(2, &["user"]), user(1))).unwrap();
+
+    assert_no_hash(&value);
+    assert!(value.get("email").is_none());
+}
+
+#[test]
+fn admins_and_the_user_themselves_get_the_full_view() {
+    for caller in [claims(2, &["admin"]), claims(1, &["user"])] {
+        let value = serde_json::to_value(user_view(&caller, user(1))).unwrap();
+
+        assert_no_hash(&value);
+        assert_eq!(value["email"], "alice@example.com");
+    }
+}
+
+#[test]
+fn debug_output_redacts_the_password_hash() {
+    let debug = format!("{:?}", user(1));
+
+    assert!(!debug.contains(PASSWORD_HASH));
+    assert!(debug.contains("[REDACTED]"));
+}
@@ -0,0 +1,240 @@
+// File: src/tests/user_test.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::application::use_cases::auth_use_cases::SendVerificationEmailUseCase;
+use crate::application::use_cases::user_use_cases::{CreateUserUseCase, DeleteUserUseCase, UpdateUserUseCase};
+use crate::domain::entities::audit::{AuditAction, AuditOutcome};
+use crate::domain::entities::auth::TokenPurpose;
+use crate::domain::entities::session::ClientInfo;
+use crate::domain::entities::user::{CreateUserDto, UpdateUserDto, User};
+use crate::domain::repositories::audit_repository::MockAuditRepository;
+use crate::domain::repositories::auth_repository::MockAuthRepository;
+use crate::domain::repositories::user_repository::MockUserRepository;
+use crate::domain::services::token_service::TokenService;
+use crate::domain::services::validation::PasswordPolicy;
+use crate::infrastructure::mail::memory_mailer::MemoryMailer;
+use crate::tests::fixtures::{error_kind, user, StubRoleRepository};
+
+const USER_ID: i32 = 7;
+const OTHER_ID: i32 = 8;
+const ADMIN_ID: i32 = 1;
+
+fn roles() -> Arc<StubRoleRepository> {
+    Arc::new(StubRoleRepository::default().with_role(ADMIN_ID, "admin"))
+}
+
+fn accepting_audit() -> Arc<MockAuditRepository> {
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event().returning(|_| Ok(()));
+    Arc::new(audit)
+}
+
+fn update_use_case(
+    repository: MockUserRepository,
+    audit: Arc<MockAuditRepository>,
+    mailer: Arc<MemoryMailer>,
+) -> UpdateUserUseCase<MockUserRepository, MockAuthRepository, StubRoleRepository> {
+    let mut auth_repository = MockAuthRepository::new();
+    auth_repository.expect_revoke_tokens().returning(|_, _| Ok(()));
+    auth_repository.expect_create_token()
+        .withf(|_, purpose, _, _| *purpose == TokenPurpose::EmailVerification)
+        .returning(|_, _, _, _| Ok(()));
+    let send_verification_email = SendVerificationEmailUseCase::new(
+        auth_repository,
+        Arc::new(TokenService::new("user-test-secret")),
+        mailer,
+        "http://localhost:8080".to_string(),
+    );
+
+    UpdateUserUseCase::new(repository, roles(), Arc::new(send_verification_email), audit)
+}
+
+fn email_change() -> UpdateUserDto {
+    UpdateUserDto {
+        username: None,
+        email: Some("attacker@example.com".to_string()),
+    }
+}
+
+fn create_dto() -> CreateUserDto {
+    CreateUserDto {
+        username: "carol".to_string(),
+        email: "carol@example.com".to_string(),
+        password: "correct horse battery".to_string(),
+    }
+}
+
+fn create_use_case(repository: MockAuthRepository) -> CreateUserUseCase<MockAuthRepository, StubRoleRepository> {
+    CreateUserUseCase::new(repository, roles(), Arc::new(PasswordPolicy::default()), accepting_audit())
+}
+
+#[tokio::test]
+async fn admins_create_users_through_registration() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register()
+        .withf(|dto, admission| {
+            dto.username == "carol"
+                && dto.password == "correct horse battery"
+                && admission.invite_code_hash.is_none()
+                && !admission.pending_approval
+        })
+        .times(1)
+        .returning(|dto, _| Ok(user(9, &dto.username)));
+
+    let created = create_use_case(repository)
+        .execute(ADMIN_ID, create_dto(), &ClientInfo::default())
+        .await
+        .unwrap();
+
+    assert_eq!(created.username, "carol");
+}
+
+#[tokio::test]
+async fn only_admins_can_create_users() {
+    let mut repository = MockAuthRepository::new();
+    repository.expect_register().never();
+
+    let e = create_use_case(repository)
+        .execute(USER_ID, create_dto(), &ClientInfo::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn users_can_update_their_own_profile() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(user(id, "alice")));
+    repository.expect_update()
+        .times(1)
+        .returning(|id, _| Ok(user(id, "alice")));
+
+    let use_case = update_use_case(repository, accepting_audit(), Arc::new(MemoryMailer::new()));
+    let updated = use_case.execute(USER_ID, USER_ID, email_change(), &ClientInfo::default()).await.unwrap();
+
+    assert_eq!(updated.id, USER_ID);
+}
+
+#[tokio::test]
+async fn users_cannot_update_someone_else() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_update().never();
+
+    let mut audit = MockAuditRepository::new();
+    audit.expect_record_event()
+        .withf(|event| {
+            event.action == AuditAction::UserUpdated
+                && event.outcome == AuditOutcome::Failure
+                && event.target_user_id == Some(OTHER_ID)
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let use_case = update_use_case(repository, Arc::new(audit), Arc::new(MemoryMailer::new()));
+    let e = use_case.execute(USER_ID, OTHER_ID, email_change(), &ClientInfo::default()).await.unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn admins_can_update_anyone() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(user(id, "bob")));
+    repository.expect_update()
+        .times(1)
+        .returning(|id, _| Ok(user(id, "bob")));
+
+    let use_case = update_use_case(repository, accepting_audit(), Arc::new(MemoryMailer::new()));
+
+    assert!(use_case.execute(ADMIN_ID, OTHER_ID, email_change(), &ClientInfo::default()).await.is_ok());
+}
+
+#[tokio::test]
+async fn a_new_email_address_is_sent_a_verification_link() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(User {
+        email_verified_at: Some(Utc::now().naive_utc()),
+        ..user(id, "alice")
+    }));
+    repository.expect_update().returning(|id, dto| Ok(User {
+        email: dto.email.unwrap(),
+        ..user(id, "alice")
+    }));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let updated = update_use_case(repository, accepting_audit(), mailer.clone())
+        .execute(USER_ID, USER_ID, email_change(), &ClientInfo::default())
+        .await
+        .unwrap();
+
+    assert_eq!(updated.email_verified_at, None);
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "attacker@example.com");
+    assert!(sent[0].body.contains("/api/v1/auth/verify-email?token="));
+}
+
+#[tokio::test]
+async fn keeping_the_email_address_sends_no_link() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(user(id, "alice")));
+    repository.expect_update().returning(|id, dto| Ok(User {
+        username: dto.username.unwrap_or_else(|| "alice".to_string()),
+        email: dto.email.unwrap_or_else(|| "alice@example.com".to_string()),
+        ..user(id, "alice")
+    }));
+    let mailer = Arc::new(MemoryMailer::new());
+    let use_case = update_use_case(repository, accepting_audit(), mailer.clone());
+
+    let rename = UpdateUserDto { username: Some("alicia".to_string()), email: None };
+    use_case.execute(USER_ID, USER_ID, rename, &ClientInfo::default()).await.unwrap();
+    let recase = UpdateUserDto { username: None, email: Some("Alice@Example.com".to_string()) };
+    use_case.execute(USER_ID, USER_ID, recase, &ClientInfo::default()).await.unwrap();
+
+    assert!(mailer.sent().is_empty());
+}
+
+#[tokio::test]
+async fn a_taken_email_address_is_a_conflict() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_find_by_id().returning(|id| Ok(user(id, "alice")));
+    repository.expect_update().returning(|_, _| Err(Box::new(std::io::Error::new(
+        ErrorKind::AlreadyExists,
+        "An account with this email address already exists",
+    ))));
+    let mailer = Arc::new(MemoryMailer::new());
+
+    let e = update_use_case(repository, accepting_audit(), mailer.clone())
+        .execute(USER_ID, USER_ID, email_change(), &ClientInfo::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::AlreadyExists));
+    assert!(mailer.sent().is_empty());
+}
+
+#[tokio::test]
+async fn users_cannot_delete_someone_else() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_delete().never();
+
+    let use_case = DeleteUserUseCase::new(repository, roles(), accepting_audit());
+    let e = use_case.execute(USER_ID, OTHER_ID, &ClientInfo::default()).await.unwrap_err();
+
+    assert_eq!(error_kind(&*e), Some(ErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn users_and_admins_can_delete_accounts_they_may_manage() {
+    let mut repository = MockUserRepository::new();
+    repository.expect_delete().times(2).returning(|_| Ok(()));
+
+    let use_case = DeleteUserUseCase::new(repository, roles(), accepting_audit());
+
+    assert!(use_case.execute(USER_ID, USER_ID, &ClientInfo::default()).await.is_ok());
+    assert!(use_case.execute(ADMIN_ID, OTHER_ID, &ClientInfo::default()).await.is_ok());
+}
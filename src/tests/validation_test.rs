@@ -0,0 +1,85 @@
+// File: src/tests/validation_test.rs
+
+use crate::domain::services::validation::{
+    validate_email, validate_name, validate_username, FieldError, PasswordPolicy, Validator,
+};
+
+#[test]
+fn accepts_ordinary_usernames() {
+    for username in ["alice", "bob_smith", "j.doe-42", "abc"] {
+        assert!(validate_username(username).is_ok(), "{} should be valid", username);
+    }
+}
+
+#[test]
+fn rejects_bad_usernames() {
+    let too_long = "a".repeat(33);
+    for username in ["", "ab", too_long.as_str(), "alice@example.com", "has space", "_leading", "émile"] {
+        assert!(validate_username(username).is_err(), "{:?} should be invalid", username);
+    }
+}
+
+#[test]
+fn accepts_ordinary_emails() {
+    for email in ["alice@example.com", "first.last+tag@mail.example.co.uk", "x@a-b.io"] {
+        assert!(validate_email(email).is_ok(), "{} should be valid", email);
+    }
+}
+
+#[test]
+fn rejects_malformed_emails() {
+    let too_long = format!("{}@example.com", "a".repeat(250));
+    for email in [
+        "",
+        "alice",
+        "@example.com",
+        "alice@",
+        "alice@localhost",
+        "alice@exa mple.com",
+        "alice@-example.com",
+        "al..ice@example.com",
+        ".alice@example.com",
+        too_long.as_str(),
+    ] {
+        assert!(validate_email(email).is_err(), "{:?} should be invalid", email);
+    }
+}
+
+#[test]
+fn limits_name_length() {
+    assert!(validate_name("Ada").is_ok());
+    assert!(validate_name(&"a".repeat(100)).is_ok());
+    assert!(validate_name(&"a".repeat(101)).is_err());
+    assert!(validate_name("Ada\u{0}").is_err());
+}
+
+#[test]
+fn password_policy_enforces_length() {
+    let policy = PasswordPolicy::new(10, Vec::new());
+
+    assert!(policy.validate("short").is_err());
+    assert!(policy.validate("long enough pw").is_ok());
+    assert!(policy.validate(&"a".repeat(73)).is_err());
+}
+
+#[test]
+fn password_policy_refuses_breached_passwords() {
+    let policy = PasswordPolicy::new(8, vec!["Password123".to_string(), "qwertyuiop".to_string()]);
+
+    assert!(policy.validate("password123").is_err());
+    assert!(policy.validate("QWERTYUIOP").is_err());
+    assert!(policy.validate("correct horse battery").is_ok());
+}
+
+#[test]
+fn validator_reports_every_failing_field() {
+    let result = Validator::new()
+        .check("username", validate_username("a"))
+        .check("email", validate_email("alice@example.com"))
+        .check("password", PasswordPolicy::default().validate("x"))
+        .check_optional("first_name", None::<&str>, validate_name)
+        .finish();
+
+    let fields = result.unwrap_err().errors.into_iter().map(|FieldError { field, .. }| field).collect::<Vec<_>>();
+    assert_eq!(fields, vec!["username", "password"]);
+}
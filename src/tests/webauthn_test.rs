@@ -0,0 +1,270 @@
+// File: src/tests/webauthn_test.rs
+
+use ring::digest;
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use serde_json::json;
+
+use crate::domain::services::webauthn::{
+    client_data_challenge, verify_assertion, verify_registration, RelyingParty, COSE_ALG_ES256,
+};
+
+const RP_ID: &str = "example.com";
+const ORIGIN: &str = "https://example.com";
+const CHALLENGE: &str = "registration-challenge-0123456789";
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+fn relying_party() -> RelyingParty {
+    RelyingParty {
+        id: RP_ID.to_string(),
+        name: "Example".to_string(),
+        origin: ORIGIN.to_string(),
+    }
+}
+
+fn cbor_head(major: u8, value: u64) -> Vec<u8> {
+    match value {
+        0..=23 => vec![(major << 5) | value as u8],
+        24..=0xff => vec![(major << 5) | 24, value as u8],
+        0x100..=0xffff => [vec![(major << 5) | 25], (value as u16).to_be_bytes().to_vec()].concat(),
+        _ => [vec![(major << 5) | 26], (value as u32).to_be_bytes().to_vec()].concat(),
+    }
+}
+
+fn cbor_int(value: i64) -> Vec<u8> {
+    if value >= 0 {
+        cbor_head(0, value as u64)
+    } else {
+        cbor_head(1, (-1 - value) as u64)
+    }
+}
+
+fn cbor_bytes(bytes: &[u8]) -> Vec<u8> {
+    [cbor_head(2, bytes.len() as u64), bytes.to_vec()].concat()
+}
+
+fn cbor_text(text: &str) -> Vec<u8> {
+    [cbor_head(3, text.len() as u64), text.as_bytes().to_vec()].concat()
+}
+
+fn cbor_map(entries: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<u8> {
+    let mut encoded = cbor_head(5, entries.len() as u64);
+    for (key, value) in entries {
+        encoded.extend(key);
+        encoded.extend(value);
+    }
+    encoded
+}
+
+/// A software authenticator holding one P-256 credential, standing in for a security key
+/// or platform authenticator.
+struct SoftAuthenticator {
+    rng: SystemRandom,
+    key_pair: EcdsaKeyPair,
+    credential_id: Vec<u8>,
+    sign_count: u32,
+    flags: u8,
+}
+
+impl SoftAuthenticator {
+    fn new() -> Self {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+
+        Self {
+            rng,
+            key_pair,
+            credential_id: b"soft-authenticator-credential".to_vec(),
+            sign_count: 0,
+            flags: FLAG_USER_PRESENT | FLAG_USER_VERIFIED,
+        }
+    }
+
+    fn cose_key(&self) -> Vec<u8> {
+        // Uncompressed point: 0x04 || x || y
+        let point = self.key_pair.public_key().as_ref();
+        cbor_map(vec![
+            (cbor_int(1), cbor_int(2)),
+            (cbor_int(3), cbor_int(COSE_ALG_ES256)),
+            (cbor_int(-1), cbor_int(1)),
+            (cbor_int(-2), cbor_bytes(&point[1..33])),
+            (cbor_int(-3), cbor_bytes(&point[33..65])),
+        ])
+    }
+
+    fn authenticator_data(&self, rp_id: &str, attested: bool) -> Vec<u8> {
+        let mut data = digest::digest(&digest::SHA256, rp_id.as_bytes()).as_ref().to_vec();
+        data.push(if attested { self.flags | FLAG_ATTESTED_CREDENTIAL_DATA } else { self.flags });
+        data.extend(self.sign_count.to_be_bytes());
+
+        if attested {
+            data.extend([0u8; 16]);
+            data.extend((self.credential_id.len() as u16).to_be_bytes());
+            data.extend(&self.credential_id);
+            data.extend(self.cose_key());
+        }
+        data
+    }
+
+    fn client_data(ceremony: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        json!({ "type": ceremony, "challenge": challenge, "origin": origin, "crossOrigin": false })
+            .to_string()
+            .into_bytes()
+    }
+
+    /// `navigator.credentials.create()`: returns the client data JSON and attestation object.
+    fn create(&self, rp_id: &str, origin: &str, challenge: &str) -> (Vec<u8>, Vec<u8>) {
+        let attestation_object = cbor_map(vec![
+            (cbor_text("fmt"), cbor_text("none")),
+            (cbor_text("attStmt"), cbor_map(Vec::new())),
+            (cbor_text("authData"), cbor_bytes(&self.authenticator_data(rp_id, true))),
+        ]);
+        (Self::client_data("webauthn.create", challenge, origin), attestation_object)
+    }
+
+    /// `navigator.credentials.get()`: returns the client data JSON, authenticator data and signature.
+    fn get(&mut self, rp_id: &str, origin: &str, challenge: &str) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        self.sign_count += 1;
+
+        let client_data_json = Self::client_data("webauthn.get", challenge, origin);
+        let authenticator_data = self.authenticator_data(rp_id, false);
+        let client_data_hash = digest::digest(&digest::SHA256, &client_data_json);
+        let signature = self.key_pair
+            .sign(&self.rng, &[authenticator_data.as_slice(), client_data_hash.as_ref()].concat())
+            .unwrap();
+
+        (client_data_json, authenticator_data, signature.as_ref().to_vec())
+    }
+}
+
+#[test]
+fn test_registration_and_assertion_with_software_authenticator() {
+    let relying_party = relying_party();
+    let mut authenticator = SoftAuthenticator::new();
+
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, ORIGIN, CHALLENGE);
+    assert_eq!(client_data_challenge(&client_data_json).unwrap(), CHALLENGE);
+
+    let credential = verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object)
+        .expect("Registration should be accepted");
+    assert_eq!(credential.credential_id, authenticator.credential_id);
+    assert_eq!(credential.algorithm, COSE_ALG_ES256);
+    assert_eq!(credential.sign_count, 0);
+
+    let (client_data_json, authenticator_data, signature) = authenticator.get(RP_ID, ORIGIN, "login-challenge");
+    let sign_count = verify_assertion(
+        &relying_party,
+        "login-challenge",
+        &credential.public_key,
+        credential.sign_count,
+        &client_data_json,
+        &authenticator_data,
+        &signature,
+    )
+        .expect("Assertion should be accepted");
+    assert_eq!(sign_count, 1);
+}
+
+#[test]
+fn test_registration_rejects_other_origin_and_relying_party() {
+    let relying_party = relying_party();
+    let authenticator = SoftAuthenticator::new();
+
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, "https://evil.example", CHALLENGE);
+    assert!(verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object).is_err());
+
+    let (client_data_json, attestation_object) = authenticator.create("evil.example", ORIGIN, CHALLENGE);
+    assert!(verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object).is_err());
+}
+
+#[test]
+fn test_registration_rejects_other_challenge() {
+    let authenticator = SoftAuthenticator::new();
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, ORIGIN, CHALLENGE);
+
+    let result = verify_registration(&relying_party(), "another-challenge", &client_data_json, &attestation_object);
+    assert!(result.is_err(), "A different challenge should be rejected");
+}
+
+#[test]
+fn test_registration_requires_user_verification() {
+    let mut authenticator = SoftAuthenticator::new();
+    authenticator.flags = FLAG_USER_PRESENT;
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, ORIGIN, CHALLENGE);
+
+    let result = verify_registration(&relying_party(), CHALLENGE, &client_data_json, &attestation_object);
+    assert!(result.is_err(), "Registration without user verification should be rejected");
+}
+
+#[test]
+fn test_assertion_rejects_tampered_signature() {
+    let relying_party = relying_party();
+    let mut authenticator = SoftAuthenticator::new();
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, ORIGIN, CHALLENGE);
+    let credential = verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object).unwrap();
+
+    let (client_data_json, mut authenticator_data, signature) = authenticator.get(RP_ID, ORIGIN, "login-challenge");
+    // Claim a much higher counter than the one that was signed
+    let last = authenticator_data.len() - 1;
+    authenticator_data[last] ^= 0x80;
+
+    let result = verify_assertion(
+        &relying_party,
+        "login-challenge",
+        &credential.public_key,
+        credential.sign_count,
+        &client_data_json,
+        &authenticator_data,
+        &signature,
+    );
+    assert!(result.is_err(), "A modified assertion should be rejected");
+}
+
+#[test]
+fn test_assertion_rejects_key_of_another_credential() {
+    let relying_party = relying_party();
+    let registered = SoftAuthenticator::new();
+    let (client_data_json, attestation_object) = registered.create(RP_ID, ORIGIN, CHALLENGE);
+    let credential = verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object).unwrap();
+
+    let mut other = SoftAuthenticator::new();
+    let (client_data_json, authenticator_data, signature) = other.get(RP_ID, ORIGIN, "login-challenge");
+
+    let result = verify_assertion(
+        &relying_party,
+        "login-challenge",
+        &credential.public_key,
+        credential.sign_count,
+        &client_data_json,
+        &authenticator_data,
+        &signature,
+    );
+    assert!(result.is_err(), "A signature from another key should be rejected");
+}
+
+#[test]
+fn test_assertion_rejects_counter_that_did_not_increase() {
+    let relying_party = relying_party();
+    let mut authenticator = SoftAuthenticator::new();
+    let (client_data_json, attestation_object) = authenticator.create(RP_ID, ORIGIN, CHALLENGE);
+    let credential = verify_registration(&relying_party, CHALLENGE, &client_data_json, &attestation_object).unwrap();
+
+    let (client_data_json, authenticator_data, signature) = authenticator.get(RP_ID, ORIGIN, "login-challenge");
+
+    // The server has already seen counter 5, as if a clone of the key had been used
+    let result = verify_assertion(
+        &relying_party,
+        "login-challenge",
+        &credential.public_key,
+        5,
+        &client_data_json,
+        &authenticator_data,
+        &signature,
+    );
+    assert!(result.is_err(), "A counter that went backwards should be rejected");
+}
+